@@ -832,6 +832,24 @@ impl Step for Assemble {
             let dst = libdir.parent().unwrap().join("bin");
             t!(fs::create_dir_all(&dst));
             builder.copy(&lld_install.join("bin").join(&src_exe), &dst.join(&dst_exe));
+            // `-C link-self-contained` makes the `cc` driver link with `rust-lld` through
+            // `-fuse-ld=lld`, which looks for an `ld.lld` executable in the `-B` directories.
+            let gcc_ld_dir = dst.join("gcc-ld");
+            t!(fs::create_dir_all(&gcc_ld_dir));
+            builder.copy(
+                &lld_install.join("bin").join(&src_exe),
+                &gcc_ld_dir.join(exe("ld.lld", &target_compiler.host)),
+            );
+        }
+
+        // `-C split-debuginfo=packed` packages the `.dwo` files with the `llvm-dwp` next to
+        // `llvm-config`, which isn't part of an external LLVM installation in every case.
+        let llvm_config = builder.ensure(native::Llvm { target: target_compiler.host });
+        let llvm_dwp = llvm_config.with_file_name(exe("llvm-dwp", &target_compiler.host));
+        if llvm_dwp.exists() {
+            let dst = libdir.parent().unwrap().join("bin");
+            t!(fs::create_dir_all(&dst));
+            builder.copy(&llvm_dwp, &dst.join(exe("llvm-dwp", &target_compiler.host)));
         }
 
         // Ensure that `libLLVM.so` ends up in the newly build compiler directory,
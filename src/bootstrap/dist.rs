@@ -529,12 +529,24 @@ impl Step for Rustc {
             // Copy over lld if it's there
             if builder.config.lld_enabled {
                 let exe = exe("rust-lld", &compiler.host);
-                let src =
-                    builder.sysroot_libdir(compiler, host).parent().unwrap().join("bin").join(&exe);
+                let src_dir = builder.sysroot_libdir(compiler, host).parent().unwrap().join("bin");
                 // for the rationale about this rename check `compile::copy_lld_to_sysroot`
-                let dst = image.join("lib/rustlib").join(&*host).join("bin").join(&exe);
-                t!(fs::create_dir_all(&dst.parent().unwrap()));
-                builder.copy(&src, &dst);
+                let dst_dir = image.join("lib/rustlib").join(&*host).join("bin");
+                t!(fs::create_dir_all(&dst_dir));
+                builder.copy(&src_dir.join(&exe), &dst_dir.join(&exe));
+                // The `ld.lld` that `-C link-self-contained` runs through the `cc` driver.
+                let gcc_ld_exe = Path::new("gcc-ld").join(exe("ld.lld", &compiler.host));
+                t!(fs::create_dir_all(&dst_dir.join("gcc-ld")));
+                builder.copy(&src_dir.join(&gcc_ld_exe), &dst_dir.join(&gcc_ld_exe));
+            }
+
+            // Copy over llvm-dwp if it's there, for `-C split-debuginfo=packed`
+            let exe = exe("llvm-dwp", &compiler.host);
+            let src_dir = builder.sysroot_libdir(compiler, host).parent().unwrap().join("bin");
+            if src_dir.join(&exe).exists() {
+                let dst_dir = image.join("lib/rustlib").join(&*host).join("bin");
+                t!(fs::create_dir_all(&dst_dir));
+                builder.copy(&src_dir.join(&exe), &dst_dir.join(&exe));
             }
 
             // Man pages
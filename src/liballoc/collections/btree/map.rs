@@ -6,7 +6,7 @@ use core::iter::{FromIterator, FusedIterator, Peekable};
 use core::marker::PhantomData;
 use core::mem::{self, ManuallyDrop};
 use core::ops::Bound::{Excluded, Included, Unbounded};
-use core::ops::{Index, RangeBounds};
+use core::ops::{Bound, Index, RangeBounds};
 use core::{fmt, ptr};
 
 use super::node::{self, marker, ForceResult::*, Handle, InsertResult::*, NodeRef};
@@ -481,6 +481,69 @@ impl<K: Debug + Ord, V: Debug> Debug for OccupiedEntry<'_, K, V> {
     }
 }
 
+/// A cursor over a `BTreeMap`.
+///
+/// A `Cursor` is like an iterator, except that it can freely seek back-and-forth.
+///
+/// Cursors always point to an element in the tree, and index in a logically circular way.
+/// To accommodate this, there is a "ghost" non-element that yields `None` between the last and
+/// first elements of the tree.
+///
+/// A `Cursor` is created with the [`BTreeMap::lower_bound`] and [`BTreeMap::upper_bound`]
+/// methods.
+///
+/// [`BTreeMap::lower_bound`]: struct.BTreeMap.html#method.lower_bound
+/// [`BTreeMap::upper_bound`]: struct.BTreeMap.html#method.upper_bound
+#[unstable(feature = "btree_cursors", issue = "none")]
+pub struct Cursor<'a, K: 'a, V: 'a> {
+    current: Option<Handle<NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal>, marker::KV>>,
+    root: Option<&'a node::Root<K, V>>,
+}
+
+#[unstable(feature = "btree_cursors", issue = "none")]
+impl<K, V> Clone for Cursor<'_, K, V> {
+    fn clone(&self) -> Self {
+        Cursor { current: self.current, root: self.root }
+    }
+}
+
+#[unstable(feature = "btree_cursors", issue = "none")]
+impl<K: Debug, V: Debug> Debug for Cursor<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Cursor").field(&self.key_value()).finish()
+    }
+}
+
+/// A cursor over a `BTreeMap` with editing operations.
+///
+/// A `Cursor` is like an iterator, except that it can freely seek back-and-forth, and can
+/// safely mutate the tree during iteration. This is because the lifetime of its yielded
+/// references is tied to its own lifetime, instead of just the underlying tree. This means
+/// cursors cannot yield multiple elements at once.
+///
+/// Cursors always point to an element in the tree, and index in a logically circular way.
+/// To accommodate this, there is a "ghost" non-element that yields `None` between the last and
+/// first elements of the tree.
+///
+/// A `CursorMut` is created with the [`BTreeMap::lower_bound_mut`] and
+/// [`BTreeMap::upper_bound_mut`] methods.
+///
+/// [`BTreeMap::lower_bound_mut`]: struct.BTreeMap.html#method.lower_bound_mut
+/// [`BTreeMap::upper_bound_mut`]: struct.BTreeMap.html#method.upper_bound_mut
+#[unstable(feature = "btree_cursors", issue = "none")]
+pub struct CursorMut<'a, K: 'a, V: 'a> {
+    current: Option<Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::KV>>,
+    root: &'a mut Option<node::Root<K, V>>,
+    length: &'a mut usize,
+}
+
+#[unstable(feature = "btree_cursors", issue = "none")]
+impl<K: Debug, V: Debug> Debug for CursorMut<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CursorMut").field(&self.key_value()).finish()
+    }
+}
+
 // An iterator for merging two sorted sequences into one
 struct MergeIter<K, V, I: Iterator<Item = (K, V)>> {
     left: Peekable<I>,
@@ -1069,6 +1132,170 @@ impl<K: Ord, V> BTreeMap<K, V> {
         }
     }
 
+    /// Returns a [`Cursor`] pointing at the first element that is above the
+    /// given bound.
+    ///
+    /// If no such element exists then a cursor pointing at the "ghost"
+    /// non-element is returned.
+    ///
+    /// Passing [`Unbounded`] will return a cursor pointing at the first
+    /// element of the map.
+    ///
+    /// [`Cursor`]: struct.Cursor.html
+    /// [`Unbounded`]: ../../std/ops/enum.Bound.html#variant.Unbounded
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeMap;
+    /// use std::ops::Bound;
+    ///
+    /// let mut a = BTreeMap::new();
+    /// a.insert(1, "a");
+    /// a.insert(2, "b");
+    /// a.insert(3, "c");
+    /// a.insert(4, "c");
+    /// let cursor = a.lower_bound(Bound::Excluded(&2));
+    /// assert_eq!(cursor.key(), Some(&3));
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn lower_bound<Q: ?Sized>(&self, bound: Bound<&Q>) -> Cursor<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let root = self.root.as_ref();
+        Cursor { current: root.and_then(|root| lower_bound_kv(root.as_ref(), bound)), root }
+    }
+
+    /// Returns a [`CursorMut`] pointing at the first element that is above the
+    /// given bound.
+    ///
+    /// If no such element exists then a cursor pointing at the "ghost"
+    /// non-element is returned.
+    ///
+    /// Passing [`Unbounded`] will return a cursor pointing at the first
+    /// element of the map.
+    ///
+    /// [`CursorMut`]: struct.CursorMut.html
+    /// [`Unbounded`]: ../../std/ops/enum.Bound.html#variant.Unbounded
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeMap;
+    /// use std::ops::Bound;
+    ///
+    /// let mut a = BTreeMap::new();
+    /// a.insert(1, "a");
+    /// a.insert(2, "b");
+    /// a.insert(3, "c");
+    /// a.insert(4, "c");
+    /// let cursor = a.lower_bound_mut(Bound::Excluded(&2));
+    /// assert_eq!(cursor.key(), Some(&3));
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn lower_bound_mut<Q: ?Sized>(&mut self, bound: Bound<&Q>) -> CursorMut<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let mut cursor =
+            CursorMut { current: None, root: &mut self.root, length: &mut self.length };
+        cursor.current = cursor.root_node().and_then(|root| lower_bound_kv(root, bound));
+        cursor
+    }
+
+    /// Returns a [`Cursor`] pointing at the last element that is below the
+    /// given bound.
+    ///
+    /// If no such element exists then a cursor pointing at the "ghost"
+    /// non-element is returned.
+    ///
+    /// Passing [`Unbounded`] will return a cursor pointing at the last
+    /// element of the map.
+    ///
+    /// [`Cursor`]: struct.Cursor.html
+    /// [`Unbounded`]: ../../std/ops/enum.Bound.html#variant.Unbounded
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeMap;
+    /// use std::ops::Bound;
+    ///
+    /// let mut a = BTreeMap::new();
+    /// a.insert(1, "a");
+    /// a.insert(2, "b");
+    /// a.insert(3, "c");
+    /// a.insert(4, "c");
+    /// let cursor = a.upper_bound(Bound::Excluded(&3));
+    /// assert_eq!(cursor.key(), Some(&2));
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn upper_bound<Q: ?Sized>(&self, bound: Bound<&Q>) -> Cursor<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let root = self.root.as_ref();
+        Cursor { current: root.and_then(|root| upper_bound_kv(root.as_ref(), bound)), root }
+    }
+
+    /// Returns a [`CursorMut`] pointing at the last element that is below the
+    /// given bound.
+    ///
+    /// If no such element exists then a cursor pointing at the "ghost"
+    /// non-element is returned.
+    ///
+    /// Passing [`Unbounded`] will return a cursor pointing at the last
+    /// element of the map.
+    ///
+    /// [`CursorMut`]: struct.CursorMut.html
+    /// [`Unbounded`]: ../../std/ops/enum.Bound.html#variant.Unbounded
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeMap;
+    /// use std::ops::Bound;
+    ///
+    /// let mut a = BTreeMap::new();
+    /// a.insert(1, "a");
+    /// a.insert(2, "b");
+    /// a.insert(3, "c");
+    /// a.insert(4, "c");
+    /// let cursor = a.upper_bound_mut(Bound::Excluded(&3));
+    /// assert_eq!(cursor.key(), Some(&2));
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn upper_bound_mut<Q: ?Sized>(&mut self, bound: Bound<&Q>) -> CursorMut<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let mut cursor =
+            CursorMut { current: None, root: &mut self.root, length: &mut self.length };
+        cursor.current = cursor.root_node().and_then(|root| upper_bound_kv(root, bound));
+        cursor
+    }
+
     fn from_sorted_iter<I: Iterator<Item = (K, V)>>(&mut self, iter: I) {
         self.ensure_root_is_owned();
         let mut cur_node = self.root.as_mut().unwrap().as_mut().last_leaf_edge().into_node();
@@ -2077,6 +2304,52 @@ where
     }
 }
 
+/// Finds the first KV above `bound`, or `None` if all KVs are at or below it.
+fn lower_bound_kv<BorrowType, K, V, Q: ?Sized>(
+    root: NodeRef<BorrowType, K, V, marker::LeafOrInternal>,
+    bound: Bound<&Q>,
+) -> Option<Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV>>
+where
+    Q: Ord,
+    K: Borrow<Q>,
+{
+    let edge = match bound {
+        Included(key) => match search::search_tree(root, key) {
+            Found(kv) => return Some(kv),
+            GoDown(edge) => edge,
+        },
+        Excluded(key) => match search::search_tree(root, key) {
+            Found(kv) => kv.next_leaf_edge(),
+            GoDown(edge) => edge,
+        },
+        Unbounded => root.first_leaf_edge(),
+    };
+    edge.next_kv().ok()
+}
+
+/// Finds the last KV below `bound`, or `None` if all KVs are at or above it.
+fn upper_bound_kv<BorrowType, K, V, Q: ?Sized>(
+    root: NodeRef<BorrowType, K, V, marker::LeafOrInternal>,
+    bound: Bound<&Q>,
+) -> Option<Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV>>
+where
+    Q: Ord,
+    K: Borrow<Q>,
+{
+    let edge = match bound {
+        Included(key) => match search::search_tree(root, key) {
+            Found(kv) => return Some(kv),
+            GoDown(edge) => edge,
+        },
+        Excluded(key) => match search::search_tree(root, key) {
+            Found(kv) => kv.next_back_leaf_edge(),
+            GoDown(edge) => edge,
+        },
+        Unbounded => root.last_leaf_edge(),
+    };
+    edge.next_back_kv().ok()
+}
+
 /// Equivalent to `range_search(k, v, ..)` without the `Ord` bound.
 fn full_range_search<BorrowType, K, V>(
     root: NodeRef<BorrowType, K, V, marker::LeafOrInternal>,
@@ -2490,38 +2763,45 @@ impl<'a, K: Ord, V> VacantEntry<'a, K, V> {
     pub fn insert(self, value: V) -> &'a mut V {
         *self.length += 1;
 
-        let out_ptr;
-
-        let mut ins_k;
-        let mut ins_v;
-        let mut ins_edge;
-
-        let mut cur_parent = match self.handle.insert(self.key, value) {
+        let out_ptr = match self.handle.insert(self.key, value) {
             (Fit(handle), _) => return handle.into_kv_mut().1,
             (Split(left, k, v, right), ptr) => {
-                ins_k = k;
-                ins_v = v;
-                ins_edge = right;
-                out_ptr = ptr;
-                left.ascend().map_err(|n| n.into_root_mut())
+                insert_split(left, k, v, right);
+                ptr
             }
         };
+        unsafe { &mut *out_ptr }
+    }
+}
 
-        loop {
-            match cur_parent {
-                Ok(parent) => match parent.insert(ins_k, ins_v, ins_edge) {
-                    Fit(_) => return unsafe { &mut *out_ptr },
-                    Split(left, k, v, right) => {
-                        ins_k = k;
-                        ins_v = v;
-                        ins_edge = right;
-                        cur_parent = left.ascend().map_err(|n| n.into_root_mut());
-                    }
-                },
-                Err(root) => {
-                    root.push_level().push(ins_k, ins_v, ins_edge);
-                    return unsafe { &mut *out_ptr };
+/// Inserts the key/value pair and the right node produced by splitting `left` into the
+/// parent of `left`, splitting ancestors in turn as needed and pushing a new root level
+/// if the split reaches the root.
+fn insert_split<'a, K, V, Type>(
+    left: NodeRef<marker::Mut<'a>, K, V, Type>,
+    k: K,
+    v: V,
+    right: node::Root<K, V>,
+) {
+    let mut ins_k = k;
+    let mut ins_v = v;
+    let mut ins_edge = right;
+    let mut cur_parent = left.ascend().map_err(|n| n.into_root_mut());
+
+    loop {
+        match cur_parent {
+            Ok(parent) => match parent.insert(ins_k, ins_v, ins_edge) {
+                Fit(_) => return,
+                Split(left, k, v, right) => {
+                    ins_k = k;
+                    ins_v = v;
+                    ins_edge = right;
+                    cur_parent = left.ascend().map_err(|n| n.into_root_mut());
                 }
+            },
+            Err(root) => {
+                root.push_level().push(ins_k, ins_v, ins_edge);
+                return;
             }
         }
     }
@@ -2697,6 +2977,399 @@ impl<'a, K: Ord, V> OccupiedEntry<'a, K, V> {
     }
 }
 
+impl<'a, K, V> Cursor<'a, K, V> {
+    /// Moves the cursor to the next element of the `BTreeMap`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the first element of the `BTreeMap`. If it is pointing to the last
+    /// element of the `BTreeMap` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn move_next(&mut self) {
+        let next_edge = match self.current {
+            None => self.root.map(|root| root.as_ref().first_leaf_edge()),
+            Some(current) => Some(current.next_leaf_edge()),
+        };
+        self.current = next_edge.and_then(|edge| edge.next_kv().ok());
+    }
+
+    /// Moves the cursor to the previous element of the `BTreeMap`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the last element of the `BTreeMap`. If it is pointing to the first
+    /// element of the `BTreeMap` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn move_prev(&mut self) {
+        let prev_edge = match self.current {
+            None => self.root.map(|root| root.as_ref().last_leaf_edge()),
+            Some(current) => Some(current.next_back_leaf_edge()),
+        };
+        self.current = prev_edge.and_then(|edge| edge.next_back_kv().ok());
+    }
+
+    /// Returns a reference to the key of the element that the cursor is
+    /// currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn key(&self) -> Option<&'a K> {
+        self.current.map(|current| current.into_kv().0)
+    }
+
+    /// Returns a reference to the value of the element that the cursor is
+    /// currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn value(&self) -> Option<&'a V> {
+        self.current.map(|current| current.into_kv().1)
+    }
+
+    /// Returns a reference to the key and value of the element that the cursor
+    /// is currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn key_value(&self) -> Option<(&'a K, &'a V)> {
+        self.current.map(|current| current.into_kv())
+    }
+
+    /// Returns a reference to the next element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the first element of the `BTreeMap`. If it is pointing to the last
+    /// element of the `BTreeMap` then this returns `None`.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn peek_next(&self) -> Option<(&'a K, &'a V)> {
+        let mut next = self.clone();
+        next.move_next();
+        next.key_value()
+    }
+
+    /// Returns a reference to the previous element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the last element of the `BTreeMap`. If it is pointing to the first
+    /// element of the `BTreeMap` then this returns `None`.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn peek_prev(&self) -> Option<(&'a K, &'a V)> {
+        let mut prev = self.clone();
+        prev.move_prev();
+        prev.key_value()
+    }
+}
+
+impl<'a, K, V> CursorMut<'a, K, V> {
+    /// Returns a reference to the root node that is valid for as long as the cursor.
+    ///
+    /// The caller must not keep the returned reference alive across a mutation made
+    /// through `current`, and vice versa.
+    fn root_node(&mut self) -> Option<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>> {
+        // SAFETY: `self.root` is borrowed for `'a`, and the cursor never hands out
+        // overlapping node references from `root` and `current` at the same time.
+        let root = unsafe { &mut *(self.root as *mut Option<node::Root<K, V>>) };
+        root.as_mut().map(|root| root.as_mut())
+    }
+
+    /// Moves the cursor to the next element of the `BTreeMap`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the first element of the `BTreeMap`. If it is pointing to the last
+    /// element of the `BTreeMap` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn move_next(&mut self) {
+        let next_edge = match self.current.take() {
+            None => self.root_node().map(|root| root.first_leaf_edge()),
+            Some(current) => Some(current.next_leaf_edge()),
+        };
+        self.current = next_edge.and_then(|edge| edge.next_kv().ok());
+    }
+
+    /// Moves the cursor to the previous element of the `BTreeMap`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the last element of the `BTreeMap`. If it is pointing to the first
+    /// element of the `BTreeMap` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn move_prev(&mut self) {
+        let prev_edge = match self.current.take() {
+            None => self.root_node().map(|root| root.last_leaf_edge()),
+            Some(current) => Some(current.next_back_leaf_edge()),
+        };
+        self.current = prev_edge.and_then(|edge| edge.next_back_kv().ok());
+    }
+
+    /// Returns a reference to the key of the element that the cursor is
+    /// currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn key(&self) -> Option<&K> {
+        self.current.as_ref().map(|current| current.reborrow().into_kv().0)
+    }
+
+    /// Returns a reference to the value of the element that the cursor is
+    /// currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn value(&self) -> Option<&V> {
+        self.current.as_ref().map(|current| current.reborrow().into_kv().1)
+    }
+
+    /// Returns a reference to the key and value of the element that the cursor
+    /// is currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn key_value(&self) -> Option<(&K, &V)> {
+        self.current.as_ref().map(|current| current.reborrow().into_kv())
+    }
+
+    /// Returns a mutable reference to the value of the element that the cursor
+    /// is currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn value_mut(&mut self) -> Option<&mut V> {
+        self.current.as_mut().map(|current| current.kv_mut().1)
+    }
+
+    /// Returns a reference to the key and a mutable reference to the value of the
+    /// element that the cursor is currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn key_value_mut(&mut self) -> Option<(&K, &mut V)> {
+        self.current.as_mut().map(|current| {
+            let (k, v) = current.kv_mut();
+            (&*k, v)
+        })
+    }
+
+    /// Returns a reference to the key and a mutable reference to the value of the
+    /// next element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the first element of the `BTreeMap`. If it is pointing to the last
+    /// element of the `BTreeMap` then this returns `None`.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn peek_next(&mut self) -> Option<(&K, &mut V)> {
+        let next_edge = match &self.current {
+            None => self.root_node()?.first_leaf_edge(),
+            // SAFETY: the duplicated handle is only used to find the neighbouring element.
+            Some(current) => unsafe { ptr::read(current) }.next_leaf_edge(),
+        };
+        let (k, v) = next_edge.next_kv().ok()?.into_kv_mut();
+        Some((&*k, v))
+    }
+
+    /// Returns a reference to the key and a mutable reference to the value of the
+    /// previous element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the last element of the `BTreeMap`. If it is pointing to the first
+    /// element of the `BTreeMap` then this returns `None`.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn peek_prev(&mut self) -> Option<(&K, &mut V)> {
+        let prev_edge = match &self.current {
+            None => self.root_node()?.last_leaf_edge(),
+            // SAFETY: the duplicated handle is only used to find the neighbouring element.
+            Some(current) => unsafe { ptr::read(current) }.next_back_leaf_edge(),
+        };
+        let (k, v) = prev_edge.next_back_kv().ok()?.into_kv_mut();
+        Some((&*k, v))
+    }
+
+    /// Returns a read-only cursor pointing to the current element.
+    ///
+    /// The lifetime of the returned `Cursor` is bound to that of the
+    /// `CursorMut`, which means it cannot outlive the `CursorMut` and that the
+    /// `CursorMut` is frozen for the lifetime of the `Cursor`.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn as_cursor(&self) -> Cursor<'_, K, V> {
+        Cursor {
+            current: self.current.as_ref().map(|current| current.reborrow()),
+            root: self.root.as_ref(),
+        }
+    }
+
+    /// Inserts a new element into the `BTreeMap` after the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the new element is
+    /// inserted at the front of the `BTreeMap`.
+    ///
+    /// The cursor keeps pointing at the same element (or at the "ghost" non-element).
+    ///
+    /// # Safety
+    ///
+    /// You must ensure that the `BTreeMap` invariants are maintained.
+    /// Specifically:
+    ///
+    /// * The key of the newly inserted element must be unique in the tree.
+    /// * All keys in the tree must remain in sorted order.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub unsafe fn insert_after_unchecked(&mut self, key: K, value: V) {
+        let edge = match self.current.take() {
+            None => {
+                self.root.get_or_insert_with(node::Root::new_leaf);
+                let root = unsafe { unwrap_unchecked(self.root_node()) };
+                root.first_leaf_edge()
+            }
+            Some(current) => current.next_leaf_edge(),
+        };
+        let inserted = insert_tracking(edge, key, value);
+        *self.length += 1;
+        self.current = inserted.left_edge().next_back_kv().ok();
+    }
+
+    /// Inserts a new element into the `BTreeMap` before the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the new element is
+    /// inserted at the end of the `BTreeMap`.
+    ///
+    /// The cursor keeps pointing at the same element (or at the "ghost" non-element).
+    ///
+    /// # Safety
+    ///
+    /// You must ensure that the `BTreeMap` invariants are maintained.
+    /// Specifically:
+    ///
+    /// * The key of the newly inserted element must be unique in the tree.
+    /// * All keys in the tree must remain in sorted order.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub unsafe fn insert_before_unchecked(&mut self, key: K, value: V) {
+        let edge = match self.current.take() {
+            None => {
+                self.root.get_or_insert_with(node::Root::new_leaf);
+                let root = unsafe { unwrap_unchecked(self.root_node()) };
+                root.last_leaf_edge()
+            }
+            Some(current) => current.next_back_leaf_edge(),
+        };
+        let inserted = insert_tracking(edge, key, value);
+        *self.length += 1;
+        self.current = inserted.right_edge().next_kv().ok();
+    }
+
+    /// Removes the current element from the `BTreeMap`.
+    ///
+    /// The element that was removed is returned, and the cursor is
+    /// moved to point to the next element in the `BTreeMap`.
+    ///
+    /// If the cursor is currently pointing to the "ghost" non-element then no element
+    /// is removed and `None` is returned. The cursor is not moved in this case.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn remove_current(&mut self) -> Option<(K, V)> {
+        let current = self.current.take()?;
+        *self.length -= 1;
+        let (k, v, pos) = current.remove_kv_tracking();
+        self.current = pos.next_kv().ok();
+        Some((k, v))
+    }
+
+    /// Removes the current element from the `BTreeMap`.
+    ///
+    /// The element that was removed is returned, and the cursor is
+    /// moved to point to the previous element in the `BTreeMap`.
+    ///
+    /// If the cursor is currently pointing to the "ghost" non-element then no element
+    /// is removed and `None` is returned. The cursor is not moved in this case.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn remove_current_and_move_back(&mut self) -> Option<(K, V)> {
+        let current = self.current.take()?;
+        *self.length -= 1;
+        let (k, v, pos) = current.remove_kv_tracking();
+        self.current = pos.next_back_kv().ok();
+        Some((k, v))
+    }
+}
+
+impl<'a, K: Ord, V> CursorMut<'a, K, V> {
+    /// Inserts a new element into the `BTreeMap` after the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the new element is
+    /// inserted at the front of the `BTreeMap`.
+    ///
+    /// The cursor keeps pointing at the same element (or at the "ghost" non-element).
+    ///
+    /// # Panics
+    ///
+    /// This function panics if:
+    /// - the given key compares less than or equal to the current element (unless
+    ///   the cursor is pointing at the "ghost" non-element).
+    /// - the given key compares greater than or equal to the next element (unless
+    ///   the cursor is pointing at the last element of the `BTreeMap`).
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn insert_after(&mut self, key: K, value: V) {
+        if let Some(current) = self.key() {
+            if &key <= current {
+                panic!("key must be ordered above the current element");
+            }
+        }
+        if let Some((next, _)) = self.peek_next() {
+            if &key >= next {
+                panic!("key must be ordered below the next element");
+            }
+        }
+        unsafe {
+            self.insert_after_unchecked(key, value);
+        }
+    }
+
+    /// Inserts a new element into the `BTreeMap` before the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the new element is
+    /// inserted at the end of the `BTreeMap`.
+    ///
+    /// The cursor keeps pointing at the same element (or at the "ghost" non-element).
+    ///
+    /// # Panics
+    ///
+    /// This function panics if:
+    /// - the given key compares greater than or equal to the current element (unless
+    ///   the cursor is pointing at the "ghost" non-element).
+    /// - the given key compares less than or equal to the previous element (unless
+    ///   the cursor is pointing at the first element of the `BTreeMap`).
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn insert_before(&mut self, key: K, value: V) {
+        if let Some(current) = self.key() {
+            if &key >= current {
+                panic!("key must be ordered below the current element");
+            }
+        }
+        if let Some((prev, _)) = self.peek_prev() {
+            if &key <= prev {
+                panic!("key must be ordered above the previous element");
+            }
+        }
+        unsafe {
+            self.insert_before_unchecked(key, value);
+        }
+    }
+}
+
+/// Inserts a key/value pair at the given leaf edge, splitting nodes up the tree as
+/// needed, and returns a handle to the inserted pair.
+fn insert_tracking<'a, K, V>(
+    edge: Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>,
+    key: K,
+    value: V,
+) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV> {
+    let (result, inserted) = edge.insert_tracking(key, value);
+    if let Split(left, k, v, right) = result {
+        insert_split(left, k, v, right);
+    }
+    inserted
+}
+
 impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::KV> {
     /// Removes a key/value-pair from the map, and returns that pair, as well as
     /// the leaf edge corresponding to that former pair.
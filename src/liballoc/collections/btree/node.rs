@@ -845,26 +845,52 @@ impl<'a, K, V> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge
     /// this edge. This method splits the node if there isn't enough room.
     ///
     /// The returned pointer points to the inserted value.
-    pub fn insert(mut self, key: K, val: V) -> (InsertResult<'a, K, V, marker::Leaf>, *mut V) {
+    pub fn insert(self, key: K, val: V) -> (InsertResult<'a, K, V, marker::Leaf>, *mut V) {
+        let (result, kv) = self.insert_tracking(key, val);
+        (result, kv.into_kv_mut().1)
+    }
+
+    /// Inserts a new key/value pair between the key/value pairs to the right and left of
+    /// this edge. This method splits the node if there isn't enough room.
+    ///
+    /// The returned handle points to the inserted pair. Leaf nodes never move when their
+    /// ancestors are split, so the handle remains valid after the caller has inserted the
+    /// split off node into the parent, and it can be used to navigate the whole tree.
+    pub fn insert_tracking(
+        mut self,
+        key: K,
+        val: V,
+    ) -> (
+        InsertResult<'a, K, V, marker::Leaf>,
+        Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV>,
+    ) {
         if self.node.len() < CAPACITY {
-            let ptr = self.insert_fit(key, val);
+            self.insert_fit(key, val);
+            let tracked = unsafe { Handle::new_kv(ptr::read(&self.node), self.idx) };
             let kv = unsafe { Handle::new_kv(self.node, self.idx) };
-            (InsertResult::Fit(kv), ptr)
+            (InsertResult::Fit(kv), tracked)
         } else {
+            // The right half of the split becomes an owned `Root` whose `as_mut` points
+            // back at that local `Root`, so build the tracked handle from the original
+            // tree's root instead.
+            let root = self.node.root;
             let middle = unsafe { Handle::new_kv(self.node, B) };
             let (mut left, k, v, mut right) = middle.split();
-            let ptr = if self.idx <= B {
-                unsafe { Handle::new_edge(left.reborrow_mut(), self.idx).insert_fit(key, val) }
+            let (node, idx) = if self.idx <= B {
+                unsafe { Handle::new_edge(left.reborrow_mut(), self.idx).insert_fit(key, val) };
+                (left.node, self.idx)
             } else {
+                let idx = self.idx - (B + 1);
                 unsafe {
-                    Handle::new_edge(
-                        right.as_mut().cast_unchecked::<marker::Leaf>(),
-                        self.idx - (B + 1),
-                    )
-                    .insert_fit(key, val)
-                }
+                    Handle::new_edge(right.as_mut().cast_unchecked::<marker::Leaf>(), idx)
+                        .insert_fit(key, val)
+                };
+                (right.node.as_ptr(), idx)
+            };
+            let tracked = unsafe {
+                Handle::new_kv(NodeRef { height: 0, node, root, _marker: PhantomData }, idx)
             };
-            (InsertResult::Split(left, k, v, right), ptr)
+            (InsertResult::Split(left, k, v, right), tracked)
         }
     }
 }
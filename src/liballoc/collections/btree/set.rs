@@ -6,9 +6,9 @@ use core::cmp::Ordering::{Equal, Greater, Less};
 use core::cmp::{max, min};
 use core::fmt::{self, Debug};
 use core::iter::{FromIterator, FusedIterator, Peekable};
-use core::ops::{BitAnd, BitOr, BitXor, RangeBounds, Sub};
+use core::ops::{BitAnd, BitOr, BitXor, Bound, RangeBounds, Sub};
 
-use super::map::{BTreeMap, Keys};
+use super::map::{self, BTreeMap, Keys};
 use super::Recover;
 
 // FIXME(conventions): implement bounded iterators
@@ -289,6 +289,61 @@ impl<T: fmt::Debug> fmt::Debug for Union<'_, T> {
     }
 }
 
+/// A cursor over a `BTreeSet`.
+///
+/// A `Cursor` is like an iterator, except that it can freely seek back-and-forth.
+///
+/// Cursors always point to an element in the set, and index in a logically circular way.
+/// To accommodate this, there is a "ghost" non-element that yields `None` between the last and
+/// first elements of the set.
+///
+/// A `Cursor` is created with the [`BTreeSet::lower_bound`] and [`BTreeSet::upper_bound`]
+/// methods.
+///
+/// [`BTreeSet::lower_bound`]: struct.BTreeSet.html#method.lower_bound
+/// [`BTreeSet::upper_bound`]: struct.BTreeSet.html#method.upper_bound
+#[unstable(feature = "btree_cursors", issue = "none")]
+pub struct Cursor<'a, T: 'a> {
+    inner: map::Cursor<'a, T, ()>,
+}
+
+#[unstable(feature = "btree_cursors", issue = "none")]
+impl<T> Clone for Cursor<'_, T> {
+    fn clone(&self) -> Self {
+        Cursor { inner: self.inner.clone() }
+    }
+}
+
+#[unstable(feature = "btree_cursors", issue = "none")]
+impl<T: Debug> Debug for Cursor<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Cursor").field(&self.get()).finish()
+    }
+}
+
+/// A cursor over a `BTreeSet` with editing operations.
+///
+/// A `CursorMut` is like a [`Cursor`], except that it can also insert elements
+/// next to the current one and remove the current one.
+///
+/// A `CursorMut` is created with the [`BTreeSet::lower_bound_mut`] and
+/// [`BTreeSet::upper_bound_mut`] methods.
+///
+/// [`Cursor`]: struct.Cursor.html
+/// [`BTreeSet::lower_bound_mut`]: struct.BTreeSet.html#method.lower_bound_mut
+/// [`BTreeSet::upper_bound_mut`]: struct.BTreeSet.html#method.upper_bound_mut
+#[unstable(feature = "btree_cursors", issue = "none")]
+pub struct CursorMut<'a, T: 'a> {
+    inner: map::CursorMut<'a, T, ()>,
+}
+
+#[unstable(feature = "btree_cursors", issue = "none")]
+impl<T: Debug> Debug for CursorMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CursorMut").field(&self.get()).finish()
+    }
+}
+
 // This constant is used by functions that compare two sets.
 // It estimates the relative size at which searching performs better
 // than iterating, based on the benchmarks in
@@ -346,6 +401,141 @@ impl<T: Ord> BTreeSet<T> {
         Range { iter: self.map.range(range) }
     }
 
+    /// Returns a [`Cursor`] pointing at the first element that is above the
+    /// given bound.
+    ///
+    /// If no such element exists then a cursor pointing at the "ghost"
+    /// non-element is returned.
+    ///
+    /// Passing [`Unbounded`] will return a cursor pointing at the first
+    /// element of the set.
+    ///
+    /// [`Cursor`]: struct.Cursor.html
+    /// [`Unbounded`]: ../../std/ops/enum.Bound.html#variant.Unbounded
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeSet;
+    /// use std::ops::Bound;
+    ///
+    /// let set: BTreeSet<_> = [1, 2, 3, 4].iter().cloned().collect();
+    /// let cursor = set.lower_bound(Bound::Excluded(&2));
+    /// assert_eq!(cursor.get(), Some(&3));
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn lower_bound<K: ?Sized>(&self, bound: Bound<&K>) -> Cursor<'_, T>
+    where
+        K: Ord,
+        T: Borrow<K>,
+    {
+        Cursor { inner: self.map.lower_bound(bound) }
+    }
+
+    /// Returns a [`CursorMut`] pointing at the first element that is above the
+    /// given bound.
+    ///
+    /// If no such element exists then a cursor pointing at the "ghost"
+    /// non-element is returned.
+    ///
+    /// Passing [`Unbounded`] will return a cursor pointing at the first
+    /// element of the set.
+    ///
+    /// [`CursorMut`]: struct.CursorMut.html
+    /// [`Unbounded`]: ../../std/ops/enum.Bound.html#variant.Unbounded
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeSet;
+    /// use std::ops::Bound;
+    ///
+    /// let mut set: BTreeSet<_> = [1, 2, 4].iter().cloned().collect();
+    /// let mut cursor = set.lower_bound_mut(Bound::Excluded(&2));
+    /// cursor.insert_before(3);
+    /// assert_eq!(cursor.get(), Some(&4));
+    /// assert!(set.contains(&3));
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn lower_bound_mut<K: ?Sized>(&mut self, bound: Bound<&K>) -> CursorMut<'_, T>
+    where
+        K: Ord,
+        T: Borrow<K>,
+    {
+        CursorMut { inner: self.map.lower_bound_mut(bound) }
+    }
+
+    /// Returns a [`Cursor`] pointing at the last element that is below the
+    /// given bound.
+    ///
+    /// If no such element exists then a cursor pointing at the "ghost"
+    /// non-element is returned.
+    ///
+    /// Passing [`Unbounded`] will return a cursor pointing at the last
+    /// element of the set.
+    ///
+    /// [`Cursor`]: struct.Cursor.html
+    /// [`Unbounded`]: ../../std/ops/enum.Bound.html#variant.Unbounded
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeSet;
+    /// use std::ops::Bound;
+    ///
+    /// let set: BTreeSet<_> = [1, 2, 3, 4].iter().cloned().collect();
+    /// let cursor = set.upper_bound(Bound::Excluded(&3));
+    /// assert_eq!(cursor.get(), Some(&2));
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn upper_bound<K: ?Sized>(&self, bound: Bound<&K>) -> Cursor<'_, T>
+    where
+        K: Ord,
+        T: Borrow<K>,
+    {
+        Cursor { inner: self.map.upper_bound(bound) }
+    }
+
+    /// Returns a [`CursorMut`] pointing at the last element that is below the
+    /// given bound.
+    ///
+    /// If no such element exists then a cursor pointing at the "ghost"
+    /// non-element is returned.
+    ///
+    /// Passing [`Unbounded`] will return a cursor pointing at the last
+    /// element of the set.
+    ///
+    /// [`CursorMut`]: struct.CursorMut.html
+    /// [`Unbounded`]: ../../std/ops/enum.Bound.html#variant.Unbounded
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeSet;
+    /// use std::ops::Bound;
+    ///
+    /// let mut set: BTreeSet<_> = [1, 2, 3, 4].iter().cloned().collect();
+    /// let mut cursor = set.upper_bound_mut(Bound::Included(&3));
+    /// assert_eq!(cursor.remove_current(), Some(3));
+    /// assert_eq!(cursor.get(), Some(&4));
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn upper_bound_mut<K: ?Sized>(&mut self, bound: Bound<&K>) -> CursorMut<'_, T>
+    where
+        K: Ord,
+        T: Borrow<K>,
+    {
+        CursorMut { inner: self.map.upper_bound_mut(bound) }
+    }
+
     /// Visits the values representing the difference,
     /// i.e., the values that are in `self` but not in `other`,
     /// in ascending order.
@@ -1545,3 +1735,209 @@ impl<'a, T: Ord> Iterator for Union<'a, T> {
 
 #[stable(feature = "fused", since = "1.26.0")]
 impl<T: Ord> FusedIterator for Union<'_, T> {}
+
+impl<'a, T> Cursor<'a, T> {
+    /// Moves the cursor to the next element of the `BTreeSet`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the first element of the `BTreeSet`. If it is pointing to the last
+    /// element of the `BTreeSet` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn move_next(&mut self) {
+        self.inner.move_next()
+    }
+
+    /// Moves the cursor to the previous element of the `BTreeSet`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the last element of the `BTreeSet`. If it is pointing to the first
+    /// element of the `BTreeSet` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn move_prev(&mut self) {
+        self.inner.move_prev()
+    }
+
+    /// Returns a reference to the element that the cursor is currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn get(&self) -> Option<&'a T> {
+        self.inner.key()
+    }
+
+    /// Returns a reference to the next element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the first element of the `BTreeSet`. If it is pointing to the last
+    /// element of the `BTreeSet` then this returns `None`.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn peek_next(&self) -> Option<&'a T> {
+        self.inner.peek_next().map(|(k, _)| k)
+    }
+
+    /// Returns a reference to the previous element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the last element of the `BTreeSet`. If it is pointing to the first
+    /// element of the `BTreeSet` then this returns `None`.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        self.inner.peek_prev().map(|(k, _)| k)
+    }
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    /// Moves the cursor to the next element of the `BTreeSet`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the first element of the `BTreeSet`. If it is pointing to the last
+    /// element of the `BTreeSet` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn move_next(&mut self) {
+        self.inner.move_next()
+    }
+
+    /// Moves the cursor to the previous element of the `BTreeSet`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the last element of the `BTreeSet`. If it is pointing to the first
+    /// element of the `BTreeSet` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn move_prev(&mut self) {
+        self.inner.move_prev()
+    }
+
+    /// Returns a reference to the element that the cursor is currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn get(&self) -> Option<&T> {
+        self.inner.key()
+    }
+
+    /// Returns a reference to the next element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the first element of the `BTreeSet`. If it is pointing to the last
+    /// element of the `BTreeSet` then this returns `None`.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn peek_next(&mut self) -> Option<&T> {
+        self.inner.peek_next().map(|(k, _)| k)
+    }
+
+    /// Returns a reference to the previous element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the last element of the `BTreeSet`. If it is pointing to the first
+    /// element of the `BTreeSet` then this returns `None`.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn peek_prev(&mut self) -> Option<&T> {
+        self.inner.peek_prev().map(|(k, _)| k)
+    }
+
+    /// Returns a read-only cursor pointing to the current element.
+    ///
+    /// The lifetime of the returned `Cursor` is bound to that of the
+    /// `CursorMut`, which means it cannot outlive the `CursorMut` and that the
+    /// `CursorMut` is frozen for the lifetime of the `Cursor`.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn as_cursor(&self) -> Cursor<'_, T> {
+        Cursor { inner: self.inner.as_cursor() }
+    }
+
+    /// Inserts a new element into the `BTreeSet` after the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the new element is
+    /// inserted at the front of the `BTreeSet`.
+    ///
+    /// # Safety
+    ///
+    /// You must ensure that the `BTreeSet` invariants are maintained.
+    /// Specifically:
+    ///
+    /// * The newly inserted element must be unique in the set.
+    /// * All elements in the set must remain in sorted order.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub unsafe fn insert_after_unchecked(&mut self, value: T) {
+        unsafe { self.inner.insert_after_unchecked(value, ()) }
+    }
+
+    /// Inserts a new element into the `BTreeSet` before the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the new element is
+    /// inserted at the end of the `BTreeSet`.
+    ///
+    /// # Safety
+    ///
+    /// You must ensure that the `BTreeSet` invariants are maintained.
+    /// Specifically:
+    ///
+    /// * The newly inserted element must be unique in the set.
+    /// * All elements in the set must remain in sorted order.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub unsafe fn insert_before_unchecked(&mut self, value: T) {
+        unsafe { self.inner.insert_before_unchecked(value, ()) }
+    }
+
+    /// Removes the current element from the `BTreeSet`.
+    ///
+    /// The element that was removed is returned, and the cursor is
+    /// moved to point to the next element in the `BTreeSet`.
+    ///
+    /// If the cursor is currently pointing to the "ghost" non-element then no element
+    /// is removed and `None` is returned. The cursor is not moved in this case.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn remove_current(&mut self) -> Option<T> {
+        self.inner.remove_current().map(|(k, _)| k)
+    }
+
+    /// Removes the current element from the `BTreeSet`.
+    ///
+    /// The element that was removed is returned, and the cursor is
+    /// moved to point to the previous element in the `BTreeSet`.
+    ///
+    /// If the cursor is currently pointing to the "ghost" non-element then no element
+    /// is removed and `None` is returned. The cursor is not moved in this case.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn remove_current_and_move_back(&mut self) -> Option<T> {
+        self.inner.remove_current_and_move_back().map(|(k, _)| k)
+    }
+}
+
+impl<'a, T: Ord> CursorMut<'a, T> {
+    /// Inserts a new element into the `BTreeSet` after the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the new element is
+    /// inserted at the front of the `BTreeSet`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if:
+    /// - the given element compares less than or equal to the current element (unless
+    ///   the cursor is pointing at the "ghost" non-element).
+    /// - the given element compares greater than or equal to the next element (unless
+    ///   the cursor is pointing at the last element of the `BTreeSet`).
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn insert_after(&mut self, value: T) {
+        self.inner.insert_after(value, ())
+    }
+
+    /// Inserts a new element into the `BTreeSet` before the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the new element is
+    /// inserted at the end of the `BTreeSet`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if:
+    /// - the given element compares greater than or equal to the current element (unless
+    ///   the cursor is pointing at the "ghost" non-element).
+    /// - the given element compares less than or equal to the previous element (unless
+    ///   the cursor is pointing at the first element of the `BTreeSet`).
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn insert_before(&mut self, value: T) {
+        self.inner.insert_before(value, ())
+    }
+}
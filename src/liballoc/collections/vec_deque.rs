@@ -515,6 +515,35 @@ impl<T> VecDeque<T> {
         VecDeque { tail: 0, head: 0, buf: RawVec::with_capacity(cap) }
     }
 
+    /// Creates an empty `VecDeque` with space for at least `capacity` elements,
+    /// returning an error instead of panicking or aborting if the allocation fails.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    /// use std::collections::VecDeque;
+    ///
+    /// let vector: VecDeque<u32> = VecDeque::try_with_capacity(10).expect("out of memory");
+    /// assert!(vector.capacity() >= 10);
+    /// ```
+    #[unstable(feature = "try_reserve", reason = "new API", issue = "48043")]
+    pub fn try_with_capacity(capacity: usize) -> Result<VecDeque<T>, TryReserveError> {
+        // +1 since the ringbuffer always leaves one space empty
+        let cap = capacity
+            .checked_add(1)
+            .map(|cap| cmp::max(cap, MINIMUM_CAPACITY + 1))
+            .and_then(usize::checked_next_power_of_two)
+            .ok_or(TryReserveError::CapacityOverflow)?;
+
+        Ok(VecDeque { tail: 0, head: 0, buf: RawVec::try_with_capacity(cap)? })
+    }
+
     /// Provides a reference to the element at the given index.
     ///
     /// Element at index 0 is the front of the queue.
@@ -1391,6 +1420,41 @@ impl<T> VecDeque<T> {
         }
     }
 
+    /// Prepends an element to the `VecDeque`, returning an error instead of
+    /// aborting if the buffer needs to grow and the allocation fails.
+    ///
+    /// On error, `value` is dropped and the `VecDeque` is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    /// use std::collections::VecDeque;
+    ///
+    /// let mut d = VecDeque::new();
+    /// d.try_push_front(1).expect("out of memory");
+    /// d.try_push_front(2).expect("out of memory");
+    /// assert_eq!(d.front(), Some(&2));
+    /// ```
+    #[unstable(feature = "try_reserve", reason = "new API", issue = "48043")]
+    pub fn try_push_front(&mut self, value: T) -> Result<(), TryReserveError> {
+        if self.is_full() {
+            self.try_grow()?;
+        }
+
+        self.tail = self.wrap_sub(self.tail, 1);
+        let tail = self.tail;
+        unsafe {
+            self.buffer_write(tail, value);
+        }
+        Ok(())
+    }
+
     /// Appends an element to the back of the `VecDeque`.
     ///
     /// # Examples
@@ -1414,6 +1478,39 @@ impl<T> VecDeque<T> {
         unsafe { self.buffer_write(head, value) }
     }
 
+    /// Appends an element to the back of the `VecDeque`, returning an error
+    /// instead of aborting if the buffer needs to grow and the allocation fails.
+    ///
+    /// On error, `value` is dropped and the `VecDeque` is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    /// use std::collections::VecDeque;
+    ///
+    /// let mut buf = VecDeque::new();
+    /// buf.try_push_back(1).expect("out of memory");
+    /// buf.try_push_back(3).expect("out of memory");
+    /// assert_eq!(3, *buf.back().unwrap());
+    /// ```
+    #[unstable(feature = "try_reserve", reason = "new API", issue = "48043")]
+    pub fn try_push_back(&mut self, value: T) -> Result<(), TryReserveError> {
+        if self.is_full() {
+            self.try_grow()?;
+        }
+
+        let head = self.head;
+        self.head = self.wrap_add(self.head, 1);
+        unsafe { self.buffer_write(head, value) }
+        Ok(())
+    }
+
     #[inline]
     fn is_contiguous(&self) -> bool {
         self.tail <= self.head
@@ -2049,6 +2146,22 @@ impl<T> VecDeque<T> {
         }
     }
 
+    // Like `grow`, but returns on errors instead of panicking or aborting
+    #[inline(never)]
+    fn try_grow(&mut self) -> Result<(), TryReserveError> {
+        if self.is_full() {
+            let old_cap = self.cap();
+            // Double the buffer size.
+            self.buf.try_reserve_exact(old_cap, old_cap)?;
+            assert!(self.cap() == old_cap * 2);
+            unsafe {
+                self.handle_capacity_increase(old_cap);
+            }
+            debug_assert!(!self.is_full());
+        }
+        Ok(())
+    }
+
     /// Modifies the `VecDeque` in-place so that `len()` is equal to `new_len`,
     /// either by removing excess elements from the back or by appending
     /// elements generated by calling `generator` to the back.
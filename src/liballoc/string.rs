@@ -417,6 +417,29 @@ impl String {
         String { vec: Vec::with_capacity(capacity) }
     }
 
+    /// Creates a new empty `String` with a particular capacity, returning an
+    /// error instead of aborting if the allocation fails.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    ///
+    /// let s = String::try_with_capacity(10).expect("out of memory");
+    /// assert_eq!(s.len(), 0);
+    /// assert_eq!(s.capacity(), 10);
+    /// ```
+    #[inline]
+    #[unstable(feature = "try_reserve", reason = "new API", issue = "48043")]
+    pub fn try_with_capacity(capacity: usize) -> Result<String, TryReserveError> {
+        Ok(String { vec: Vec::try_with_capacity(capacity)? })
+    }
+
     // HACK(japaric): with cfg(test) the inherent `[T]::to_vec` method, which is
     // required for this method definition, is not available. Since we don't
     // require this method for testing purposes, I'll just stub it
@@ -837,6 +860,31 @@ impl String {
         self.vec.extend_from_slice(string.as_bytes())
     }
 
+    /// Appends a given string slice onto the end of this `String`, returning
+    /// an error instead of aborting if the allocation fails.
+    ///
+    /// On error, the `String` is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    ///
+    /// let mut s = String::from("foo");
+    /// s.try_push_str("bar").expect("out of memory");
+    /// assert_eq!("foobar", s);
+    /// ```
+    #[inline]
+    #[unstable(feature = "try_reserve", reason = "new API", issue = "48043")]
+    pub fn try_push_str(&mut self, string: &str) -> Result<(), TryReserveError> {
+        self.vec.try_extend_from_slice(string.as_bytes())
+    }
+
     /// Returns this `String`'s capacity, in bytes.
     ///
     /// # Examples
@@ -1100,6 +1148,37 @@ impl String {
         }
     }
 
+    /// Appends the given [`char`] to the end of this `String`, returning an
+    /// error instead of aborting if the allocation fails.
+    ///
+    /// On error, the `String` is left unchanged.
+    ///
+    /// [`char`]: ../../std/primitive.char.html
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    ///
+    /// let mut s = String::from("abc");
+    /// s.try_push('1').expect("out of memory");
+    /// s.try_push('ß').expect("out of memory");
+    /// assert_eq!("abc1ß", s);
+    /// ```
+    #[inline]
+    #[unstable(feature = "try_reserve", reason = "new API", issue = "48043")]
+    pub fn try_push(&mut self, ch: char) -> Result<(), TryReserveError> {
+        match ch.len_utf8() {
+            1 => self.vec.try_push(ch as u8),
+            _ => self.vec.try_extend_from_slice(ch.encode_utf8(&mut [0; 4]).as_bytes()),
+        }
+    }
+
     /// Returns a byte slice of this `String`'s contents.
     ///
     /// The inverse of this method is [`from_utf8`].
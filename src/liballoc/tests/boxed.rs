@@ -1,3 +1,4 @@
+use std::alloc::System;
 use std::mem::MaybeUninit;
 use std::ptr::NonNull;
 
@@ -49,3 +50,25 @@ fn box_clone_from_ptr_stability() {
         assert_eq!(copy.as_ptr() as usize, copy_raw);
     }
 }
+
+#[test]
+fn box_in_custom_allocator() {
+    let boxed = Box::new_in(Dummy { _data: 42 }, System);
+    let clone = boxed.clone();
+    assert_eq!(boxed, clone);
+
+    let (ptr, alloc) = Box::into_raw_with_alloc(boxed);
+    let boxed = unsafe { Box::from_raw_in(ptr, alloc) };
+    assert_eq!(*boxed, Dummy { _data: 42 });
+
+    let zero = Box::<u32, _>::new_zeroed_in(System);
+    assert_eq!(unsafe { *zero.assume_init() }, 0);
+}
+
+#[test]
+fn box_try_new() {
+    let boxed = Box::try_new(Dummy { _data: 42 }).unwrap();
+    assert_eq!(*boxed, Dummy { _data: 42 });
+    let zst = Box::try_new(()).unwrap();
+    assert_eq!(*zst, ());
+}
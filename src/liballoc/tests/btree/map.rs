@@ -1311,3 +1311,140 @@ fn test_into_iter_drop_leak_height_1() {
         assert_eq!(DROPS.load(Ordering::SeqCst), size);
     }
 }
+
+#[test]
+fn test_cursor() {
+    let map = BTreeMap::from_iter([(1, 'a'), (2, 'b'), (3, 'c')].iter().cloned());
+
+    let mut cur = map.lower_bound(Unbounded);
+    assert_eq!(cur.key(), Some(&1));
+    cur.move_next();
+    assert_eq!(cur.key(), Some(&2));
+    assert_eq!(cur.peek_next(), Some((&3, &'c')));
+    cur.move_prev();
+    assert_eq!(cur.key(), Some(&1));
+    assert_eq!(cur.peek_prev(), None);
+
+    let mut cur = map.upper_bound(Excluded(&1));
+    assert_eq!(cur.key(), None);
+    cur.move_next();
+    assert_eq!(cur.key(), Some(&1));
+    cur.move_prev();
+    assert_eq!(cur.key(), None);
+    assert_eq!(cur.peek_prev(), Some((&3, &'c')));
+
+    assert_eq!(map.lower_bound(Included(&2)).key(), Some(&2));
+    assert_eq!(map.lower_bound(Excluded(&2)).key(), Some(&3));
+    assert_eq!(map.lower_bound(Excluded(&3)).key(), None);
+    assert_eq!(map.upper_bound(Included(&2)).key(), Some(&2));
+    assert_eq!(map.upper_bound(Excluded(&2)).key(), Some(&1));
+    assert_eq!(map.upper_bound(Unbounded).key(), Some(&3));
+
+    let empty: BTreeMap<i32, char> = BTreeMap::new();
+    let mut cur = empty.lower_bound(Unbounded);
+    assert_eq!(cur.key(), None);
+    cur.move_next();
+    assert_eq!(cur.key(), None);
+}
+
+#[test]
+fn test_cursor_mut() {
+    let mut map = BTreeMap::from_iter([(1, 'a'), (3, 'c'), (5, 'e')].iter().cloned());
+    let mut cur = map.lower_bound_mut(Excluded(&3));
+    assert_eq!(cur.key(), Some(&5));
+    cur.insert_before(4, 'd');
+    assert_eq!(cur.key(), Some(&5));
+    assert_eq!(cur.peek_prev(), Some((&4, &mut 'd')));
+    cur.move_next();
+    assert_eq!(cur.key(), None);
+    cur.insert_before(6, 'f');
+    assert_eq!(cur.key(), None);
+    assert_eq!(cur.remove_current(), None);
+    assert_eq!(cur.key(), None);
+    cur.insert_after(0, '?');
+    assert_eq!(cur.key(), None);
+    assert_eq!(
+        map,
+        BTreeMap::from_iter(
+            [(0, '?'), (1, 'a'), (3, 'c'), (4, 'd'), (5, 'e'), (6, 'f')].iter().cloned()
+        )
+    );
+
+    let mut cur = map.upper_bound_mut(Included(&5));
+    assert_eq!(cur.key(), Some(&5));
+    assert_eq!(cur.remove_current(), Some((5, 'e')));
+    assert_eq!(cur.key(), Some(&6));
+    assert_eq!(cur.remove_current_and_move_back(), Some((6, 'f')));
+    assert_eq!(cur.key(), Some(&4));
+    *cur.value_mut().unwrap() = 'D';
+    assert_eq!(
+        map,
+        BTreeMap::from_iter([(0, '?'), (1, 'a'), (3, 'c'), (4, 'D')].iter().cloned())
+    );
+}
+
+#[test]
+fn test_cursor_mut_empty() {
+    let mut map = BTreeMap::new();
+    let mut cur = map.lower_bound_mut(Unbounded);
+    assert_eq!(cur.key(), None);
+    cur.insert_after(2, 'b');
+    cur.insert_after(1, 'a');
+    cur.insert_before(3, 'c');
+    assert_eq!(cur.key(), None);
+    cur.move_next();
+    assert_eq!(cur.key_value(), Some((&1, &'a')));
+    assert_eq!(map, BTreeMap::from_iter([(1, 'a'), (2, 'b'), (3, 'c')].iter().cloned()));
+}
+
+#[test]
+#[should_panic(expected = "key must be ordered above the current element")]
+fn test_cursor_mut_insert_after_1() {
+    let mut map = BTreeMap::from_iter([(1, 'a'), (2, 'b'), (3, 'c')].iter().cloned());
+    let mut cur = map.upper_bound_mut(Included(&2));
+    cur.insert_after(1, 'x');
+}
+
+#[test]
+#[should_panic(expected = "key must be ordered below the next element")]
+fn test_cursor_mut_insert_after_2() {
+    let mut map = BTreeMap::from_iter([(1, 'a'), (2, 'b'), (3, 'c')].iter().cloned());
+    let mut cur = map.upper_bound_mut(Included(&2));
+    cur.insert_after(3, 'x');
+}
+
+#[test]
+fn test_cursor_mut_large() {
+    // Grow and shrink a multi-level tree purely through a cursor, checking
+    // that the cursor stays on the element it pointed at across node splits
+    // and merges.
+    let size = MIN_INSERTS_HEIGHT_2;
+    let mut map = BTreeMap::new();
+    let mut cur = map.lower_bound_mut(Unbounded);
+    for i in (0..size).rev() {
+        cur.insert_after(i * 2, i);
+        cur.move_next();
+        assert_eq!(cur.key(), Some(&(i * 2)));
+        cur.move_prev();
+    }
+    cur.move_next();
+    for i in 0..size {
+        assert_eq!(cur.key(), Some(&(i * 2)));
+        cur.insert_after(i * 2 + 1, i);
+        assert_eq!(cur.key(), Some(&(i * 2)));
+        cur.move_next();
+        cur.move_next();
+    }
+    assert_eq!(cur.key(), None);
+    assert_eq!(map.len(), size * 2);
+    assert!(map.keys().cloned().eq(0..size * 2));
+
+    let mut cur = map.lower_bound_mut(Unbounded);
+    for i in 0..size {
+        assert_eq!(cur.remove_current(), Some((i * 2, i)));
+        cur.move_next();
+    }
+    assert_eq!(cur.key(), None);
+    assert_eq!(map.len(), size);
+    assert!(map.keys().cloned().eq((0..size).map(|i| i * 2 + 1)));
+}
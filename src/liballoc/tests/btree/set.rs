@@ -1,5 +1,6 @@
 use std::collections::BTreeSet;
 use std::iter::FromIterator;
+use std::ops::Bound;
 use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::sync::atomic::{AtomicU32, Ordering};
 
@@ -633,3 +634,26 @@ fn test_split_off_large_random_sorted() {
     assert!(set.into_iter().eq(data.clone().into_iter().filter(|x| *x < key)));
     assert!(right.into_iter().eq(data.into_iter().filter(|x| *x >= key)));
 }
+
+#[test]
+fn test_cursor() {
+    let mut set: BTreeSet<_> = [1, 3, 5].iter().cloned().collect();
+
+    let mut cur = set.lower_bound(Bound::Included(&2));
+    assert_eq!(cur.get(), Some(&3));
+    assert_eq!(cur.peek_prev(), Some(&1));
+    cur.move_next();
+    cur.move_next();
+    assert_eq!(cur.get(), None);
+    assert_eq!(cur.peek_next(), Some(&1));
+
+    let mut cur = set.upper_bound_mut(Bound::Excluded(&5));
+    assert_eq!(cur.get(), Some(&3));
+    cur.insert_after(4);
+    cur.insert_before(2);
+    assert_eq!(cur.get(), Some(&3));
+    assert_eq!(cur.remove_current(), Some(3));
+    assert_eq!(cur.get(), Some(&4));
+    assert_eq!(cur.as_cursor().peek_prev(), Some(&2));
+    assert!(set.iter().cloned().eq(vec![1, 2, 4, 5]));
+}
@@ -714,3 +714,12 @@ fn test_try_reserve_exact() {
         }
     }
 }
+
+#[test]
+fn test_try_push() {
+    let mut s = String::try_with_capacity(1).unwrap();
+    s.try_push('a').unwrap();
+    s.try_push('ß').unwrap();
+    s.try_push_str("bc").unwrap();
+    assert_eq!(s, "aßbc");
+}
@@ -1,3 +1,4 @@
+use std::alloc::System;
 use std::borrow::Cow;
 use std::collections::TryReserveError::*;
 use std::fmt::Debug;
@@ -1627,3 +1628,42 @@ fn partialeq_vec_full() {
     assert_partial_eq_valid!(vec2,vec3; array2,array3);
     assert_partial_eq_valid!(vec2,vec3; arrayref2,arrayref3);
 }
+
+#[test]
+fn vec_in_custom_allocator() {
+    let mut vec = Vec::with_capacity_in(4, System);
+    assert!(vec.capacity() >= 4);
+    vec.extend_from_slice(&[1, 2, 3]);
+    vec.push(4);
+    vec.extend(5..8);
+    assert_eq!(vec, [1, 2, 3, 4, 5, 6, 7]);
+
+    let tail = vec.split_off(4);
+    assert_eq!(tail, [5, 6, 7]);
+    assert_eq!(vec.drain(1..3).collect::<Vec<_>>(), [2, 3]);
+    assert_eq!(vec, [1, 4]);
+
+    let clone = vec.clone();
+    assert_eq!(clone, vec);
+
+    let boxed = vec.into_boxed_slice();
+    assert_eq!(*boxed, [1, 4]);
+
+    let (ptr, len, cap, alloc) = clone.into_raw_parts_with_alloc();
+    let rebuilt = unsafe { Vec::from_raw_parts_in(ptr, len, cap, alloc) };
+    assert_eq!(rebuilt, [1, 4]);
+}
+
+#[test]
+fn test_try_push_and_extend() {
+    let mut vec: Vec<u32> = Vec::try_with_capacity(2).unwrap();
+    assert!(vec.capacity() >= 2);
+    vec.try_push(1).unwrap();
+    vec.try_extend_from_slice(&[2, 3, 4]).unwrap();
+    assert_eq!(vec, [1, 2, 3, 4]);
+
+    if let Err(CapacityOverflow) = Vec::<u32>::try_with_capacity(usize::MAX) {
+    } else {
+        panic!("usize::MAX should trigger an overflow!")
+    }
+}
@@ -1644,3 +1644,20 @@ fn test_drain_leak() {
     drop(v);
     assert_eq!(unsafe { DROPS }, 7);
 }
+
+#[test]
+fn test_try_push() {
+    let mut deque: VecDeque<u32> = VecDeque::try_with_capacity(1).unwrap();
+    for i in 0..10 {
+        deque.try_push_back(i).unwrap();
+        deque.try_push_front(i).unwrap();
+    }
+    assert_eq!(deque.len(), 20);
+    assert_eq!(deque.front(), Some(&9));
+    assert_eq!(deque.back(), Some(&9));
+
+    if let Err(CapacityOverflow) = VecDeque::<u32>::try_with_capacity(usize::MAX) {
+    } else {
+        panic!("usize::MAX should trigger an overflow!")
+    }
+}
@@ -142,6 +142,7 @@
 #![feature(abi_unadjusted)]
 #![feature(adx_target_feature)]
 #![feature(maybe_uninit_slice)]
+#![cfg_attr(not(bootstrap), feature(must_not_suspend))]
 #![feature(external_doc)]
 #![feature(associated_type_bounds)]
 #![feature(const_type_id)]
@@ -13,6 +13,7 @@ use rustc_middle::ty::query::Providers;
 use rustc_middle::ty::{self, TyCtxt};
 use rustc_session::config::{OptLevel, SanitizerSet};
 use rustc_session::Session;
+use rustc_target::spec::StackProtector;
 
 use crate::attributes;
 use crate::llvm::AttributePlace::Function;
@@ -58,6 +59,18 @@ pub fn sanitize(cx: &CodegenCx<'ll, '_>, no_sanitize: SanitizerSet, llfn: &'ll V
     }
 }
 
+/// Tell LLVM which stack smashing protection to use for the function.
+#[inline]
+fn stack_protector(cx: &CodegenCx<'ll, '_>, llfn: &'ll Value) {
+    let attr = match cx.sess().stack_protector() {
+        StackProtector::None => return,
+        StackProtector::Basic => Attribute::StackProtect,
+        StackProtector::Strong => Attribute::StackProtectStrong,
+        StackProtector::All => Attribute::StackProtectReq,
+    };
+    attr.apply_llfn(Function, llfn);
+}
+
 /// Tell LLVM to emit or not emit the information necessary to unwind the stack for the function.
 #[inline]
 pub fn emit_uwtable(val: &'ll Value, emit: bool) {
@@ -273,6 +286,11 @@ pub fn from_fn_attrs(cx: &CodegenCx<'ll, 'tcx>, llfn: &'ll Value, instance: ty::
     set_instrument_function(cx, llfn);
     set_probestack(cx, llfn);
 
+    // Naked functions have no prologue in which to set up a stack canary.
+    if !codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::NAKED) {
+        stack_protector(cx, llfn);
+    }
+
     if codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::COLD) {
         Attribute::Cold.apply_llfn(Function, llfn);
     }
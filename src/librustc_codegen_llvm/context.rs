@@ -1,5 +1,6 @@
 use crate::attributes;
 use crate::callee::get_fn;
+use crate::coverageinfo;
 use crate::debuginfo;
 use crate::llvm;
 use crate::llvm_util;
@@ -23,6 +24,7 @@ use rustc_span::symbol::Symbol;
 use rustc_target::abi::{HasDataLayout, LayoutOf, PointeeInfo, Size, TargetDataLayout, VariantIdx};
 use rustc_target::spec::{HasTargetSpec, RelocModel, Target, TlsModel};
 
+use libc::c_uint;
 use std::cell::{Cell, RefCell};
 use std::ffi::CStr;
 use std::str;
@@ -77,6 +79,7 @@ pub struct CodegenCx<'ll, 'tcx> {
     pub pointee_infos: RefCell<FxHashMap<(Ty<'tcx>, Size), Option<PointeeInfo>>>,
     pub isize_ty: &'ll Type,
 
+    pub coverage_cx: Option<coverageinfo::CrateCoverageContext<'ll, 'tcx>>,
     pub dbg_cx: Option<debuginfo::CrateDebugContext<'ll, 'tcx>>,
 
     eh_personality: Cell<Option<&'ll Value>>,
@@ -256,6 +259,12 @@ impl<'ll, 'tcx> CodegenCx<'ll, 'tcx> {
 
         let (llcx, llmod) = (&*llvm_module.llcx, llvm_module.llmod());
 
+        let coverage_cx = if tcx.sess.opts.debugging_opts.instrument_coverage {
+            Some(coverageinfo::CrateCoverageContext::new())
+        } else {
+            None
+        };
+
         let dbg_cx = if tcx.sess.opts.debuginfo != DebugInfo::None {
             let dctx = debuginfo::CrateDebugContext::new(llmod);
             debuginfo::metadata::compile_unit_metadata(tcx, &codegen_unit.name().as_str(), &dctx);
@@ -285,6 +294,7 @@ impl<'ll, 'tcx> CodegenCx<'ll, 'tcx> {
             scalar_lltypes: Default::default(),
             pointee_infos: Default::default(),
             isize_ty,
+            coverage_cx,
             dbg_cx,
             eh_personality: Cell::new(None),
             rust_try_fn: Cell::new(None),
@@ -296,6 +306,11 @@ impl<'ll, 'tcx> CodegenCx<'ll, 'tcx> {
     crate fn statics_to_rauw(&self) -> &RefCell<Vec<(&'ll Value, &'ll Value)>> {
         &self.statics_to_rauw
     }
+
+    #[inline]
+    crate fn coverage_context(&self) -> Option<&coverageinfo::CrateCoverageContext<'ll, 'tcx>> {
+        self.coverage_cx.as_ref()
+    }
 }
 
 impl MiscMethods<'tcx> for CodegenCx<'ll, 'tcx> {
@@ -758,6 +773,7 @@ impl CodegenCx<'b, 'tcx> {
         ifn!("llvm.x86.seh.recoverfp", fn(i8p, i8p) -> i8p);
 
         ifn!("llvm.assume", fn(i1) -> void);
+        ifn!("llvm.type.test", fn(i8p, self.type_metadata()) -> i1);
         ifn!("llvm.prefetch", fn(i8p, t_i32, t_i32, t_i32) -> void);
 
         // variadic intrinsics
@@ -787,6 +803,24 @@ impl<'b, 'tcx> CodegenCx<'b, 'tcx> {
         base_n::push_str(idx as u128, base_n::ALPHANUMERIC_ONLY, &mut name);
         name
     }
+
+    /// Returns the metadata string naming a `-Z sanitizer=cfi` type identifier.
+    pub fn typeid_metadata(&self, typeid: &str) -> &'b Value {
+        unsafe {
+            llvm::LLVMMDStringInContext(self.llcx, typeid.as_ptr().cast(), typeid.len() as c_uint)
+        }
+    }
+
+    /// Attaches `!type !{i64 0, !"<typeid>"}` to `llfn`, marking it as a valid
+    /// target for indirect calls checked against `typeid`.
+    pub fn add_type_metadata(&self, llfn: &'b Value, typeid: &str) {
+        let offset = self.const_u64(0);
+        let data = [offset, self.typeid_metadata(typeid)];
+        unsafe {
+            let node = llvm::LLVMMDNodeInContext(self.llcx, data.as_ptr(), data.len() as c_uint);
+            llvm::LLVMRustGlobalAddMetadata(llfn, llvm::MD_type as c_uint, node);
+        }
+    }
 }
 
 impl HasDataLayout for CodegenCx<'ll, 'tcx> {
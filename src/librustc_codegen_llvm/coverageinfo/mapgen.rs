@@ -0,0 +1,311 @@
+use crate::common::CodegenCx;
+use crate::coverageinfo;
+use crate::llvm;
+
+use llvm::coverageinfo::CounterMappingRegion;
+use log::debug;
+use rustc_codegen_ssa::coverageinfo::map::{Counter, CounterExpression, FunctionCoverage};
+use rustc_codegen_ssa::traits::*;
+use rustc_data_structures::const_cstr;
+use rustc_data_structures::fx::FxIndexSet;
+use rustc_hir::def_id::{DefId, LOCAL_CRATE};
+use rustc_llvm::RustString;
+use rustc_middle::mir::coverage::{CodeRegion, CoverageKind};
+use rustc_middle::mir::StatementKind;
+use rustc_middle::ty::subst::InternalSubsts;
+use rustc_middle::ty::{self, Instance, TyCtxt};
+
+use std::ffi::CString;
+use std::path::PathBuf;
+
+/// Generates the coverage map of the codegen unit, in version 3 of the
+/// [LLVM Code Coverage Mapping Format](https://llvm.org/docs/CoverageMappingFormat.html), the
+/// version read by the `llvm-profdata` and `llvm-cov` tools of Rust's LLVM. The layout of the
+/// map follows the one Clang generates.
+pub fn finalize<'ll, 'tcx>(cx: &CodegenCx<'ll, 'tcx>) {
+    let tcx = cx.tcx;
+    let function_coverage_map =
+        cx.coverage_context().expect("coverage is enabled").take_function_coverage_map();
+
+    let mut functions: Vec<(String, FunctionCoverage)> = function_coverage_map
+        .into_iter()
+        .map(|(instance, function_coverage)| {
+            (tcx.symbol_name(instance).name.to_string(), function_coverage)
+        })
+        .collect();
+
+    // The functions of the crate that are never codegenned still need zero-count records, or
+    // they would be left out of the coverage report. Only one codegen unit emits them.
+    let mut unused_name_vars = Vec::new();
+    if is_first_codegen_unit(cx) {
+        for def_id in unused_function_def_ids(tcx) {
+            if let Some(function_coverage) = unused_function_coverage(tcx, def_id) {
+                let instance = unused_function_instance(tcx, def_id);
+                let mangled_fn_name = tcx.symbol_name(instance).name.to_string();
+                unused_name_vars.push(create_unused_pgo_func_name_var(cx, &mangled_fn_name));
+                functions.push((mangled_fn_name, function_coverage));
+            }
+        }
+    }
+    if functions.is_empty() {
+        return;
+    }
+    // Emit the records in a stable order.
+    functions.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let mut mapgen = CoverageMapGenerator::new(tcx.sess.working_dir.0.clone());
+
+    // Encode the coverage mappings, and generate the function records
+    let mut function_records = Vec::with_capacity(functions.len());
+    let mut coverage_mappings_buffer = Vec::new();
+    for (mangled_function_name, function_coverage) in &functions {
+        let (expressions, counter_regions) =
+            function_coverage.get_expressions_and_counter_regions();
+        let mut mapping_data = llvm::build_byte_buffer(|buffer| {
+            mapgen.write_coverage_mappings(expressions, counter_regions, buffer);
+        });
+        debug_assert!(!mapping_data.is_empty(), "every function has at least one code region");
+
+        function_records.push(make_function_record(
+            cx,
+            mangled_function_name,
+            function_coverage.source_hash(),
+            mapping_data.len(),
+        ));
+        coverage_mappings_buffer.append(&mut mapping_data);
+    }
+
+    // Encode all filenames referenced by counters/expressions in this module
+    let filenames_buffer = llvm::build_byte_buffer(|filenames_buffer| {
+        coverageinfo::write_filenames_section_to_buffer(&mapgen.filenames, filenames_buffer);
+    });
+
+    // Generate the LLVM IR representation of the coverage map and store it in a well-known global
+    save_generated_coverage_map(cx, function_records, filenames_buffer, coverage_mappings_buffer);
+
+    if !unused_name_vars.is_empty() {
+        save_unused_name_vars(cx, &unused_name_vars);
+    }
+}
+
+struct CoverageMapGenerator {
+    /// The directory relative file names are resolved against: the coverage map has no
+    /// compilation directory, so `llvm-cov` needs absolute file names.
+    working_dir: PathBuf,
+    filenames: FxIndexSet<CString>,
+}
+
+impl CoverageMapGenerator {
+    fn new(working_dir: PathBuf) -> Self {
+        Self { working_dir, filenames: FxIndexSet::default() }
+    }
+
+    /// Encodes the coverage mapping of a function (its virtual file mapping, counter expressions
+    /// and mapping regions) into `coverage_mappings_buffer`, and adds the files it refers to to
+    /// the filenames of the coverage map.
+    fn write_coverage_mappings(
+        &mut self,
+        expressions: Vec<CounterExpression>,
+        counter_regions: Vec<(Counter, &CodeRegion)>,
+        coverage_mappings_buffer: &RustString,
+    ) {
+        let mut virtual_file_mapping = Vec::new();
+        let mut mapping_regions = Vec::new();
+        let mut current_file_name = None;
+        let mut current_file_id = 0;
+
+        // The regions are sorted by file name, so a new file ID is needed each time the file name
+        // changes.
+        for (counter, region) in counter_regions {
+            let CodeRegion { file_name, start_line, start_col, end_line, end_col } = *region;
+            let same_file = current_file_name.map_or(false, |p| p == file_name);
+            if !same_file {
+                if current_file_name.is_some() {
+                    current_file_id += 1;
+                }
+                current_file_name = Some(file_name);
+                let path = self.working_dir.join(&*file_name.as_str());
+                let c_filename = CString::new(path.to_string_lossy().into_owned())
+                    .expect("null error converting filename to C string");
+                debug!("  file_id: {} = '{:?}'", current_file_id, c_filename);
+                let (filenames_index, _) = self.filenames.insert_full(c_filename);
+                virtual_file_mapping.push(filenames_index as u32);
+            }
+            mapping_regions.push(CounterMappingRegion::code_region(
+                counter,
+                current_file_id,
+                start_line,
+                start_col,
+                end_line,
+                end_col,
+            ));
+        }
+
+        // Encode and append the current function's coverage mapping data
+        coverageinfo::write_mapping_to_buffer(
+            virtual_file_mapping,
+            expressions,
+            mapping_regions,
+            coverage_mappings_buffer,
+        );
+    }
+}
+
+/// Combines the header, the function records, the encoded filenames and the encoded coverage
+/// mappings into the coverage map, and saves it to the module.
+fn save_generated_coverage_map(
+    cx: &CodegenCx<'ll, 'tcx>,
+    function_records: Vec<&'ll llvm::Value>,
+    filenames_buffer: Vec<u8>,
+    mut coverage_mappings_buffer: Vec<u8>,
+) {
+    // Concatenate the encoded filenames and encoded coverage mappings, and add additional zero
+    // bytes as-needed to ensure 8-byte alignment.
+    let mut coverage_size = coverage_mappings_buffer.len();
+    let filenames_size = filenames_buffer.len();
+    let remaining_bytes =
+        (filenames_size + coverage_size) % coverageinfo::COVMAP_VAR_ALIGN_BYTES as usize;
+    if remaining_bytes > 0 {
+        let pad = coverageinfo::COVMAP_VAR_ALIGN_BYTES as usize - remaining_bytes;
+        coverage_mappings_buffer.append(&mut [0].repeat(pad));
+        coverage_size += pad;
+    }
+    let filenames_and_coverage_mappings = [filenames_buffer, coverage_mappings_buffer].concat();
+    let filenames_and_coverage_mappings_val = cx.const_bytes(&filenames_and_coverage_mappings);
+
+    debug!(
+        "cov map: n_records = {}, filenames_size = {}, coverage_size = {}, \
+        0-based version = {}",
+        function_records.len(),
+        filenames_size,
+        coverage_size,
+        coverageinfo::mapping_version()
+    );
+
+    // Create the coverage data header
+    let n_records_val = cx.const_u32(function_records.len() as u32);
+    let filenames_size_val = cx.const_u32(filenames_size as u32);
+    let coverage_size_val = cx.const_u32(coverage_size as u32);
+    let version_val = cx.const_u32(coverageinfo::mapping_version());
+    let cov_data_header_val = cx.const_struct(
+        &[n_records_val, filenames_size_val, coverage_size_val, version_val],
+        /*packed=*/ false,
+    );
+
+    // Create the function records array
+    let name_ref_from_u64 = cx.type_i64();
+    let mapping_data_size_from_u32 = cx.type_i32();
+    let func_hash_from_u64 = cx.type_i64();
+    let function_record_ty = cx.type_struct(
+        &[name_ref_from_u64, mapping_data_size_from_u32, func_hash_from_u64],
+        /*packed=*/ true,
+    );
+    let function_records_val = cx.const_array(function_record_ty, &function_records[..]);
+
+    // Create the complete LLVM coverage data value to add to the LLVM IR
+    let cov_data_val = cx.const_struct(
+        &[cov_data_header_val, function_records_val, filenames_and_coverage_mappings_val],
+        /*packed=*/ false,
+    );
+
+    // Save the coverage data value to LLVM IR
+    coverageinfo::save_map_to_mod(cx, cov_data_val);
+}
+
+/// Generates a function record: the hash of the function's name, the size of its encoded
+/// coverage mapping, and the hash of its source code.
+fn make_function_record(
+    cx: &CodegenCx<'ll, 'tcx>,
+    mangled_function_name: &str,
+    function_source_hash: u64,
+    mapping_data_size: usize,
+) -> &'ll llvm::Value {
+    let name_ref = coverageinfo::compute_hash(mangled_function_name);
+    let name_ref_val = cx.const_u64(name_ref);
+    let mapping_data_size_val = cx.const_u32(mapping_data_size as u32);
+    let func_hash_val = cx.const_u64(function_source_hash);
+    cx.const_struct(&[name_ref_val, mapping_data_size_val, func_hash_val], /*packed=*/ true)
+}
+
+/// Returns whether `cx` is codegenning the first codegen unit of the crate, which is the one that
+/// emits the coverage records of unused functions.
+fn is_first_codegen_unit(cx: &CodegenCx<'_, '_>) -> bool {
+    let (_, codegen_units) = cx.tcx.collect_and_partition_mono_items(LOCAL_CRATE);
+    codegen_units.first().map_or(false, |cgu| cgu.name() == cx.codegen_unit.name())
+}
+
+/// The functions, methods and closures of the local crate that have coverage counters, but are not
+/// codegenned at all: they have no mono items, so their coverage is never collected. Generic
+/// functions are unused only if they have no instantiation in the crate.
+fn unused_function_def_ids(tcx: TyCtxt<'_>) -> Vec<DefId> {
+    let (codegenned_def_ids, _) = tcx.collect_and_partition_mono_items(LOCAL_CRATE);
+    tcx.body_owners()
+        .map(|local_def_id| local_def_id.to_def_id())
+        .filter(|&def_id| {
+            let hir_id = tcx.hir().as_local_hir_id(def_id.expect_local());
+            tcx.hir().body_owner_kind(hir_id).is_fn_or_closure()
+                && !codegenned_def_ids.contains(&def_id)
+        })
+        .collect()
+}
+
+/// Returns the instance naming the coverage records of an unused function. Generic functions and
+/// closures have no instantiation to be named after, so they are instantiated with their own
+/// generic parameters, which gives them a symbol name no real instantiation has.
+fn unused_function_instance(tcx: TyCtxt<'tcx>, def_id: DefId) -> Instance<'tcx> {
+    let substs = InternalSubsts::for_item(tcx, def_id, |param, _| match param.kind {
+        ty::GenericParamDefKind::Lifetime => tcx.lifetimes.re_erased.into(),
+        _ => tcx.mk_param_from_def(param),
+    });
+    Instance::new(def_id, substs)
+}
+
+/// Collects the code regions of an unused function from its MIR, all with a count of zero.
+/// Returns `None` if the function was not instrumented.
+fn unused_function_coverage(tcx: TyCtxt<'_>, def_id: DefId) -> Option<FunctionCoverage> {
+    let mut function_source_hash = None;
+    let mut code_regions = Vec::new();
+    for data in tcx.optimized_mir(def_id).basic_blocks() {
+        for statement in &data.statements {
+            if let StatementKind::Coverage(coverage) = &statement.kind {
+                if let CoverageKind::Counter { function_source_hash: hash, .. } = coverage.kind {
+                    function_source_hash = Some(hash);
+                }
+                code_regions.extend(coverage.code_regions.iter().cloned());
+            }
+        }
+    }
+    if code_regions.is_empty() {
+        return None;
+    }
+    Some(FunctionCoverage::unused(function_source_hash?, code_regions))
+}
+
+/// Creates the variable holding the name of an unused function. The names of functions that are
+/// codegenned are collected by LLVM from their `llvm.instrprof.increment` calls.
+fn create_unused_pgo_func_name_var(
+    cx: &CodegenCx<'ll, 'tcx>,
+    mangled_fn_name: &str,
+) -> &'ll llvm::Value {
+    let mangled_fn_name =
+        CString::new(mangled_fn_name).expect("error converting function name to C string");
+    unsafe { llvm::LLVMRustCoverageCreateUnusedPGOFuncNameVar(cx.llmod, mangled_fn_name.as_ptr()) }
+}
+
+/// Creates the `__llvm_coverage_names` array, from which LLVM's InstrProf lowering adds the names
+/// of unused functions to the profile's names section. The array itself is removed by the
+/// lowering.
+fn save_unused_name_vars(cx: &CodegenCx<'ll, 'tcx>, name_vars: &[&'ll llvm::Value]) {
+    let name_vars: Vec<_> = name_vars
+        .iter()
+        .map(|&name_var| unsafe { llvm::LLVMConstPointerCast(name_var, cx.type_i8p()) })
+        .collect();
+    let array = cx.const_array(cx.type_i8p(), &name_vars);
+    let name = const_cstr!("__llvm_coverage_names");
+    unsafe {
+        let g = llvm::LLVMAddGlobal(cx.llmod, cx.val_ty(array), name.as_ptr());
+        llvm::LLVMSetInitializer(g, array);
+        llvm::LLVMSetGlobalConstant(g, llvm::True);
+        llvm::LLVMRustSetLinkage(g, llvm::Linkage::InternalLinkage);
+    }
+}
@@ -0,0 +1,195 @@
+use crate::llvm;
+
+use crate::builder::Builder;
+use crate::common::CodegenCx;
+use crate::llvm::coverageinfo::CounterMappingRegion;
+use crate::value::Value;
+
+use libc::c_uint;
+use log::debug;
+use rustc_codegen_ssa::coverageinfo::map::{CounterExpression, FunctionCoverage};
+use rustc_codegen_ssa::traits::*;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_llvm::RustString;
+use rustc_middle::mir::coverage::*;
+use rustc_middle::ty::Instance;
+
+use std::cell::RefCell;
+use std::ffi::CString;
+
+pub mod mapgen;
+
+/// The alignment LLVM requires of the coverage map global.
+const COVMAP_VAR_ALIGN_BYTES: c_uint = 8;
+
+/// A context object for maintaining all state needed by the coverageinfo module.
+pub struct CrateCoverageContext<'ll, 'tcx> {
+    /// The coverage collected so far for each instrumented function instance.
+    pub(crate) function_coverage_map: RefCell<FxHashMap<Instance<'tcx>, FunctionCoverage>>,
+    pgo_func_name_var_map: RefCell<FxHashMap<Instance<'tcx>, &'ll Value>>,
+}
+
+impl<'ll, 'tcx> CrateCoverageContext<'ll, 'tcx> {
+    pub fn new() -> Self {
+        Self {
+            function_coverage_map: Default::default(),
+            pgo_func_name_var_map: Default::default(),
+        }
+    }
+
+    pub fn take_function_coverage_map(&self) -> FxHashMap<Instance<'tcx>, FunctionCoverage> {
+        self.function_coverage_map.replace(FxHashMap::default())
+    }
+}
+
+impl CoverageInfoMethods for CodegenCx<'ll, 'tcx> {
+    fn coverageinfo_finalize(&self) {
+        if self.coverage_context().is_some() {
+            mapgen::finalize(self)
+        }
+    }
+}
+
+impl CoverageInfoBuilderMethods<'tcx> for Builder<'a, 'll, 'tcx> {
+    /// Returns the `llvm::GlobalVariable` holding the function instance's mangled name, with the
+    /// variable name and linkage required by LLVM InstrProf instrumentation. The variable is
+    /// created on first use, and reused for every counter of the instance.
+    fn create_pgo_func_name_var(&self, instance: Instance<'tcx>) -> &'ll Value {
+        let coverage_context = self.coverage_context().expect("coverage is enabled");
+        *coverage_context
+            .pgo_func_name_var_map
+            .borrow_mut()
+            .entry(instance)
+            .or_insert_with(|| create_pgo_func_name_var(self, instance))
+    }
+
+    fn add_counter_region(
+        &mut self,
+        instance: Instance<'tcx>,
+        function_source_hash: u64,
+        id: CounterValueReference,
+        regions: Vec<CodeRegion>,
+    ) -> bool {
+        if let Some(coverage_context) = self.coverage_context() {
+            debug!(
+                "adding counter to coverage_map: instance={:?}, function_source_hash={}, id={:?}, \
+                at {:?}",
+                instance, function_source_hash, id, regions,
+            );
+            let mut coverage_map = coverage_context.function_coverage_map.borrow_mut();
+            coverage_map
+                .entry(instance)
+                .or_insert_with(|| FunctionCoverage::new(self.tcx, instance))
+                .add_counter(function_source_hash, id, regions);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn add_counter_expression_region(
+        &mut self,
+        instance: Instance<'tcx>,
+        id: InjectedExpressionIndex,
+        lhs: CoverageTerm,
+        op: Op,
+        rhs: CoverageTerm,
+        regions: Vec<CodeRegion>,
+    ) -> bool {
+        if let Some(coverage_context) = self.coverage_context() {
+            debug!(
+                "adding counter expression to coverage_map: instance={:?}, id={:?}, {:?} {:?} \
+                {:?}, at {:?}",
+                instance, id, lhs, op, rhs, regions,
+            );
+            let mut coverage_map = coverage_context.function_coverage_map.borrow_mut();
+            coverage_map
+                .entry(instance)
+                .or_insert_with(|| FunctionCoverage::new(self.tcx, instance))
+                .add_counter_expression(id, lhs, op, rhs, regions);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+fn create_pgo_func_name_var(bx: &Builder<'a, 'll, 'tcx>, instance: Instance<'tcx>) -> &'ll Value {
+    let mangled_fn_name = CString::new(bx.tcx.symbol_name(instance).name.as_str().as_bytes())
+        .expect("error converting function name to C string");
+    let llfn = bx.cx.get_fn(instance);
+    unsafe { llvm::LLVMRustCoverageCreatePGOFuncNameVar(llfn, mangled_fn_name.as_ptr()) }
+}
+
+pub(crate) fn write_filenames_section_to_buffer<'a>(
+    filenames: impl IntoIterator<Item = &'a CString>,
+    buffer: &RustString,
+) {
+    let c_str_vec = filenames.into_iter().map(|cstring| cstring.as_ptr()).collect::<Vec<_>>();
+    unsafe {
+        llvm::LLVMRustCoverageWriteFilenamesSectionToBuffer(
+            c_str_vec.as_ptr(),
+            c_str_vec.len(),
+            buffer,
+        );
+    }
+}
+
+pub(crate) fn write_mapping_to_buffer(
+    virtual_file_mapping: Vec<u32>,
+    expressions: Vec<CounterExpression>,
+    mut mapping_regions: Vec<CounterMappingRegion>,
+    buffer: &RustString,
+) {
+    unsafe {
+        llvm::LLVMRustCoverageWriteMappingToBuffer(
+            virtual_file_mapping.as_ptr(),
+            virtual_file_mapping.len() as c_uint,
+            expressions.as_ptr(),
+            expressions.len() as c_uint,
+            mapping_regions.as_mut_ptr(),
+            mapping_regions.len() as c_uint,
+            buffer,
+        );
+    }
+}
+
+pub(crate) fn compute_hash(name: &str) -> u64 {
+    let name = CString::new(name).expect("null error converting hashable name to C string");
+    unsafe { llvm::LLVMRustCoverageComputeHash(name.as_ptr()) }
+}
+
+pub(crate) fn mapping_version() -> u32 {
+    unsafe { llvm::LLVMRustCoverageMappingVersion() }
+}
+
+/// Creates the `__llvm_coverage_mapping` global, with the linkage, section and alignment LLVM
+/// expects for the coverage map.
+pub(crate) fn save_map_to_mod<'ll, 'tcx>(cx: &CodegenCx<'ll, 'tcx>, cov_data_val: &'ll Value) {
+    let covmap_var_name = llvm::build_string(|s| unsafe {
+        llvm::LLVMRustCoverageWriteMappingVarNameToString(s);
+    })
+    .expect("Rust Coverage Mapping var name failed UTF-8 conversion");
+    debug!("covmap var name: {:?}", covmap_var_name);
+
+    let covmap_section_name = llvm::build_string(|s| unsafe {
+        llvm::LLVMRustCoverageWriteSectionNameToString(cx.llmod, s);
+    })
+    .expect("Rust Coverage section name failed UTF-8 conversion");
+    debug!("covmap section name: {:?}", covmap_section_name);
+
+    let covmap_var_name = CString::new(covmap_var_name).unwrap();
+    let covmap_section_name = CString::new(covmap_section_name).unwrap();
+    unsafe {
+        let llglobal =
+            llvm::LLVMAddGlobal(cx.llmod, cx.val_ty(cov_data_val), covmap_var_name.as_ptr());
+        llvm::LLVMSetInitializer(llglobal, cov_data_val);
+        llvm::LLVMSetGlobalConstant(llglobal, llvm::True);
+        llvm::LLVMRustSetLinkage(llglobal, llvm::Linkage::InternalLinkage);
+        llvm::LLVMSetSection(llglobal, covmap_section_name.as_ptr());
+        llvm::LLVMSetAlignment(llglobal, COVMAP_VAR_ALIGN_BYTES);
+        // The coverage map is not referenced by any code: keep it in the `llvm.used` variable.
+        let cast = llvm::LLVMConstPointerCast(llglobal, cx.type_i8p());
+        cx.used_statics.borrow_mut().push(cast);
+    }
+}
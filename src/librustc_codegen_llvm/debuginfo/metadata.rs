@@ -954,6 +954,9 @@ pub fn compile_unit_metadata(
             split_name.as_ptr().cast(),
             split_name.len(),
             kind,
+            // Sample profiles are more accurate with the extra debuginfo LLVM emits for
+            // profiling.
+            tcx.sess.opts.cg.profile_sample_use.is_some(),
         );
 
         if tcx.sess.opts.debugging_opts.profile {
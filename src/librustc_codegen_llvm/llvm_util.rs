@@ -4,7 +4,7 @@ use libc::c_int;
 use rustc_data_structures::fx::FxHashSet;
 use rustc_feature::UnstableFeatures;
 use rustc_middle::bug;
-use rustc_session::config::PrintRequest;
+use rustc_session::config::{DebugInfoCompression, PrintRequest};
 use rustc_session::Session;
 use rustc_span::symbol::sym;
 use rustc_span::symbol::Symbol;
@@ -35,9 +35,23 @@ pub(crate) fn init(sess: &Session) {
         if POISONED.load(Ordering::SeqCst) {
             bug!("couldn't enable multi-threaded LLVM");
         }
+
+        if sess.opts.debugging_opts.debuginfo_compression == DebugInfoCompression::Zlib
+            && !has_zlib_compression()
+        {
+            sess.warn(
+                "`-Z debuginfo-compression=zlib` is ignored for object files because LLVM \
+                 was built without zlib support",
+            );
+        }
     }
 }
 
+/// Whether LLVM was built with zlib, which compressing debuginfo sections requires.
+pub(crate) fn has_zlib_compression() -> bool {
+    unsafe { llvm::LLVMRustLLVMHasZlibCompression() }
+}
+
 fn require_inited() {
     INIT.call_once(|| bug!("llvm is not initialized"));
     if POISONED.load(Ordering::SeqCst) {
@@ -4,15 +4,22 @@ use crate::base;
 use crate::context::CodegenCx;
 use crate::llvm;
 use crate::type_of::LayoutLlvmExt;
+use crate::value::Value;
 use log::debug;
 use rustc_codegen_ssa::traits::*;
+use rustc_hir as hir;
 use rustc_hir::def_id::{DefId, LOCAL_CRATE};
 pub use rustc_middle::mir::mono::MonoItem;
+use rustc_middle::bug;
 use rustc_middle::mir::mono::{Linkage, Visibility};
 use rustc_middle::ty::layout::FnAbiExt;
-use rustc_middle::ty::{Instance, TypeFoldable};
+use rustc_middle::ty::{self, Instance, TypeFoldable};
+use rustc_session::config::SanitizerSet;
+use rustc_symbol_mangling::{typeid_for_fn_sig, typeid_for_virtual_call};
 use rustc_target::abi::LayoutOf;
 
+use std::iter;
+
 impl PreDefineMethods<'tcx> for CodegenCx<'ll, 'tcx> {
     fn predefine_static(
         &self,
@@ -79,6 +86,74 @@ impl PreDefineMethods<'tcx> for CodegenCx<'ll, 'tcx> {
 
         attributes::from_fn_attrs(self, lldecl, instance);
 
+        if self.tcx.sess.opts.debugging_opts.sanitizer.contains(SanitizerSet::CFI) {
+            self.add_type_metadata_for_instance(lldecl, instance);
+        }
+
         self.instances.borrow_mut().insert(instance, lldecl);
     }
 }
+
+impl CodegenCx<'ll, 'tcx> {
+    /// Attaches the `-Z sanitizer=cfi` type identifiers `instance` can be called
+    /// through: its `fn` pointer type, and for trait methods, closures and drop
+    /// glue, the `dyn Trait` method it implements.
+    fn add_type_metadata_for_instance(&self, llfn: &'ll Value, instance: Instance<'tcx>) {
+        let tcx = self.tcx;
+        let ty = instance.monomorphic_ty(tcx);
+        let (fn_sig, virtual_sig) = match (instance.def, &ty.kind) {
+            // A `Fn` or `FnMut` closure coerced to a `fn` pointer is reified as
+            // this shim of `FnOnce::call_once`, which is called through the `fn`
+            // pointer with the un-tupled signature of the closure, and through
+            // `dyn FnOnce` vtables as `call_once`.
+            (ty::InstanceDef::ClosureOnceShim { .. }, _) => {
+                let closure_sig = match instance.substs.type_at(0).kind {
+                    ty::Closure(_, substs) => substs.as_closure().sig(),
+                    _ => bug!("closure shim {:?} for a non-closure type", instance),
+                };
+                let fn_sig = ty.fn_sig(tcx);
+                (tcx.signature_unclosure(closure_sig, hir::Unsafety::Normal), Some(fn_sig))
+            }
+            // Drop glue is called directly, and as the destructor of vtables.
+            (ty::InstanceDef::DropGlue(..), _) => {
+                let fn_sig = ty.fn_sig(tcx);
+                (fn_sig, Some(fn_sig))
+            }
+            (_, ty::FnDef(..)) | (_, ty::FnPtr(_)) => {
+                let fn_sig = ty.fn_sig(tcx);
+                let def_id = instance.def_id();
+                let is_trait_method = tcx.trait_of_item(def_id).is_some()
+                    || tcx.impl_of_method(def_id).and_then(|i| tcx.trait_id_of_impl(i)).is_some();
+                if is_trait_method && tcx.associated_item(def_id).fn_has_self_parameter {
+                    (fn_sig, Some(fn_sig))
+                } else {
+                    (fn_sig, None)
+                }
+            }
+            (_, &ty::Closure(def_id, substs)) => {
+                // The body of a `FnOnce` closure is the target of the `fn`
+                // pointers it is coerced to, and is called without its
+                // environment through them, but with it through vtables.
+                let sig = substs.as_closure().sig();
+                let env_ty = tcx.closure_env_ty(def_id, substs).unwrap();
+                let virtual_sig = sig.map_bound(|sig| {
+                    tcx.mk_fn_sig(
+                        iter::once(*env_ty.skip_binder()).chain(sig.inputs().iter().cloned()),
+                        sig.output(),
+                        sig.c_variadic,
+                        sig.unsafety,
+                        sig.abi,
+                    )
+                });
+                (tcx.signature_unclosure(sig, hir::Unsafety::Normal), Some(virtual_sig))
+            }
+            // Generators are only ever resumed directly.
+            _ => return,
+        };
+
+        self.add_type_metadata(llfn, &typeid_for_fn_sig(tcx, fn_sig));
+        if let Some(virtual_sig) = virtual_sig {
+            self.add_type_metadata(llfn, &typeid_for_virtual_call(tcx, virtual_sig));
+        }
+    }
+}
@@ -4,16 +4,25 @@ use rustc_span::symbol::Symbol;
 use std::io;
 use std::path::{Path, PathBuf};
 
-pub fn find_library(name: Symbol, search_paths: &[PathBuf], sess: &Session) -> PathBuf {
+pub fn find_library(
+    name: Symbol,
+    verbatim: bool,
+    search_paths: &[PathBuf],
+    sess: &Session,
+) -> PathBuf {
     // On Windows, static libraries sometimes show up as libfoo.a and other
     // times show up as foo.lib
-    let oslibname = format!(
-        "{}{}{}",
-        sess.target.target.options.staticlib_prefix,
-        name,
-        sess.target.target.options.staticlib_suffix
-    );
-    let unixlibname = format!("lib{}.a", name);
+    let oslibname = if verbatim {
+        name.to_string()
+    } else {
+        format!(
+            "{}{}{}",
+            sess.target.target.options.staticlib_prefix,
+            name,
+            sess.target.target.options.staticlib_suffix
+        )
+    };
+    let unixlibname = if verbatim { name.to_string() } else { format!("lib{}.a", name) };
 
     for path in search_paths {
         debug!("looking for {} inside {:?}", name, path);
@@ -49,7 +58,7 @@ pub trait ArchiveBuilder<'a> {
         lto: bool,
         skip_objects: bool,
     ) -> io::Result<()>;
-    fn add_native_library(&mut self, name: Symbol);
+    fn add_native_library(&mut self, name: Symbol, verbatim: bool);
     fn update_symbols(&mut self);
 
     fn build(self);
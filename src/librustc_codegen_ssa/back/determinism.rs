@@ -0,0 +1,250 @@
+//! Comparison of the outputs of the two compilations of `-Z verify-determinism`.
+//!
+//! Outputs are compared part by part, to point at the source of the nondeterminism rather than
+//! at a byte offset: archives member by member, ELF object files section by section, and crate
+//! metadata by the tables and other parts recorded while encoding it. The encoder records these
+//! parts in `Session::metadata_sections`.
+//!
+//! Later parts of the metadata refer to earlier ones by position, so a part that changes size
+//! changes every part after it. Only the first differing part is reported as such.
+
+use super::elf::{Elf, Section, SHT_NOBITS};
+
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+use std::str;
+
+const AR_MAGIC: &[u8] = b"!<arch>\n";
+const AR_HEADER_SIZE: usize = 60;
+const ELF_MAGIC: &[u8] = b"\x7fELF";
+/// The start of the metadata header, before the metadata version.
+const METADATA_MAGIC: &[u8] = b"rust\0\0\0";
+
+/// The name and the byte range of each part of the crate metadata.
+pub type MetadataSections = [(&'static str, Range<usize>)];
+
+/// Compares an output of the first compilation with the same output of the second one, and
+/// returns the parts that differ. The metadata sections of both compilations are used for the
+/// metadata that the outputs contain.
+pub fn compare_outputs(
+    first: &Path,
+    second: &Path,
+    metadata_sections: (&MetadataSections, &MetadataSections),
+) -> Result<Vec<String>, String> {
+    let read = |path: &Path| {
+        fs::read(path).map_err(|err| format!("failed to read `{}`: {}", path.display(), err))
+    };
+    let (first, second) = (read(first)?, read(second)?);
+    let mut cx = ComparisonCx { metadata_sections, differences: Vec::new() };
+    cx.compare_files("", &first, &second)?;
+    Ok(cx.differences)
+}
+
+struct ComparisonCx<'a> {
+    metadata_sections: (&'a MetadataSections, &'a MetadataSections),
+    differences: Vec<String>,
+}
+
+impl ComparisonCx<'_> {
+    /// Compares two files, or two members of an archive, named by `location`.
+    fn compare_files(&mut self, location: &str, first: &[u8], second: &[u8]) -> Result<(), String> {
+        if first == second {
+            return Ok(());
+        }
+        let both = |magic: &[u8]| first.starts_with(magic) && second.starts_with(magic);
+        if both(AR_MAGIC) {
+            self.compare_archives(location, first, second)
+        } else if both(METADATA_MAGIC) {
+            self.compare_metadata(location, first, second);
+            Ok(())
+        } else if both(ELF_MAGIC) {
+            self.compare_objects(location, first, second)
+        } else {
+            let offset = first_difference(first, second);
+            self.report(location, format!("the contents differ from byte {}", offset));
+            Ok(())
+        }
+    }
+
+    fn compare_archives(
+        &mut self,
+        location: &str,
+        first: &[u8],
+        second: &[u8],
+    ) -> Result<(), String> {
+        let first = archive_members(first)?;
+        let second = archive_members(second)?;
+        let differences = self.differences.len();
+        for (name, first_data) in &first {
+            let member = nested(location, format!("archive member `{}`", name));
+            match second.iter().find(|(n, _)| n == name) {
+                Some((_, second_data)) => self.compare_files(&member, first_data, second_data)?,
+                None => self.report(&member, "is missing from the second output".to_string()),
+            }
+        }
+        for (name, _) in &second {
+            if !first.iter().any(|(n, _)| n == name) {
+                let member = nested(location, format!("archive member `{}`", name));
+                self.report(&member, "is missing from the first output".to_string());
+            }
+        }
+        if self.differences.len() == differences {
+            self.report(location, "the headers of the archive members differ".to_string());
+        }
+        Ok(())
+    }
+
+    fn compare_metadata(&mut self, location: &str, first: &[u8], second: &[u8]) {
+        if first == second {
+            return;
+        }
+        let (first_sections, second_sections) = self.metadata_sections;
+        let sections = first_sections.iter().zip(second_sections);
+        let mut differing = sections.filter(|((_, first_range), (_, second_range))| {
+            first.get(first_range.clone()) != second.get(second_range.clone())
+        });
+        match differing.next() {
+            Some(((name, range), _)) => {
+                let later = differing.count();
+                let mut msg = format!("metadata `{}` differs, from byte {}", name, range.start);
+                if later > 0 {
+                    msg.push_str(&format!(
+                        ", as do {} later parts of the metadata, possibly because of it",
+                        later
+                    ));
+                }
+                self.report(location, msg);
+            }
+            // The parts aren't known if the metadata wasn't encoded by both compilations, and
+            // the position of the crate root isn't part of any of them.
+            None => {
+                let offset = first_difference(first, second);
+                self.report(location, format!("metadata differs from byte {}", offset));
+            }
+        }
+    }
+
+    fn compare_objects(
+        &mut self,
+        location: &str,
+        first: &[u8],
+        second: &[u8],
+    ) -> Result<(), String> {
+        let first = Elf::parse(first)?;
+        let second = Elf::parse(second)?;
+        let first_sections = named_sections(&first)?;
+        let second_sections = named_sections(&second)?;
+        let differences = self.differences.len();
+        for &(name, index, first_section) in &first_sections {
+            let section = nested(location, format!("section `{}`", name));
+            let second_section = second_sections
+                .iter()
+                .find(|&&(n, i, _)| n == name && i == index)
+                .map(|&(_, _, section)| section);
+            let second_section = match second_section {
+                Some(section) => section,
+                None => {
+                    self.report(&section, "is missing from the second output".to_string());
+                    continue;
+                }
+            };
+            if first_section.kind != second_section.kind {
+                self.report(&section, "has a different type".to_string());
+            } else if first_section.kind == SHT_NOBITS {
+                if first_section.size != second_section.size {
+                    self.report(&section, "has a different size".to_string());
+                }
+            } else {
+                let first_contents = first.contents(first_section)?;
+                let second_contents = second.contents(second_section)?;
+                if first_contents != second_contents {
+                    let offset = first_difference(first_contents, second_contents);
+                    self.report(&section, format!("differs from byte {}", offset));
+                }
+            }
+        }
+        for &(name, index, _) in &second_sections {
+            if !first_sections.iter().any(|&(n, i, _)| n == name && i == index) {
+                let section = nested(location, format!("section `{}`", name));
+                self.report(&section, "is missing from the first output".to_string());
+            }
+        }
+        if self.differences.len() == differences {
+            let msg = "the ELF headers or the layout of the sections differ".to_string();
+            self.report(location, msg);
+        }
+        Ok(())
+    }
+
+    fn report(&mut self, location: &str, msg: String) {
+        let msg = if location.is_empty() { msg } else { format!("{} {}", location, msg) };
+        self.differences.push(msg);
+    }
+}
+
+/// Names a part of the file or archive member named by `location`.
+fn nested(location: &str, part: String) -> String {
+    if location.is_empty() { part } else { format!("{}, {}", location, part) }
+}
+
+/// Returns the sections of an object file with their name, and the number of sections with
+/// the same name before them, as section groups and relocations make names repeat.
+fn named_sections<'a>(elf: &'a Elf<'_>) -> Result<Vec<(&'a str, usize, &'a Section)>, String> {
+    let mut sections: Vec<(&str, usize, _)> = Vec::new();
+    for section in &elf.sections {
+        let name = elf.section_name(section)?;
+        let index = sections.iter().filter(|&&(n, _, _)| n == name).count();
+        sections.push((name, index, section));
+    }
+    Ok(sections)
+}
+
+/// Returns the members of an archive, except for its symbol tables, with their names and
+/// contents. Both GNU and BSD long member names are supported.
+fn archive_members(data: &[u8]) -> Result<Vec<(String, &[u8])>, String> {
+    let mut members = Vec::new();
+    let mut long_names: &[u8] = &[];
+    let mut offset = AR_MAGIC.len();
+    while offset < data.len() {
+        let header = data
+            .get(offset..offset + AR_HEADER_SIZE)
+            .ok_or("truncated archive member header")?;
+        let field = |range: Range<usize>| {
+            str::from_utf8(&header[range]).map(|s| s.trim_end()).unwrap_or_default()
+        };
+        let size: usize = field(48..58).parse().map_err(|_| "invalid archive member size")?;
+        let start = offset + AR_HEADER_SIZE;
+        let mut contents = data.get(start..start + size).ok_or("truncated archive member")?;
+        // Members are aligned to two bytes.
+        offset = start + size + size % 2;
+
+        let name = field(0..16);
+        if name == "/" || name.starts_with("__.SYMDEF") {
+            continue;
+        }
+        if name == "//" {
+            long_names = contents;
+            continue;
+        }
+        let name = if name.starts_with("#1/") {
+            let len: usize = name[3..].parse().map_err(|_| "invalid archive member name")?;
+            let name = contents.get(..len).ok_or("truncated archive member name")?;
+            contents = &contents[len..];
+            String::from_utf8_lossy(name).trim_end_matches('\0').to_string()
+        } else if name.starts_with('/') {
+            let index: usize = name[1..].parse().map_err(|_| "invalid archive member name")?;
+            let name = long_names.get(index..).ok_or("invalid archive member name")?;
+            let len = name.iter().position(|&b| b == b'\n').unwrap_or(name.len());
+            String::from_utf8_lossy(&name[..len]).trim_end_matches('/').to_string()
+        } else {
+            name.trim_end_matches('/').to_string()
+        };
+        members.push((name, contents));
+    }
+    Ok(members)
+}
+
+fn first_difference(first: &[u8], second: &[u8]) -> usize {
+    first.iter().zip(second).position(|(a, b)| a != b).unwrap_or(first.len().min(second.len()))
+}
@@ -0,0 +1,216 @@
+//! A minimal reader of ELF object files, for the analyses of the object files emitted by
+//! codegen, like `-Z print-stack-usage`, that need their sections and symbols.
+
+use std::convert::TryInto;
+
+pub const SHT_SYMTAB: u32 = 2;
+pub const SHT_RELA: u32 = 4;
+pub const SHT_NOBITS: u32 = 8;
+pub const SHT_REL: u32 = 9;
+pub const SHT_SYMTAB_SHNDX: u32 = 18;
+pub const SHN_XINDEX: u32 = 0xffff;
+pub const STT_FUNC: u8 = 2;
+pub const STT_SECTION: u8 = 3;
+
+pub struct Elf<'a> {
+    data: &'a [u8],
+    pub is_64: bool,
+    big_endian: bool,
+    pub sections: Vec<Section>,
+    shstrndx: usize,
+}
+
+pub struct Section {
+    pub name: u32,
+    pub kind: u32,
+    pub offset: u64,
+    pub size: u64,
+    pub link: u32,
+    pub info: u32,
+}
+
+pub struct Symbol {
+    pub name: String,
+    pub kind: u8,
+    pub section: u32,
+    pub value: u64,
+}
+
+impl<'a> Elf<'a> {
+    pub fn parse(data: &'a [u8]) -> Result<Elf<'a>, String> {
+        if !data.starts_with(b"\x7fELF") {
+            return Err("not an ELF object file".to_string());
+        }
+        let is_64 = match data.get(4) {
+            Some(1) => false,
+            Some(2) => true,
+            _ => return Err("unknown ELF class".to_string()),
+        };
+        let big_endian = match data.get(5) {
+            Some(1) => false,
+            Some(2) => true,
+            _ => return Err("unknown ELF data encoding".to_string()),
+        };
+        let mut elf = Elf { data, is_64, big_endian, sections: vec![], shstrndx: 0 };
+
+        let (shoff, shentsize, shnum, shstrndx) = if is_64 {
+            (elf.u64(0x28)?, elf.u16(0x3a)?, elf.u16(0x3c)?, elf.u16(0x3e)?)
+        } else {
+            (elf.u32(0x20)? as u64, elf.u16(0x2e)?, elf.u16(0x30)?, elf.u16(0x32)?)
+        };
+        if shoff == 0 {
+            return Ok(elf);
+        }
+        let first = elf.section(shoff)?;
+        // Objects with many sections store their number and the index of the section name
+        // table in the first section header.
+        let shnum = if shnum == 0 { first.size } else { shnum as u64 };
+        let shstrndx = if shstrndx == SHN_XINDEX { first.link } else { shstrndx };
+        elf.shstrndx = shstrndx as usize;
+        for index in 0..shnum {
+            let section = elf.section(shoff + index * shentsize as u64)?;
+            elf.sections.push(section);
+        }
+        Ok(elf)
+    }
+
+    fn section(&self, offset: u64) -> Result<Section, String> {
+        Ok(if self.is_64 {
+            Section {
+                name: self.u32(offset)?,
+                kind: self.u32(offset + 4)?,
+                offset: self.u64(offset + 24)?,
+                size: self.u64(offset + 32)?,
+                link: self.u32(offset + 40)?,
+                info: self.u32(offset + 44)?,
+            }
+        } else {
+            Section {
+                name: self.u32(offset)?,
+                kind: self.u32(offset + 4)?,
+                offset: self.u32(offset + 16)? as u64,
+                size: self.u32(offset + 20)? as u64,
+                link: self.u32(offset + 24)?,
+                info: self.u32(offset + 28)?,
+            }
+        })
+    }
+
+    pub fn contents(&self, section: &Section) -> Result<&'a [u8], String> {
+        self.bytes(section.offset, section.size as usize)
+    }
+
+    pub fn section_name(&self, section: &Section) -> Result<&'a str, String> {
+        let strtab = self.sections.get(self.shstrndx).ok_or("invalid section name table")?;
+        self.string(strtab, section.name)
+    }
+
+    fn string(&self, strtab: &Section, offset: u32) -> Result<&'a str, String> {
+        let strings = self.contents(strtab)?;
+        let bytes = strings.get(offset as usize..).ok_or("invalid string offset")?;
+        let len = bytes.iter().position(|&b| b == 0).ok_or("unterminated string")?;
+        std::str::from_utf8(&bytes[..len]).map_err(|err| err.to_string())
+    }
+
+    pub fn symbols(&self, symtab: usize) -> Result<Vec<Symbol>, String> {
+        let section = &self.sections[symtab];
+        let strtab = self.sections.get(section.link as usize).ok_or("invalid string table")?;
+        // Section indices that don't fit in a symbol are stored in a separate table.
+        let shndx_table = self
+            .sections
+            .iter()
+            .find(|s| s.kind == SHT_SYMTAB_SHNDX && s.link as usize == symtab)
+            .map(|s| self.contents(s))
+            .transpose()?;
+
+        let size = if self.is_64 { 24 } else { 16 };
+        let mut symbols = vec![];
+        for index in 0..section.size / size {
+            let offset = section.offset + index * size;
+            let name = self.u32(offset)?;
+            let (info, mut shndx, value) = if self.is_64 {
+                (self.u8(offset + 4)?, self.u16(offset + 6)?, self.u64(offset + 8)?)
+            } else {
+                (self.u8(offset + 12)?, self.u16(offset + 14)?, self.u32(offset + 4)? as u64)
+            };
+            if let (SHN_XINDEX, Some(table)) = (shndx, shndx_table) {
+                shndx = self.read_u32(table, index as usize * 4)?;
+            }
+            let name = self.string(strtab, name)?.to_string();
+            symbols.push(Symbol { name, kind: info & 0xf, section: shndx, value });
+        }
+        Ok(symbols)
+    }
+
+    /// Returns the offset, symbol index and explicit addend of each relocation in `section`.
+    pub fn relocations(&self, section: &Section) -> Result<Vec<(u64, usize, i64)>, String> {
+        let explicit = section.kind == SHT_RELA;
+        let size = match (self.is_64, explicit) {
+            (true, true) => 24,
+            (true, false) => 16,
+            (false, true) => 12,
+            (false, false) => 8,
+        };
+        let mut relocations = vec![];
+        for index in 0..section.size / size {
+            let offset = section.offset + index * size;
+            relocations.push(if self.is_64 {
+                let addend = if explicit { self.u64(offset + 16)? as i64 } else { 0 };
+                (self.u64(offset)?, (self.u64(offset + 8)? >> 32) as usize, addend)
+            } else {
+                let addend = if explicit { self.u32(offset + 8)? as i32 as i64 } else { 0 };
+                (self.u32(offset)? as u64, (self.u32(offset + 4)? >> 8) as usize, addend)
+            });
+        }
+        Ok(relocations)
+    }
+
+    /// Reads an address in section `contents`. Relocations without an explicit addend use
+    /// the address as their addend.
+    pub fn read_address(&self, contents: &[u8], offset: usize) -> Result<u64, String> {
+        if self.is_64 {
+            self.read_u64(contents, offset)
+        } else {
+            self.read_u32(contents, offset).map(|address| address as u64)
+        }
+    }
+
+    fn bytes(&self, offset: u64, len: usize) -> Result<&'a [u8], String> {
+        let start = offset as usize;
+        start
+            .checked_add(len)
+            .and_then(|end| self.data.get(start..end))
+            .ok_or_else(|| "unexpected end of file".to_string())
+    }
+
+    fn u8(&self, offset: u64) -> Result<u8, String> {
+        Ok(self.bytes(offset, 1)?[0])
+    }
+
+    /// Reads a 16-bit number, widened to 32 bits as section indices can be.
+    fn u16(&self, offset: u64) -> Result<u32, String> {
+        let bytes: [u8; 2] = self.bytes(offset, 2)?.try_into().unwrap();
+        Ok(if self.big_endian { u16::from_be_bytes(bytes) } else { u16::from_le_bytes(bytes) }
+            as u32)
+    }
+
+    fn u32(&self, offset: u64) -> Result<u32, String> {
+        self.read_u32(self.data, offset as usize)
+    }
+
+    fn u64(&self, offset: u64) -> Result<u64, String> {
+        self.read_u64(self.data, offset as usize)
+    }
+
+    fn read_u32(&self, data: &[u8], offset: usize) -> Result<u32, String> {
+        let bytes = data.get(offset..offset + 4).ok_or("unexpected end of section")?;
+        let bytes: [u8; 4] = bytes.try_into().unwrap();
+        Ok(if self.big_endian { u32::from_be_bytes(bytes) } else { u32::from_le_bytes(bytes) })
+    }
+
+    fn read_u64(&self, data: &[u8], offset: usize) -> Result<u64, String> {
+        let bytes = data.get(offset..offset + 8).ok_or("unexpected end of section")?;
+        let bytes: [u8; 8] = bytes.try_into().unwrap();
+        Ok(if self.big_endian { u64::from_be_bytes(bytes) } else { u64::from_le_bytes(bytes) })
+    }
+}
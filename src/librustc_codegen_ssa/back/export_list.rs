@@ -0,0 +1,322 @@
+//! Export lists given with `-Z export-symbols`.
+//!
+//! The list is a fragment of a GNU ld version script, made of version nodes like
+//!
+//! ```text
+//! V1 {
+//!     global:
+//!         foo;
+//! };
+//! V2 {
+//!     global:
+//!         foo2;
+//!     local:
+//!         internal_helper;
+//! } V1;
+//! ```
+//!
+//! or of a single anonymous node (`{ global: foo; };`) for an unversioned list. Every listed
+//! symbol must be one that rustc exports. The exports of the crate that the list doesn't mention
+//! are added to its first node, unless a `local: *;` hides them, and the symbols listed under
+//! `local:` are hidden. Symbol patterns and `extern "C++"` blocks aren't supported, as the
+//! listed symbols are checked against, and merged with, the exports computed by rustc.
+
+use rustc_data_structures::fx::FxHashSet;
+use rustc_session::config::CrateType;
+use rustc_session::Session;
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A version node of an export list, with the exported symbols that the version defines.
+#[derive(Clone, Debug, RustcEncodable, RustcDecodable)]
+pub struct VersionNode {
+    /// The version name, e.g. `V2`, or `None` for the anonymous node of an unversioned list.
+    pub name: Option<String>,
+    /// The version that this version inherits from, if any.
+    pub parent: Option<String>,
+    /// The symbols defined with this version.
+    pub global: Vec<String>,
+}
+
+/// The export list read from the file given with `-Z export-symbols`.
+#[derive(Debug)]
+pub struct ExportList {
+    path: PathBuf,
+    nodes: Vec<VersionNode>,
+    /// Symbols that rustc would export, but which are listed under `local:`.
+    local: Vec<String>,
+    /// Whether a `local: *;` hides the exports that aren't listed in any node.
+    hide_unlisted: bool,
+}
+
+impl ExportList {
+    /// Reads and parses the export list of `-Z export-symbols`, if any.
+    pub fn from_session(sess: &Session) -> Option<ExportList> {
+        let path = sess.opts.debugging_opts.export_symbols.as_ref()?;
+        let src = fs::read_to_string(path).unwrap_or_else(|e| {
+            sess.fatal(&format!("failed to read export list `{}`: {}", path.display(), e))
+        });
+        let export_list = ExportList::parse(path, &src).unwrap_or_else(|(line, msg)| {
+            sess.fatal(&format!("{}:{}: {}", path.display(), line, msg))
+        });
+
+        // Only version scripts have symbol versions: other linkers get a plain list of exports.
+        let opts = &sess.target.target.options;
+        let versioned = export_list.nodes.iter().any(|node| node.name.is_some());
+        if versioned
+            && (opts.is_like_osx
+                || opts.is_like_windows
+                || opts.is_like_emscripten
+                || sess.target.target.arch == "wasm32")
+        {
+            sess.warn(&format!(
+                "the symbol versions of export list `{}` are ignored on target `{}`",
+                path.display(),
+                sess.opts.target_triple
+            ));
+        }
+        Some(export_list)
+    }
+
+    /// Parses the version nodes of an export list. Errors come with their line number.
+    fn parse(path: &Path, src: &str) -> Result<ExportList, (usize, String)> {
+        let mut parser = Parser { tokens: tokenize(src)?, pos: 0 };
+        let mut export_list = ExportList {
+            path: path.to_path_buf(),
+            nodes: Vec::new(),
+            local: Vec::new(),
+            hide_unlisted: false,
+        };
+        let mut listed = FxHashSet::default();
+
+        while let Some(&(_, line)) = parser.peek() {
+            let name = parser.eat_word();
+            if (name.is_none() && !export_list.nodes.is_empty())
+                || export_list.nodes.first().map_or(false, |node| node.name.is_none())
+            {
+                return Err((line, "an anonymous version node must be the only node".to_string()));
+            }
+            if let Some(name) = &name {
+                if export_list.nodes.iter().any(|node| node.name.as_ref() == Some(name)) {
+                    return Err((line, format!("version `{}` is defined more than once", name)));
+                }
+            }
+
+            parser.expect(Token::OpenBrace)?;
+            let mut global = Vec::new();
+            let mut is_local = false;
+            loop {
+                let (token, line) = parser.next()?;
+                let symbol = match token {
+                    Token::CloseBrace => break,
+                    Token::Word(word) if parser.eat(Token::Colon) => {
+                        is_local = match &word[..] {
+                            "global" => false,
+                            "local" => true,
+                            _ => return Err((line, format!("unknown scope `{}`", word))),
+                        };
+                        continue;
+                    }
+                    Token::Word(word) => word,
+                    token => return Err((line, format!("expected a symbol, found {}", token))),
+                };
+                parser.expect(Token::Semicolon)?;
+
+                if is_local && symbol == "*" {
+                    export_list.hide_unlisted = true;
+                    continue;
+                }
+                if symbol.contains(|c| c == '*' || c == '?' || c == '[') {
+                    let msg = format!("symbol patterns like `{}` are not supported", symbol);
+                    return Err((line, msg));
+                }
+                if !listed.insert(symbol.clone()) {
+                    return Err((line, format!("symbol `{}` is listed more than once", symbol)));
+                }
+                if is_local {
+                    export_list.local.push(symbol);
+                } else {
+                    global.push(symbol);
+                }
+            }
+
+            let parent = parser.eat_word();
+            if let Some(parent) = &parent {
+                if !export_list.nodes.iter().any(|node| node.name.as_ref() == Some(parent)) {
+                    let msg = format!("version `{}` must be defined before this node", parent);
+                    return Err((line, msg));
+                }
+            }
+            parser.expect(Token::Semicolon)?;
+            export_list.nodes.push(VersionNode { name, parent, global });
+        }
+        Ok(export_list)
+    }
+
+    /// Applies the export list to the symbols that rustc exports for `crate_type`: the hidden
+    /// symbols are removed from `exports`, and the remaining ones are split into the version
+    /// nodes that are returned. Listed symbols that aren't exported are reported as errors.
+    pub fn apply(
+        &self,
+        sess: &Session,
+        crate_type: CrateType,
+        exports: &mut Vec<String>,
+    ) -> Vec<VersionNode> {
+        let exported: FxHashSet<&str> = exports.iter().map(|s| &s[..]).collect();
+        let mut nodes = self.nodes.clone();
+        let listed = nodes.iter().flat_map(|node| node.global.iter()).chain(self.local.iter());
+        for symbol in listed {
+            if !exported.contains(&symbol[..]) {
+                sess.err(&format!(
+                    "symbol `{}` in export list `{}` is not exported by this crate",
+                    symbol,
+                    self.path.display()
+                ));
+            }
+        }
+        for node in &mut nodes {
+            node.global.retain(|symbol| exported.contains(&symbol[..]));
+        }
+
+        let global: FxHashSet<&str> =
+            self.nodes.iter().flat_map(|node| node.global.iter()).map(|s| &s[..]).collect();
+        exports.retain(|symbol| {
+            !self.local.contains(symbol) && (!self.hide_unlisted || global.contains(&symbol[..]))
+        });
+        if let Some(first) = nodes.first_mut() {
+            let unlisted = exports.iter().filter(|symbol| !global.contains(&symbol[..]));
+            first.global.extend(unlisted.cloned());
+        }
+        debug!("export list applied to {}: {:?}", crate_type, nodes);
+        nodes
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    OpenBrace,
+    CloseBrace,
+    Colon,
+    Semicolon,
+    Word(String),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::OpenBrace => "`{`".fmt(f),
+            Token::CloseBrace => "`}`".fmt(f),
+            Token::Colon => "`:`".fmt(f),
+            Token::Semicolon => "`;`".fmt(f),
+            Token::Word(word) => write!(f, "`{}`", word),
+        }
+    }
+}
+
+/// Splits an export list into tokens, with their line numbers, skipping whitespace and both
+/// `#` and `/* */` comments.
+fn tokenize(src: &str) -> Result<Vec<(Token, usize)>, (usize, String)> {
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut chars = src.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            '\n' => {
+                line += 1;
+                continue;
+            }
+            c if c.is_whitespace() => continue,
+            '#' => {
+                while chars.peek().map_or(false, |&c| c != '\n') {
+                    chars.next();
+                }
+                continue;
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                let start_line = line;
+                chars.next();
+                let mut prev = ' ';
+                loop {
+                    match chars.next() {
+                        Some('/') if prev == '*' => break,
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            prev = c;
+                        }
+                        None => return Err((start_line, "unterminated comment".to_string())),
+                    }
+                }
+                continue;
+            }
+            '{' => Token::OpenBrace,
+            '}' => Token::CloseBrace,
+            ':' => Token::Colon,
+            ';' => Token::Semicolon,
+            c => {
+                let mut word = c.to_string();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || "{}:;#".contains(c) {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                Token::Word(word)
+            }
+        };
+        tokens.push((token, line));
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&(Token, usize)> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Result<(Token, usize), (usize, String)> {
+        let last_line = self.tokens.last().map_or(1, |&(_, line)| line);
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| (last_line, "unexpected end of the export list".to_string()))?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn eat(&mut self, token: Token) -> bool {
+        let found = self.peek().map_or(false, |(t, _)| *t == token);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn eat_word(&mut self) -> Option<String> {
+        match self.peek() {
+            Some((Token::Word(word), _)) => {
+                let word = word.clone();
+                self.pos += 1;
+                Some(word)
+            }
+            _ => None,
+        }
+    }
+
+    fn expect(&mut self, token: Token) -> Result<(), (usize, String)> {
+        match self.next()? {
+            (t, _) if t == token => Ok(()),
+            (t, line) => Err((line, format!("expected {}, found {}", token, t))),
+        }
+    }
+}
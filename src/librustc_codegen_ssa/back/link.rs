@@ -4,7 +4,8 @@ use rustc_hir::def_id::CrateNum;
 use rustc_middle::middle::cstore::{EncodedMetadata, LibSource, NativeLib};
 use rustc_middle::middle::dependency_format::Linkage;
 use rustc_session::config::{self, CFGuard, CrateType, DebugInfo};
-use rustc_session::config::{OutputFilenames, OutputType, PrintRequest, SanitizerSet};
+use rustc_session::config::{OutputFilenames, OutputType, PrintRequest};
+use rustc_session::config::{SanitizerSet, SplitDebuginfo};
 use rustc_session::output::{check_file_is_writeable, invalid_output_for_target, out_filename};
 use rustc_session::search_paths::PathKind;
 use rustc_session::utils::NativeLibKind;
@@ -20,6 +21,7 @@ use super::archive::ArchiveBuilder;
 use super::command::Command;
 use super::linker::{self, Linker};
 use super::rpath::{self, RPathConfig};
+use super::stack_usage;
 use crate::{looks_like_rust_object_file, CodegenResults, CrateInfo, METADATA_FILENAME};
 
 use cc::windows_registry;
@@ -46,6 +48,11 @@ pub fn link_binary<'a, B: ArchiveBuilder<'a>>(
     target_cpu: &str,
 ) {
     let _timer = sess.timer("link_binary");
+    if sess.opts.output_types.should_codegen() {
+        sess.time("link_binary_stack_usage", || {
+            stack_usage::report_stack_usage(sess, codegen_results);
+        });
+    }
     let output_metadata = sess.opts.output_types.contains_key(&OutputType::Metadata);
     for &crate_type in sess.crate_types().iter() {
         // Ignore executable crates if we have -Z no-codegen, as they will error.
@@ -133,6 +140,22 @@ pub fn link_binary<'a, B: ArchiveBuilder<'a>>(
                     remove(sess, obj);
                 }
             }
+            // Packed split DWARF objects have been copied into the `.dwp` file of each
+            // output, unless an archive was produced whose objects still refer to them.
+            let output_archive = sess
+                .crate_types()
+                .iter()
+                .any(|&x| x == CrateType::Rlib || x == CrateType::Staticlib);
+            if sess.split_debuginfo() == SplitDebuginfo::Packed && !output_archive {
+                let modules = codegen_results
+                    .modules
+                    .iter()
+                    .chain(&codegen_results.metadata_module)
+                    .chain(&codegen_results.allocator_module);
+                for dwo in modules.filter_map(|m| m.dwarf_object.as_ref()) {
+                    remove(sess, dwo);
+                }
+            }
         }
     });
 }
@@ -330,8 +353,14 @@ fn link_rlib<'a, B: ArchiveBuilder<'a>>(
             | NativeLibKind::RawDylib
             | NativeLibKind::Unspecified => continue,
         }
+        if lib.modifiers.whole_archive == Some(true) && matches!(flavor, RlibFlavor::Normal) {
+            sess.err(
+                "the linking modifiers `+bundle` and `+whole-archive` are not compatible \
+                 with each other when generating rlibs",
+            );
+        }
         if let Some(name) = lib.name {
-            ab.add_native_library(name);
+            ab.add_native_library(name, lib.modifiers.verbatim);
         }
     }
 
@@ -466,7 +495,14 @@ fn link_natively<'a, B: ArchiveBuilder<'a>>(
     target_cpu: &str,
 ) {
     info!("preparing {:?} to {:?}", crate_type, out_filename);
-    let (linker_path, flavor) = linker_and_flavor(sess);
+    let (mut linker_path, flavor) = linker_and_flavor(sess);
+    let self_contained_lld = self_contained_lld_dir(sess, flavor);
+    if let (Some(dir), LinkerFlavor::Lld(_)) = (&self_contained_lld, flavor) {
+        // Run `rust-lld` directly, unless another linker was given with `-C linker`.
+        if sess.opts.cg.linker.is_none() {
+            linker_path = dir.join(exe_name("rust-lld"));
+        }
+    }
     let mut cmd = linker_with_args::<B>(
         &linker_path,
         flavor,
@@ -478,6 +514,17 @@ fn link_natively<'a, B: ArchiveBuilder<'a>>(
         target_cpu,
     );
 
+    // Make the `cc` driver run `rust-lld`, as the `ld.lld` of the `gcc-ld` directory.
+    let gcc_ld_arg = match (&self_contained_lld, flavor) {
+        (Some(dir), LinkerFlavor::Gcc) => {
+            let mut arg = OsString::from("-B");
+            arg.push(dir.join("gcc-ld"));
+            cmd.arg("-fuse-ld=lld").arg(&arg);
+            Some(arg)
+        }
+        _ => None,
+    };
+
     linker::disable_localization(&mut cmd);
 
     for &(ref k, ref v) in &sess.target.target.options.link_env {
@@ -537,6 +584,32 @@ fn link_natively<'a, B: ArchiveBuilder<'a>>(
             continue;
         }
 
+        // Check to see if the link failed because the `cc` driver doesn't support
+        // `-fuse-ld=lld`, like gcc before version 9. If so, reperform the link step
+        // with the system linker, as if `-C link-self-contained` wasn't given.
+        if let Some(gcc_ld_arg) = &gcc_ld_arg {
+            if (out.contains("unrecognized command line option")
+                || out.contains("unrecognized command-line option")
+                || out.contains("unknown argument"))
+                && out.contains("-fuse-ld=lld")
+                && cmd.get_args().iter().any(|e| e.to_string_lossy() == "-fuse-ld=lld")
+            {
+                info!("linker output: {:?}", out);
+                sess.warn(&format!(
+                    "`{}` does not support `-fuse-ld=lld`, linking with the system linker \
+                     instead of `rust-lld`",
+                    linker_path.display()
+                ));
+                for arg in cmd.take_args() {
+                    if arg.to_string_lossy() != "-fuse-ld=lld" && arg != *gcc_ld_arg {
+                        cmd.arg(arg);
+                    }
+                }
+                info!("{:?}", &cmd);
+                continue;
+            }
+        }
+
         // Detect '-static-pie' used with an older version of gcc or clang not supporting it.
         // Fallback from '-static-pie' to '-static' in that case.
         if sess.target.target.options.linker_is_gnu
@@ -764,6 +837,38 @@ fn link_natively<'a, B: ArchiveBuilder<'a>>(
             sess.fatal(&format!("failed to run dsymutil: {}", e))
         }
     }
+
+    // With packed split debuginfo, the `.dwo` files referenced by the output are
+    // packaged into a `.dwp` file next to it, where debuggers look for them.
+    if sess.split_debuginfo() == SplitDebuginfo::Packed {
+        link_dwarf_object(sess, out_filename);
+    }
+}
+
+fn link_dwarf_object(sess: &Session, out_filename: &Path) {
+    let mut dwp_out_filename = out_filename.as_os_str().to_owned();
+    dwp_out_filename.push(".dwp");
+
+    // Whether `llvm-dwp` can be found is checked when the options are validated.
+    let llvm_dwp = match sess.llvm_dwp() {
+        Some(llvm_dwp) => llvm_dwp,
+        None => sess.fatal("`llvm-dwp` was found neither in the sysroot nor in `PATH`"),
+    };
+    let mut cmd = Command::new(llvm_dwp);
+    cmd.arg("-e").arg(out_filename).arg("-o").arg(&dwp_out_filename);
+
+    match sess.time("run_dwp", || cmd.output()) {
+        Ok(prog) if !prog.status.success() => {
+            let mut output = prog.stderr.clone();
+            output.extend_from_slice(&prog.stdout);
+            sess.struct_err(&format!("packaging split debuginfo failed: {}", prog.status))
+                .note(&format!("{:?}", &cmd))
+                .note(&String::from_utf8_lossy(&output))
+                .emit();
+        }
+        Ok(_) => {}
+        Err(e) => sess.fatal(&format!("failed to run llvm-dwp: {}", e)),
+    }
 }
 
 fn link_sanitizers(sess: &Session, crate_type: CrateType, linker: &mut dyn Linker) {
@@ -802,7 +907,7 @@ fn link_sanitizer_runtime(sess: &Session, linker: &mut dyn Linker, name: &str) {
             let libname = format!("rustc{}_rt.{}", channel, name);
             let rpath = default_tlib.to_str().expect("non-utf8 component in path");
             linker.args(&["-Wl,-rpath", "-Xlinker", rpath]);
-            linker.link_dylib(Symbol::intern(&libname));
+            linker.link_dylib(Symbol::intern(&libname), false, None);
         }
         "aarch64-fuchsia"
         | "aarch64-unknown-linux-gnu"
@@ -919,6 +1024,47 @@ fn linker_and_flavor(sess: &Session) -> (PathBuf, LinkerFlavor) {
     bug!("Not enough information provided to determine how to invoke the linker");
 }
 
+fn exe_name(name: &str) -> String {
+    format!("{}{}", name, env::consts::EXE_SUFFIX)
+}
+
+/// Finds the tools directory of the sysroot that contains the `rust-lld` shipped with rustc,
+/// if the "self-contained" mode of `-C link-self-contained` links with it on this target.
+///
+/// `rust-lld` is run directly for the `ld.lld` linker flavor, and through the `cc` driver,
+/// which finds it as `gcc-ld/ld.lld` with `-fuse-ld=lld`, for the `gcc` flavor. If it wasn't
+/// shipped with rustc, the system linker is used instead.
+fn self_contained_lld_dir(sess: &Session, flavor: LinkerFlavor) -> Option<PathBuf> {
+    if sess.opts.cg.link_self_contained != Some(true)
+        || !sess.target.target.options.self_contained_lld
+    {
+        return None;
+    }
+    if flavor != LinkerFlavor::Gcc && flavor != LinkerFlavor::Lld(LldFlavor::Ld) {
+        sess.warn(&format!(
+            "`-C link-self-contained` can't link with `rust-lld` for linker flavor `{}`, \
+             linking with the system linker instead",
+            flavor.desc()
+        ));
+        return None;
+    }
+
+    let tools_paths = sess.host_filesearch(PathKind::All).get_tools_search_paths();
+    let dir = tools_paths.iter().find(|dir| {
+        dir.join(exe_name("rust-lld")).exists()
+            && dir.join("gcc-ld").join(exe_name("ld.lld")).exists()
+    });
+    if dir.is_none() {
+        sess.struct_warn(
+            "`-C link-self-contained` requires the `rust-lld` shipped with rustc, \
+             linking with the system linker instead",
+        )
+        .note(&format!("`rust-lld` was not found in `{}`", tools_paths[0].display()))
+        .emit();
+    }
+    dir.cloned()
+}
+
 /// Returns a boolean indicating whether we should preserve the object files on
 /// the filesystem for their debug information. This is often useful with
 /// split-dwarf like schemes.
@@ -972,10 +1118,11 @@ fn print_native_static_libs(sess: &Session, all_native_libs: &[NativeLib]) {
                 NativeLibKind::StaticNoBundle
                 | NativeLibKind::Dylib
                 | NativeLibKind::Unspecified => {
+                    let verbatim = lib.modifiers.verbatim;
                     if sess.target.target.options.is_like_msvc {
-                        Some(format!("{}.lib", name))
+                        Some(format!("{}{}", name, if verbatim { "" } else { ".lib" }))
                     } else {
-                        Some(format!("-l{}", name))
+                        Some(format!("-l{}{}", if verbatim { ":" } else { "" }, name))
                     }
                 }
                 NativeLibKind::Framework => {
@@ -1268,6 +1415,10 @@ fn link_output_kind(sess: &Session, crate_type: CrateType) -> LinkOutputKind {
 /// Whether we link to our own CRT objects instead of relying on gcc to pull them.
 /// We only provide such support for a very limited number of targets.
 fn crt_objects_fallback(sess: &Session, crate_type: CrateType) -> bool {
+    if let Some(self_contained) = sess.opts.cg.link_self_contained {
+        return self_contained && sess.target.target.options.crt_objects_fallback.is_some();
+    }
+
     match sess.target.target.options.crt_objects_fallback {
         // FIXME: Find a better heuristic for "native musl toolchain is available",
         // based on host and linker path, for example.
@@ -1731,11 +1882,21 @@ fn add_local_native_libraries(
             Some(l) => l,
             None => continue,
         };
+        let verbatim = lib.modifiers.verbatim;
         match lib.kind {
-            NativeLibKind::Dylib | NativeLibKind::Unspecified => cmd.link_dylib(name),
+            NativeLibKind::Dylib | NativeLibKind::Unspecified => {
+                cmd.link_dylib(name, verbatim, lib.modifiers.as_needed)
+            }
             NativeLibKind::Framework => cmd.link_framework(name),
-            NativeLibKind::StaticNoBundle => cmd.link_staticlib(name),
-            NativeLibKind::StaticBundle => cmd.link_whole_staticlib(name, &search_path),
+            NativeLibKind::StaticNoBundle | NativeLibKind::StaticBundle => {
+                // Bundled libraries are linked as whole archives unless `-whole-archive` is given.
+                let whole_archive = lib.kind == NativeLibKind::StaticBundle;
+                if lib.modifiers.whole_archive.unwrap_or(whole_archive) {
+                    cmd.link_whole_staticlib(name, verbatim, &search_path);
+                } else {
+                    cmd.link_staticlib(name, verbatim);
+                }
+            }
             NativeLibKind::RawDylib => {
                 // FIXME(#58713): Proper handling for raw dylibs.
                 bug!("raw_dylib feature not yet implemented");
@@ -2067,8 +2228,11 @@ fn add_upstream_native_libraries(
             if !relevant_lib(sess, &lib) {
                 continue;
             }
+            let verbatim = lib.modifiers.verbatim;
             match lib.kind {
-                NativeLibKind::Dylib | NativeLibKind::Unspecified => cmd.link_dylib(name),
+                NativeLibKind::Dylib | NativeLibKind::Unspecified => {
+                    cmd.link_dylib(name, verbatim, lib.modifiers.as_needed)
+                }
                 NativeLibKind::Framework => cmd.link_framework(name),
                 NativeLibKind::StaticNoBundle => {
                     // Link "static-nobundle" native libs only if the crate they originate from
@@ -2076,7 +2240,11 @@ fn add_upstream_native_libraries(
                     // or is an rlib already included via some other dylib crate, the symbols from
                     // native libs will have already been included in that dylib.
                     if data[cnum.as_usize() - 1] == Linkage::Static {
-                        cmd.link_staticlib(name)
+                        if lib.modifiers.whole_archive == Some(true) {
+                            cmd.link_whole_staticlib(name, verbatim, &archive_search_paths(sess))
+                        } else {
+                            cmd.link_staticlib(name, verbatim)
+                        }
                     }
                 }
                 // ignore statically included native libraries here as we've
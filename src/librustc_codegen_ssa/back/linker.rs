@@ -1,5 +1,6 @@
 use super::archive;
 use super::command::Command;
+use super::export_list::{ExportList, VersionNode};
 use super::symbol_export;
 use rustc_span::symbol::sym;
 
@@ -15,7 +16,8 @@ use rustc_hir::def_id::{CrateNum, LOCAL_CRATE};
 use rustc_middle::middle::dependency_format::Linkage;
 use rustc_middle::ty::TyCtxt;
 use rustc_serialize::{json, Encoder};
-use rustc_session::config::{self, CrateType, DebugInfo, LinkerPluginLto, Lto, OptLevel, Strip};
+use rustc_session::config::{self, CrateType, DebugInfo, DebugInfoCompression};
+use rustc_session::config::{LinkerPluginLto, Lto, OptLevel, Strip};
 use rustc_session::Session;
 use rustc_span::symbol::Symbol;
 use rustc_target::spec::{LinkOutputKind, LinkerFlavor, LldFlavor};
@@ -38,18 +40,28 @@ pub fn disable_localization(linker: &mut Command) {
 #[derive(RustcEncodable, RustcDecodable)]
 pub struct LinkerInfo {
     exports: FxHashMap<CrateType, Vec<String>>,
+    /// The version nodes of the exports, when an export list is given with `-Z export-symbols`.
+    version_nodes: FxHashMap<CrateType, Vec<VersionNode>>,
 }
 
 impl LinkerInfo {
     pub fn new(tcx: TyCtxt<'_>) -> LinkerInfo {
-        LinkerInfo {
-            exports: tcx
-                .sess
-                .crate_types()
-                .iter()
-                .map(|&c| (c, exported_symbols(tcx, c)))
-                .collect(),
+        let export_list = ExportList::from_session(tcx.sess);
+        let mut exports = FxHashMap::default();
+        let mut version_nodes = FxHashMap::default();
+        for &crate_type in tcx.sess.crate_types().iter() {
+            let mut symbols = exported_symbols(tcx, crate_type);
+            // Rlibs and proc macros are never linked with an export list.
+            if let Some(export_list) = &export_list {
+                if crate_type != CrateType::Rlib && crate_type != CrateType::ProcMacro {
+                    let nodes = export_list.apply(tcx.sess, crate_type, &mut symbols);
+                    version_nodes.insert(crate_type, nodes);
+                }
+            }
+            exports.insert(crate_type, symbols);
         }
+        tcx.sess.abort_if_errors();
+        LinkerInfo { exports, version_nodes }
     }
 
     pub fn to_linker<'a>(
@@ -93,6 +105,16 @@ impl LinkerInfo {
     }
 }
 
+/// Warns that the linker can't honour the `as-needed` modifier given to `lib`, if any.
+fn warn_as_needed_ignored(sess: &Session, lib: Symbol, as_needed: Option<bool>) {
+    if as_needed.is_some() {
+        sess.warn(&format!(
+            "linking modifier `as-needed` of library `{}` is ignored by the linker of target `{}`",
+            lib, sess.opts.target_triple
+        ));
+    }
+}
+
 /// Linker abstraction used by `back::link` to build up the command to invoke a
 /// linker.
 ///
@@ -103,13 +125,13 @@ impl LinkerInfo {
 pub trait Linker {
     fn cmd(&mut self) -> &mut Command;
     fn set_output_kind(&mut self, output_kind: LinkOutputKind, out_filename: &Path);
-    fn link_dylib(&mut self, lib: Symbol);
+    fn link_dylib(&mut self, lib: Symbol, verbatim: bool, as_needed: Option<bool>);
     fn link_rust_dylib(&mut self, lib: Symbol, path: &Path);
     fn link_framework(&mut self, framework: Symbol);
-    fn link_staticlib(&mut self, lib: Symbol);
+    fn link_staticlib(&mut self, lib: Symbol, verbatim: bool);
     fn link_rlib(&mut self, lib: &Path);
     fn link_whole_rlib(&mut self, lib: &Path);
-    fn link_whole_staticlib(&mut self, lib: Symbol, search_path: &[PathBuf]);
+    fn link_whole_staticlib(&mut self, lib: Symbol, verbatim: bool, search_path: &[PathBuf]);
     fn include_path(&mut self, path: &Path);
     fn framework_path(&mut self, path: &Path);
     fn output_filename(&mut self, path: &Path);
@@ -210,6 +232,18 @@ impl<'a> GccLinker<'a> {
         }
     }
 
+    /// Passes a library to the linker by name, or by file name if `verbatim`.
+    fn link_lib_arg(&mut self, lib: Symbol, verbatim: bool) {
+        if !verbatim {
+            self.cmd.arg(format!("-l{}", lib));
+        } else if self.sess.target.target.options.is_like_osx {
+            // ld64 doesn't support `-l:`, so the file is passed as an input instead.
+            self.cmd.sym_arg(lib);
+        } else {
+            self.cmd.arg(format!("-l:{}", lib));
+        }
+    }
+
     fn push_linker_plugin_lto_args(&mut self, plugin_path: Option<&OsStr>) {
         if let Some(plugin_path) = plugin_path {
             let mut arg = OsString::from("-plugin=");
@@ -333,13 +367,25 @@ impl<'a> Linker for GccLinker<'a> {
         }
     }
 
-    fn link_dylib(&mut self, lib: Symbol) {
+    fn link_dylib(&mut self, lib: Symbol, verbatim: bool, as_needed: Option<bool>) {
         self.hint_dynamic();
-        self.cmd.arg(format!("-l{}", lib));
+        match as_needed {
+            Some(as_needed) if self.sess.target.target.options.linker_is_gnu => {
+                // The linker's default isn't known, so the setting is scoped to this library.
+                self.linker_arg("--push-state");
+                self.linker_arg(if as_needed { "--as-needed" } else { "--no-as-needed" });
+                self.link_lib_arg(lib, verbatim);
+                self.linker_arg("--pop-state");
+            }
+            _ => {
+                warn_as_needed_ignored(self.sess, lib, as_needed);
+                self.link_lib_arg(lib, verbatim);
+            }
+        }
     }
-    fn link_staticlib(&mut self, lib: Symbol) {
+    fn link_staticlib(&mut self, lib: Symbol, verbatim: bool) {
         self.hint_static();
-        self.cmd.arg(format!("-l{}", lib));
+        self.link_lib_arg(lib, verbatim);
     }
     fn link_rlib(&mut self, lib: &Path) {
         self.hint_static();
@@ -384,17 +430,18 @@ impl<'a> Linker for GccLinker<'a> {
     // don't otherwise explicitly reference them. This can occur for
     // libraries which are just providing bindings, libraries with generic
     // functions, etc.
-    fn link_whole_staticlib(&mut self, lib: Symbol, search_path: &[PathBuf]) {
+    fn link_whole_staticlib(&mut self, lib: Symbol, verbatim: bool, search_path: &[PathBuf]) {
         self.hint_static();
         let target = &self.sess.target.target;
         if !target.options.is_like_osx {
-            self.linker_arg("--whole-archive").cmd.arg(format!("-l{}", lib));
+            self.linker_arg("--whole-archive");
+            self.link_lib_arg(lib, verbatim);
             self.linker_arg("--no-whole-archive");
         } else {
             // -force_load is the macOS equivalent of --whole-archive, but it
             // involves passing the full path to the library to link.
             self.linker_arg("-force_load");
-            let lib = archive::find_library(lib, search_path, &self.sess);
+            let lib = archive::find_library(lib, verbatim, search_path, &self.sess);
             self.linker_arg(&lib);
         }
     }
@@ -490,6 +537,15 @@ impl<'a> Linker for GccLinker<'a> {
                 self.linker_arg("-s");
             }
         }
+
+        // The linker decompresses the debuginfo sections of its inputs, so it has
+        // to be asked to compress them again in the output.
+        match self.sess.opts.debugging_opts.debuginfo_compression {
+            DebugInfoCompression::None => {}
+            DebugInfoCompression::Zlib => {
+                self.linker_arg("--compress-debug-sections=zlib");
+            }
+        }
     }
 
     fn no_crt_objects(&mut self) {
@@ -545,15 +601,42 @@ impl<'a> Linker for GccLinker<'a> {
             // Write an LD version script
             let res: io::Result<()> = try {
                 let mut f = BufWriter::new(File::create(&path)?);
-                writeln!(f, "{{")?;
-                if !self.info.exports[&crate_type].is_empty() {
-                    writeln!(f, "  global:")?;
-                    for sym in self.info.exports[&crate_type].iter() {
-                        debug!("    {};", sym);
-                        writeln!(f, "    {};", sym)?;
+                match self.info.version_nodes.get(&crate_type) {
+                    Some(nodes) if !nodes.is_empty() => {
+                        // Everything not exported is hidden by the first node.
+                        for (i, node) in nodes.iter().enumerate() {
+                            match &node.name {
+                                Some(name) => writeln!(f, "{} {{", name)?,
+                                None => writeln!(f, "{{")?,
+                            }
+                            if !node.global.is_empty() {
+                                writeln!(f, "  global:")?;
+                                for sym in node.global.iter() {
+                                    debug!("    {};", sym);
+                                    writeln!(f, "    {};", sym)?;
+                                }
+                            }
+                            if i == 0 {
+                                writeln!(f, "\n  local:\n    *;")?;
+                            }
+                            match &node.parent {
+                                Some(parent) => writeln!(f, "}} {};", parent)?,
+                                None => writeln!(f, "}};")?,
+                            }
+                        }
+                    }
+                    _ => {
+                        writeln!(f, "{{")?;
+                        if !self.info.exports[&crate_type].is_empty() {
+                            writeln!(f, "  global:")?;
+                            for sym in self.info.exports[&crate_type].iter() {
+                                debug!("    {};", sym);
+                                writeln!(f, "    {};", sym)?;
+                            }
+                        }
+                        writeln!(f, "\n  local:\n    *;\n}};")?;
                     }
                 }
-                writeln!(f, "\n  local:\n    *;\n}};")?;
             };
             if let Err(e) = res {
                 self.sess.fatal(&format!("failed to write version script: {}", e));
@@ -663,8 +746,10 @@ impl<'a> Linker for MsvcLinker<'a> {
         }
     }
 
-    fn link_dylib(&mut self, lib: Symbol) {
-        self.cmd.arg(&format!("{}.lib", lib));
+    fn link_dylib(&mut self, lib: Symbol, verbatim: bool, as_needed: Option<bool>) {
+        // Only the DLLs whose imports are used are ever loaded.
+        warn_as_needed_ignored(self.sess, lib, as_needed);
+        self.cmd.arg(&format!("{}{}", lib, if verbatim { "" } else { ".lib" }));
     }
 
     fn link_rust_dylib(&mut self, lib: Symbol, path: &Path) {
@@ -678,8 +763,8 @@ impl<'a> Linker for MsvcLinker<'a> {
         }
     }
 
-    fn link_staticlib(&mut self, lib: Symbol) {
-        self.cmd.arg(&format!("{}.lib", lib));
+    fn link_staticlib(&mut self, lib: Symbol, verbatim: bool) {
+        self.cmd.arg(&format!("{}{}", lib, if verbatim { "" } else { ".lib" }));
     }
 
     fn full_relro(&mut self) {
@@ -721,9 +806,9 @@ impl<'a> Linker for MsvcLinker<'a> {
         bug!("frameworks are not supported on windows")
     }
 
-    fn link_whole_staticlib(&mut self, lib: Symbol, _search_path: &[PathBuf]) {
-        self.link_staticlib(lib);
-        self.cmd.arg(format!("/WHOLEARCHIVE:{}.lib", lib));
+    fn link_whole_staticlib(&mut self, lib: Symbol, verbatim: bool, _search_path: &[PathBuf]) {
+        self.link_staticlib(lib, verbatim);
+        self.cmd.arg(format!("/WHOLEARCHIVE:{}{}", lib, if verbatim { "" } else { ".lib" }));
     }
     fn link_whole_rlib(&mut self, path: &Path) {
         self.link_rlib(path);
@@ -868,8 +953,12 @@ impl<'a> Linker for EmLinker<'a> {
         self.cmd.arg("-L").arg(path);
     }
 
-    fn link_staticlib(&mut self, lib: Symbol) {
-        self.cmd.arg("-l").sym_arg(lib);
+    fn link_staticlib(&mut self, lib: Symbol, verbatim: bool) {
+        if verbatim {
+            self.cmd.sym_arg(lib);
+        } else {
+            self.cmd.arg("-l").sym_arg(lib);
+        }
     }
 
     fn output_filename(&mut self, path: &Path) {
@@ -880,14 +969,15 @@ impl<'a> Linker for EmLinker<'a> {
         self.cmd.arg(path);
     }
 
-    fn link_dylib(&mut self, lib: Symbol) {
+    fn link_dylib(&mut self, lib: Symbol, verbatim: bool, as_needed: Option<bool>) {
         // Emscripten always links statically
-        self.link_staticlib(lib);
+        warn_as_needed_ignored(self.sess, lib, as_needed);
+        self.link_staticlib(lib, verbatim);
     }
 
-    fn link_whole_staticlib(&mut self, lib: Symbol, _search_path: &[PathBuf]) {
+    fn link_whole_staticlib(&mut self, lib: Symbol, verbatim: bool, _search_path: &[PathBuf]) {
         // not supported?
-        self.link_staticlib(lib);
+        self.link_staticlib(lib, verbatim);
     }
 
     fn link_whole_rlib(&mut self, lib: &Path) {
@@ -896,7 +986,7 @@ impl<'a> Linker for EmLinker<'a> {
     }
 
     fn link_rust_dylib(&mut self, lib: Symbol, _path: &Path) {
-        self.link_dylib(lib);
+        self.link_dylib(lib, false, None);
     }
 
     fn link_rlib(&mut self, lib: &Path) {
@@ -1051,6 +1141,17 @@ impl<'a> WasmLd<'a> {
     }
 }
 
+impl<'a> WasmLd<'a> {
+    /// Passes a library to `wasm-ld` by name, or by file name if `verbatim`.
+    fn link_lib_arg(&mut self, lib: Symbol, verbatim: bool) {
+        if verbatim {
+            self.cmd.arg(format!("-l:{}", lib));
+        } else {
+            self.cmd.arg("-l").sym_arg(lib);
+        }
+    }
+}
+
 impl<'a> Linker for WasmLd<'a> {
     fn cmd(&mut self) -> &mut Command {
         &mut self.cmd
@@ -1068,12 +1169,13 @@ impl<'a> Linker for WasmLd<'a> {
         }
     }
 
-    fn link_dylib(&mut self, lib: Symbol) {
-        self.cmd.arg("-l").sym_arg(lib);
+    fn link_dylib(&mut self, lib: Symbol, verbatim: bool, as_needed: Option<bool>) {
+        warn_as_needed_ignored(self.sess, lib, as_needed);
+        self.link_lib_arg(lib, verbatim);
     }
 
-    fn link_staticlib(&mut self, lib: Symbol) {
-        self.cmd.arg("-l").sym_arg(lib);
+    fn link_staticlib(&mut self, lib: Symbol, verbatim: bool) {
+        self.link_lib_arg(lib, verbatim);
     }
 
     fn link_rlib(&mut self, lib: &Path) {
@@ -1110,8 +1212,10 @@ impl<'a> Linker for WasmLd<'a> {
         panic!("frameworks not supported")
     }
 
-    fn link_whole_staticlib(&mut self, lib: Symbol, _search_path: &[PathBuf]) {
-        self.cmd.arg("-l").sym_arg(lib);
+    fn link_whole_staticlib(&mut self, lib: Symbol, verbatim: bool, _search_path: &[PathBuf]) {
+        self.cmd.arg("--whole-archive");
+        self.link_lib_arg(lib, verbatim);
+        self.cmd.arg("--no-whole-archive");
     }
 
     fn link_whole_rlib(&mut self, lib: &Path) {
@@ -1278,7 +1382,7 @@ impl<'a> Linker for PtxLinker<'a> {
         });
     }
 
-    fn link_dylib(&mut self, _lib: Symbol) {
+    fn link_dylib(&mut self, _lib: Symbol, _verbatim: bool, _as_needed: Option<bool>) {
         panic!("external dylibs not supported")
     }
 
@@ -1286,11 +1390,11 @@ impl<'a> Linker for PtxLinker<'a> {
         panic!("external dylibs not supported")
     }
 
-    fn link_staticlib(&mut self, _lib: Symbol) {
+    fn link_staticlib(&mut self, _lib: Symbol, _verbatim: bool) {
         panic!("staticlibs not supported")
     }
 
-    fn link_whole_staticlib(&mut self, _lib: Symbol, _search_path: &[PathBuf]) {
+    fn link_whole_staticlib(&mut self, _lib: Symbol, _verbatim: bool, _search_path: &[PathBuf]) {
         panic!("staticlibs not supported")
     }
 
@@ -1,8 +1,12 @@
 pub mod archive;
 pub mod command;
+pub mod determinism;
+pub mod elf;
+pub mod export_list;
 pub mod link;
 pub mod linker;
 pub mod lto;
 pub mod rpath;
+pub mod stack_usage;
 pub mod symbol_export;
 pub mod write;
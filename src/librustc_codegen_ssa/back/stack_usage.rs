@@ -0,0 +1,424 @@
+//! Worst-case stack usage analysis for `-Z print-stack-usage`.
+//!
+//! LLVM records the frame size of every function it emits in `.stack_sizes` sections of ELF
+//! object files (see `-Z emit-stack-sizes`). These frame sizes are combined with the call graph
+//! of the functions codegened for the crate, which is built from their MIR while the type
+//! context is still around, to find the deepest chain of calls from each entry point.
+//!
+//! Calls that can't be followed statically, like calls of function pointers or trait object
+//! methods, and recursion make the stack usage of everything that reaches them unbounded. So do
+//! calls of functions that weren't codegened for the crate, whose frame sizes aren't known.
+//!
+//! The analysis under-approximates in two ways. The calls that LLVM inserts itself, like the
+//! calls of `memcpy`, `memset` or `__rust_probestack`, aren't part of the MIR and are never
+//! followed, which `UNCOUNTED` spells out to users. The panic machinery isn't followed either,
+//! since it would make nearly every function unbounded: neither the panics that failed MIR
+//! assertions lower to, nor the calls of functions that never return and weren't codegened for
+//! the crate, like the ones `panic!` expands to. The stack usage of the functions that reach
+//! them is reported as a lower bound, and `#[stack_limit]`s are only checked against it.
+
+use super::elf::{Elf, SHT_REL, SHT_RELA, SHT_SYMTAB, STT_FUNC, STT_SECTION};
+use crate::CodegenResults;
+
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_data_structures::stack::ensure_sufficient_stack;
+use rustc_hir::def_id::LOCAL_CRATE;
+use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
+use rustc_middle::mir;
+use rustc_middle::mir::mono::MonoItem;
+use rustc_middle::ty::{self, Instance, Ty, TyCtxt};
+use rustc_serialize::leb128;
+use rustc_session::Session;
+use rustc_span::Span;
+
+use std::fs;
+
+/// What the reported stack usage leaves out.
+const UNCOUNTED: &str = "the stack usage doesn't include the calls that LLVM inserts, like the \
+                         calls of `memcpy`, `memset` or `__rust_probestack`";
+
+/// The calls made by the functions codegened for the local crate.
+#[derive(Debug, Default, RustcEncodable, RustcDecodable)]
+pub struct CallGraph {
+    /// The functions whose stack usage is reported.
+    pub roots: Vec<StackRoot>,
+    /// The functions codegened for the crate, by symbol name.
+    pub nodes: FxHashMap<String, CallGraphNode>,
+}
+
+#[derive(Debug, RustcEncodable, RustcDecodable)]
+pub struct StackRoot {
+    pub symbol: String,
+    pub name: String,
+    pub span: Span,
+    /// The `#[stack_limit]` of the function, if any.
+    pub limit: Option<u64>,
+}
+
+#[derive(Debug, RustcEncodable, RustcDecodable)]
+pub struct CallGraphNode {
+    pub name: String,
+    pub callees: Vec<Callee>,
+}
+
+#[derive(Debug, RustcEncodable, RustcDecodable)]
+pub enum Callee {
+    /// A call of the function with the given symbol name, which never returns if `diverges`.
+    Direct { symbol: String, name: String, diverges: bool },
+    /// A call whose target isn't known statically, described as "calls ...".
+    Indirect(String),
+    /// The panics of failed assertions, which aren't followed.
+    Assert,
+}
+
+/// Builds the call graph of the functions codegened for the local crate. The entry function,
+/// exported functions and functions with a `#[stack_limit]` are the roots of the graph.
+pub fn call_graph(tcx: TyCtxt<'_>) -> CallGraph {
+    let (_, cgus) = tcx.collect_and_partition_mono_items(LOCAL_CRATE);
+    let entry_fn = tcx.entry_fn(LOCAL_CRATE).map(|(def_id, _)| def_id.to_def_id());
+
+    let mut graph = CallGraph::default();
+    for cgu in cgus {
+        for &item in cgu.items().keys() {
+            let instance = match item {
+                MonoItem::Fn(instance) => instance,
+                MonoItem::Static(..) | MonoItem::GlobalAsm(..) => continue,
+            };
+            let symbol = tcx.symbol_name(instance).name.to_string();
+            // Items that are instantiated locally can be in several codegen units.
+            if graph.nodes.contains_key(&symbol) {
+                continue;
+            }
+
+            let name = instance.to_string();
+            if let ty::InstanceDef::Item(def_id) = instance.def {
+                let attrs = tcx.codegen_fn_attrs(def_id);
+                let exported = attrs.flags.contains(CodegenFnAttrFlags::NO_MANGLE)
+                    || attrs.export_name.is_some();
+                if exported || attrs.stack_limit.is_some() || entry_fn == Some(def_id) {
+                    graph.roots.push(StackRoot {
+                        symbol: symbol.clone(),
+                        name: name.clone(),
+                        span: tcx.def_span(def_id),
+                        limit: attrs.stack_limit,
+                    });
+                }
+            }
+            graph.nodes.insert(symbol, CallGraphNode { name, callees: callees(tcx, instance) });
+        }
+    }
+    graph.roots.sort_by(|a, b| a.name.cmp(&b.name));
+    graph
+}
+
+fn callees<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> Vec<Callee> {
+    let body = tcx.instance_mir(instance.def);
+    let monomorphize = |ty: Ty<'tcx>| match instance.substs_for_mir_body() {
+        Some(substs) => {
+            tcx.subst_and_normalize_erasing_regions(substs, ty::ParamEnv::reveal_all(), &ty)
+        }
+        None => tcx.normalize_erasing_regions(ty::ParamEnv::reveal_all(), ty),
+    };
+
+    let mut callees = vec![];
+    for data in body.basic_blocks() {
+        let callee = match data.terminator().kind {
+            mir::TerminatorKind::Call { ref func, ref destination, .. } => {
+                match monomorphize(func.ty(body, tcx)).kind {
+                    ty::FnDef(def_id, substs) => {
+                        match Instance::resolve(tcx, ty::ParamEnv::reveal_all(), def_id, substs) {
+                            Ok(Some(callee)) => direct_callee(tcx, callee, destination.is_none()),
+                            _ => Some(Callee::Indirect(format!(
+                                "calls `{}`, which can't be resolved",
+                                tcx.def_path_str(def_id)
+                            ))),
+                        }
+                    }
+                    _ => Some(Callee::Indirect("calls a function pointer".to_string())),
+                }
+            }
+            mir::TerminatorKind::Drop { ref place, .. }
+            | mir::TerminatorKind::DropAndReplace { ref place, .. } => {
+                let ty = monomorphize(place.ty(body, tcx).ty);
+                direct_callee(tcx, Instance::resolve_drop_in_place(tcx, ty), false)
+            }
+            // One `Assert` stands for all the assertions of the function.
+            mir::TerminatorKind::Assert { .. }
+                if !callees.iter().any(|callee| matches!(callee, Callee::Assert)) =>
+            {
+                Some(Callee::Assert)
+            }
+            _ => None,
+        };
+        callees.extend(callee);
+    }
+    callees
+}
+
+fn direct_callee<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    callee: Instance<'tcx>,
+    diverges: bool,
+) -> Option<Callee> {
+    match callee.def {
+        // Drop glue for types without drop glue does nothing.
+        ty::InstanceDef::DropGlue(_, None) => None,
+        // Intrinsics are codegened inline.
+        ty::InstanceDef::Intrinsic(_) => None,
+        ty::InstanceDef::Virtual(def_id, _) => Some(Callee::Indirect(format!(
+            "calls the trait object method `{}`",
+            tcx.def_path_str(def_id)
+        ))),
+        _ => Some(Callee::Direct {
+            symbol: tcx.symbol_name(callee).name.to_string(),
+            name: callee.to_string(),
+            diverges,
+        }),
+    }
+}
+
+/// Prints the worst-case stack usage of the roots of the call graph of the crate, and checks
+/// it against their `#[stack_limit]`s. Has to run before the object files are removed.
+pub fn report_stack_usage(sess: &Session, codegen_results: &CodegenResults) {
+    let graph = match codegen_results.crate_info.stack_usage {
+        Some(ref graph) => graph,
+        None => return,
+    };
+
+    let mut frames = FxHashMap::default();
+    let modules = codegen_results.modules.iter().chain(&codegen_results.allocator_module);
+    for path in modules.filter_map(|module| module.object.as_ref()) {
+        let result = fs::read(path)
+            .map_err(|err| err.to_string())
+            .and_then(|data| read_stack_sizes(&data, &mut frames));
+        if let Err(err) = result {
+            sess.err(&format!("failed to read stack sizes from `{}`: {}", path.display(), err));
+            return;
+        }
+    }
+    if frames.is_empty() {
+        sess.warn(
+            "no stack sizes were found in the object files, \
+             `-Z print-stack-usage` is only supported for ELF targets",
+        );
+        return;
+    }
+
+    let mut usage = StackUsage {
+        graph,
+        frames: &frames,
+        memo: Default::default(),
+        in_progress: Default::default(),
+    };
+    for root in &graph.roots {
+        match usage.of(&root.symbol) {
+            Usage::Bounded(bytes, uncounted) => {
+                match uncounted {
+                    None => println!("print-stack-usage `{}`: {} bytes", root.name, bytes),
+                    Some(ref uncounted) => println!(
+                        "print-stack-usage `{}`: at least {} bytes, {}",
+                        root.name, bytes, uncounted
+                    ),
+                }
+                let limit = match root.limit {
+                    Some(limit) => limit,
+                    None => continue,
+                };
+                let mut diag = if bytes > limit {
+                    sess.struct_span_err(
+                        root.span,
+                        &format!(
+                            "`{}` may use {} bytes of stack, exceeding its \
+                             `#[stack_limit]` of {} bytes",
+                            root.name, bytes, limit
+                        ),
+                    )
+                } else {
+                    // The limit may still be exceeded by what the stack usage leaves out.
+                    let mut diag = sess.struct_note_without_error(&format!(
+                        "the `#[stack_limit]` of `{}` is only checked against a lower bound of \
+                         its stack usage, {} bytes",
+                        root.name, bytes
+                    ));
+                    diag.set_span(root.span);
+                    diag
+                };
+                if let Some(ref uncounted) = uncounted {
+                    diag.note(&format!("{}: the panic machinery isn't counted", uncounted));
+                }
+                diag.note(UNCOUNTED).emit();
+            }
+            Usage::Unbounded(reason) => {
+                println!("print-stack-usage `{}`: unbounded, {}", root.name, reason);
+                if root.limit.is_some() {
+                    sess.struct_span_err(
+                        root.span,
+                        &format!(
+                            "the stack usage of `{}` is unbounded, but it has a `#[stack_limit]`",
+                            root.name
+                        ),
+                    )
+                    .note(&reason)
+                    .emit();
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+enum Usage {
+    /// Bounded stack usage, with the panic machinery that it leaves out, if any, which makes it
+    /// a lower bound beyond what `UNCOUNTED` says.
+    Bounded(u64, Option<String>),
+    /// Unbounded stack usage, with the reason why.
+    Unbounded(String),
+}
+
+struct StackUsage<'a> {
+    graph: &'a CallGraph,
+    frames: &'a FxHashMap<String, u64>,
+    memo: FxHashMap<&'a str, Usage>,
+    /// The functions on the current call chain, to detect recursion.
+    in_progress: FxHashSet<&'a str>,
+}
+
+impl<'a> StackUsage<'a> {
+    /// Computes the worst-case stack usage of a call of the function `symbol`, which has to be
+    /// codegened for the crate.
+    fn of(&mut self, symbol: &'a str) -> Usage {
+        if let Some(usage) = self.memo.get(symbol) {
+            return usage.clone();
+        }
+        let graph = self.graph;
+        let node = &graph.nodes[symbol];
+        if !self.in_progress.insert(symbol) {
+            return Usage::Unbounded(format!("`{}` is recursive", node.name));
+        }
+
+        let mut deepest = Usage::Bounded(0, None);
+        for callee in &node.callees {
+            let usage = match *callee {
+                Callee::Direct { ref symbol, .. } if graph.nodes.contains_key(symbol) => {
+                    ensure_sufficient_stack(|| self.of(symbol))
+                }
+                // Like the panics of failed assertions, the calls of upstream functions that
+                // never return, like the panic functions, aren't followed.
+                Callee::Direct { ref name, diverges: true, .. } => Usage::Bounded(
+                    0,
+                    Some(format!("`{}` calls `{}`, which never returns", node.name, name)),
+                ),
+                Callee::Direct { ref name, .. } => Usage::Unbounded(format!(
+                    "`{}` calls `{}`, whose stack usage is unknown",
+                    node.name, name
+                )),
+                Callee::Indirect(ref what) => {
+                    Usage::Unbounded(format!("`{}` {}", node.name, what))
+                }
+                Callee::Assert => Usage::Bounded(
+                    0,
+                    Some(format!("`{}` may panic on a failed assertion", node.name)),
+                ),
+            };
+            deepest = match (deepest, usage) {
+                (Usage::Bounded(a, a_uncounted), Usage::Bounded(b, b_uncounted)) => {
+                    Usage::Bounded(a.max(b), a_uncounted.or(b_uncounted))
+                }
+                (Usage::Bounded(..), unbounded) => unbounded,
+                (unbounded, _) => unbounded,
+            };
+            if let Usage::Unbounded(_) = deepest {
+                break;
+            }
+        }
+        self.in_progress.remove(symbol);
+
+        // Functions that were inlined into all of their callers have no frame of their own.
+        let frame = self.frames.get(symbol).copied().unwrap_or(0);
+        let usage = match deepest {
+            Usage::Bounded(bytes, uncounted) => Usage::Bounded(frame + bytes, uncounted),
+            unbounded => unbounded,
+        };
+        self.memo.insert(symbol, usage.clone());
+        usage
+    }
+}
+
+/// Reads the frame sizes recorded in the `.stack_sizes` sections of a relocatable ELF object
+/// into `frames`, by symbol name. Each entry of these sections is the address of a function,
+/// given by a relocation, followed by the size of its frame as an ULEB128 number.
+fn read_stack_sizes(data: &[u8], frames: &mut FxHashMap<String, u64>) -> Result<(), String> {
+    let elf = Elf::parse(data)?;
+    let symtab = match elf.sections.iter().position(|section| section.kind == SHT_SYMTAB) {
+        Some(index) => index,
+        // An object file without symbols doesn't define any functions.
+        None => return Ok(()),
+    };
+    let symbols = elf.symbols(symtab)?;
+
+    // Relocations refer to functions either through their own symbol or through the symbol of
+    // the section they are in, with an addend.
+    let mut functions = FxHashMap::default();
+    for symbol in &symbols {
+        if symbol.kind == STT_FUNC {
+            functions.insert((symbol.section, symbol.value), &symbol.name);
+        }
+    }
+
+    for (index, section) in elf.sections.iter().enumerate() {
+        if elf.section_name(section)? != ".stack_sizes" {
+            continue;
+        }
+        let mut relocations = FxHashMap::default();
+        for relocs in &elf.sections {
+            if (relocs.kind == SHT_RELA || relocs.kind == SHT_REL) && relocs.info as usize == index
+            {
+                for (offset, symbol, addend) in elf.relocations(relocs)? {
+                    relocations.insert(offset, (symbol, addend));
+                }
+            }
+        }
+
+        let contents = elf.contents(section)?;
+        let address_size = if elf.is_64 { 8 } else { 4 };
+        let mut offset = 0;
+        while offset < contents.len() {
+            let address = elf.read_address(contents, offset)?;
+            let (size, len) = read_frame_size(&contents[offset + address_size..])?;
+            let entry = offset as u64;
+            offset += address_size + len;
+
+            let (symbol, addend) = match relocations.get(&entry) {
+                Some(&(symbol, addend)) => (symbol, addend),
+                None => continue,
+            };
+            let symbol = symbols.get(symbol).ok_or("invalid symbol index")?;
+            let name = if symbol.kind == STT_SECTION {
+                let value = symbol.value.wrapping_add(address).wrapping_add(addend as u64);
+                // Thumb functions have the lowest bit of their symbol's value set.
+                match functions.get(&(symbol.section, value)) {
+                    Some(name) => Some(*name),
+                    None => functions.get(&(symbol.section, value | 1)).copied(),
+                }
+            } else {
+                Some(&symbol.name)
+            };
+            if let Some(name) = name {
+                frames.insert(name.clone(), size);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Reads the ULEB128 frame size at the start of `data`, and returns it with its length in bytes.
+fn read_frame_size(data: &[u8]) -> Result<(u64, usize), String> {
+    // The last byte of an ULEB128 number is the first one without its high bit set, and a `u64`
+    // takes at most 10 bytes.
+    let len = match data.iter().position(|&byte| byte & 0x80 == 0) {
+        Some(index) if index < 10 => index + 1,
+        Some(_) => return Err("invalid frame size in `.stack_sizes`".to_string()),
+        None => return Err("truncated `.stack_sizes` section".to_string()),
+    };
+    Ok(leb128::read_u64_leb128(&data[..len]))
+}
@@ -85,6 +85,7 @@ pub struct ModuleConfig {
 
     pub pgo_gen: SwitchWithOptPath,
     pub pgo_use: Option<PathBuf>,
+    pub pgo_sample_use: Option<PathBuf>,
 
     pub sanitizer: SanitizerSet,
     pub sanitizer_recover: SanitizerSet,
@@ -194,6 +195,7 @@ impl ModuleConfig {
                 SwitchWithOptPath::Disabled
             ),
             pgo_use: if_regular!(sess.opts.cg.profile_use.clone(), None),
+            pgo_sample_use: if_regular!(sess.opts.cg.profile_sample_use.clone(), None),
 
             sanitizer: if_regular!(sess.opts.debugging_opts.sanitizer, SanitizerSet::empty()),
             sanitizer_recover: if_regular!(
@@ -312,6 +314,7 @@ pub struct CodegenContext<B: WriteBackendMethods> {
     pub target_pointer_width: String,
     pub target_arch: String,
     pub debuginfo: config::DebugInfo,
+    pub split_debuginfo: config::SplitDebuginfo,
 
     // Number of cgus excluding the allocator/metadata modules
     pub total_cgus: usize,
@@ -491,9 +494,10 @@ fn copy_all_cgu_workproducts_to_incr_comp_cache_dir(
 
     for module in compiled_modules.modules.iter().filter(|m| m.kind == ModuleKind::Regular) {
         let path = module.object.as_ref().map(|path| path.clone());
+        let dwarf_path = module.dwarf_object.as_ref().map(|path| path.clone());
 
         if let Some((id, product)) =
-            copy_cgu_workproduct_to_incr_comp_cache_dir(sess, &module.name, &path)
+            copy_cgu_workproduct_to_incr_comp_cache_dir(sess, &module.name, &path, &dwarf_path)
         {
             work_products.insert(id, product);
         }
@@ -834,27 +838,37 @@ fn execute_copy_from_cache_work_item<B: ExtraBackendMethods>(
     module_config: &ModuleConfig,
 ) -> Result<WorkItemResult<B>, FatalError> {
     let incr_comp_session_dir = cgcx.incr_comp_session_dir.as_ref().unwrap();
-    let mut object = None;
-    if let Some(saved_file) = module.source.saved_file {
-        let obj_out = cgcx.output_filenames.temp_path(OutputType::Object, Some(&module.name));
-        object = Some(obj_out.clone());
-        let source_file = in_incr_comp_dir(&incr_comp_session_dir, &saved_file);
+    let load_from_incr_comp_dir = |output_path: PathBuf, saved_file: &str| {
+        let source_file = in_incr_comp_dir(&incr_comp_session_dir, saved_file);
         debug!(
             "copying pre-existing module `{}` from {:?} to {}",
             module.name,
             source_file,
-            obj_out.display()
+            output_path.display()
         );
-        if let Err(err) = link_or_copy(&source_file, &obj_out) {
+        if let Err(err) = link_or_copy(&source_file, &output_path) {
             let diag_handler = cgcx.create_diag_handler();
             diag_handler.err(&format!(
                 "unable to copy {} to {}: {}",
                 source_file.display(),
-                obj_out.display(),
+                output_path.display(),
                 err
             ));
         }
-    }
+        output_path
+    };
+
+    let object = module.source.saved_file.as_ref().map(|saved_file| {
+        let obj_out = cgcx.output_filenames.temp_path(OutputType::Object, Some(&module.name));
+        load_from_incr_comp_dir(obj_out, saved_file)
+    });
+    let dwarf_object = module.source.saved_dwarf_file.as_ref().map(|saved_dwarf_file| {
+        let dwo_out = cgcx
+            .output_filenames
+            .split_dwarf_path(cgcx.split_debuginfo, Some(&module.name))
+            .expect("saved split DWARF object without split debuginfo");
+        load_from_incr_comp_dir(dwo_out, saved_dwarf_file)
+    });
 
     assert_eq!(object.is_some(), module_config.emit_obj != EmitObj::None);
 
@@ -862,6 +876,7 @@ fn execute_copy_from_cache_work_item<B: ExtraBackendMethods>(
         name: module.name,
         kind: ModuleKind::Regular,
         object,
+        dwarf_object,
         bytecode: None,
     }))
 }
@@ -1022,6 +1037,7 @@ fn start_executing_work<B: ExtraBackendMethods>(
         target_pointer_width: tcx.sess.target.target.target_pointer_width.clone(),
         target_arch: tcx.sess.target.target.arch.clone(),
         debuginfo: tcx.sess.opts.debuginfo,
+        split_debuginfo: tcx.sess.split_debuginfo(),
     };
 
     // This is the "main loop" of parallel work happening for parallel codegen.
@@ -13,6 +13,7 @@
 //!   but one `llvm::Type` corresponds to many `Ty`s; for instance, `tup(int, int,
 //!   int)` and `rec(x=int, y=int, z=int)` will have the same `llvm::Type`.
 
+use crate::back::stack_usage;
 use crate::back::write::{
     compute_per_cgu_lto_type, start_async_codegen, submit_codegened_module_to_llvm,
     submit_post_lto_module_to_llvm, submit_pre_lto_module_to_llvm, ComputedLtoType, OngoingCodegen,
@@ -28,6 +29,7 @@ use crate::{CachedModuleCodegen, CrateInfo, MemFlags, ModuleCodegen, ModuleKind}
 use rustc_attr as attr;
 use rustc_data_structures::fx::FxHashMap;
 use rustc_data_structures::profiling::print_time_passes_entry;
+use rustc_data_structures::stable_hasher::StableHasher;
 use rustc_data_structures::sync::{par_iter, Lock, ParallelIterator};
 use rustc_hir as hir;
 use rustc_hir::def_id::{LocalDefId, LOCAL_CRATE};
@@ -51,6 +53,8 @@ use rustc_symbol_mangling::test as symbol_names_test;
 use rustc_target::abi::{Abi, Align, LayoutOf, Scalar, VariantIdx};
 
 use std::cmp;
+use std::fs;
+use std::hash::Hash;
 use std::ops::{Deref, DerefMut};
 use std::time::{Duration, Instant};
 
@@ -808,6 +812,11 @@ impl CrateInfo {
             lang_item_to_crate: Default::default(),
             missing_lang_items: Default::default(),
             dependency_formats: tcx.dependency_formats(LOCAL_CRATE),
+            stack_usage: if tcx.sess.opts.debugging_opts.print_stack_usage {
+                Some(tcx.sess.time("stack_usage_call_graph", || stack_usage::call_graph(tcx)))
+            } else {
+                None
+            },
         };
         let lang_items = tcx.lang_items();
 
@@ -853,6 +862,25 @@ impl CrateInfo {
     }
 }
 
+pub fn provide(providers: &mut Providers<'_>) {
+    providers.profile_sample_use_fingerprint = |tcx, cnum| {
+        assert_eq!(cnum, LOCAL_CRATE);
+        let path = tcx.sess.opts.cg.profile_sample_use.as_ref()?;
+        match fs::read(path) {
+            Ok(contents) => {
+                let mut hasher = StableHasher::new();
+                contents.hash(&mut hasher);
+                Some(hasher.finish())
+            }
+            Err(err) => tcx.sess.fatal(&format!(
+                "failed to read sample profile `{}`: {}",
+                path.display(),
+                err
+            )),
+        }
+    };
+}
+
 pub fn provide_both(providers: &mut Providers<'_>) {
     providers.backend_optimization_level = |tcx, cratenum| {
         let for_speed = match tcx.sess.opts.optimize {
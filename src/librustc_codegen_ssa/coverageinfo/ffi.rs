@@ -0,0 +1,82 @@
+use rustc_middle::mir::coverage::{CounterValueReference, CoverageTerm, Op};
+
+/// Aligns with `llvm::coverage::Counter::CounterKind`.
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+enum CounterKind {
+    Zero = 0,
+    CounterValueReference = 1,
+    Expression = 2,
+}
+
+/// A reference to an instance of an abstract "counter" that will yield a value in a coverage
+/// report. Note that `id` has different interpretations, depending on the `kind`:
+///   * For `CounterKind::Zero`, `id` is assumed to be `0`
+///   * For `CounterKind::CounterValueReference`, `id` matches the `counter_id` of the injected
+///     instrumentation counter (the `index` argument to the LLVM intrinsic
+///     `instrprof.increment()`)
+///   * For `CounterKind::Expression`, `id` is the index into the coverage map's array of
+///     counter expressions.
+/// Aligns with `llvm::coverage::Counter`.
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct Counter {
+    // Important: The layout (order and types of fields) must match its C++ counterpart.
+    kind: CounterKind,
+    id: u32,
+}
+
+impl Counter {
+    pub fn zero() -> Self {
+        Self { kind: CounterKind::Zero, id: 0 }
+    }
+
+    pub fn counter_value_reference(counter_id: CounterValueReference) -> Self {
+        Self { kind: CounterKind::CounterValueReference, id: counter_id.as_u32() }
+    }
+
+    pub fn expression(expression_index: u32) -> Self {
+        Self { kind: CounterKind::Expression, id: expression_index }
+    }
+
+    pub fn from_term(term: CoverageTerm) -> Self {
+        match term {
+            CoverageTerm::Zero => Self::zero(),
+            CoverageTerm::Counter(id) => Self::counter_value_reference(id),
+            CoverageTerm::Expression(id) => Self::expression(id.as_u32()),
+        }
+    }
+}
+
+/// Aligns with `llvm::coverage::CounterExpression::ExprKind`.
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub enum ExprKind {
+    Subtract = 0,
+    Add = 1,
+}
+
+impl From<Op> for ExprKind {
+    fn from(op: Op) -> Self {
+        match op {
+            Op::Subtract => ExprKind::Subtract,
+            Op::Add => ExprKind::Add,
+        }
+    }
+}
+
+/// Aligns with `llvm::coverage::CounterExpression`.
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct CounterExpression {
+    // Important: The layout (order and types of fields) must match its C++ counterpart.
+    pub kind: ExprKind,
+    pub lhs: Counter,
+    pub rhs: Counter,
+}
+
+impl CounterExpression {
+    pub fn new(lhs: Counter, kind: ExprKind, rhs: Counter) -> Self {
+        Self { kind, lhs, rhs }
+    }
+}
@@ -0,0 +1,119 @@
+pub use super::ffi::*;
+
+use rustc_index::vec::IndexVec;
+use rustc_middle::mir::coverage::{
+    CodeRegion, CounterValueReference, CoverageTerm, InjectedExpressionIndex, Op,
+};
+use rustc_middle::ty::{Instance, TyCtxt};
+
+#[derive(Clone, Debug)]
+struct Expression {
+    lhs: CoverageTerm,
+    op: Op,
+    rhs: CoverageTerm,
+    regions: Vec<CodeRegion>,
+}
+
+/// Collects the counters and counter expressions of one function instance, and their code
+/// regions, as the function's `StatementKind::Coverage` statements are codegenned.
+#[derive(Clone, Debug)]
+pub struct FunctionCoverage {
+    source_hash: u64,
+    counters: IndexVec<CounterValueReference, Vec<CodeRegion>>,
+    expressions: IndexVec<InjectedExpressionIndex, Option<Expression>>,
+    unreachable_regions: Vec<CodeRegion>,
+}
+
+impl FunctionCoverage {
+    pub fn new<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> Self {
+        let coverageinfo = tcx.coverageinfo(instance.def_id());
+        Self {
+            source_hash: 0, // will be set with the first `add_counter()`
+            counters: IndexVec::from_elem_n(Vec::new(), coverageinfo.num_counters as usize),
+            expressions: IndexVec::from_elem_n(None, coverageinfo.num_expressions as usize),
+            unreachable_regions: Vec::new(),
+        }
+    }
+
+    /// Creates the coverage of a function that is never codegenned: all of its code regions
+    /// have a count of zero.
+    pub fn unused(source_hash: u64, regions: Vec<CodeRegion>) -> Self {
+        Self {
+            source_hash,
+            counters: IndexVec::new(),
+            expressions: IndexVec::new(),
+            unreachable_regions: regions,
+        }
+    }
+
+    /// Adds the code regions to be counted by an injected counter intrinsic.
+    pub fn add_counter(
+        &mut self,
+        source_hash: u64,
+        id: CounterValueReference,
+        regions: Vec<CodeRegion>,
+    ) {
+        if self.source_hash == 0 {
+            self.source_hash = source_hash;
+        } else {
+            debug_assert_eq!(source_hash, self.source_hash);
+        }
+        self.counters[id] = regions;
+    }
+
+    /// Adds a counter expression, and the code regions whose count it computes, if any.
+    pub fn add_counter_expression(
+        &mut self,
+        id: InjectedExpressionIndex,
+        lhs: CoverageTerm,
+        op: Op,
+        rhs: CoverageTerm,
+        regions: Vec<CodeRegion>,
+    ) {
+        self.expressions[id] = Some(Expression { lhs, op, rhs, regions });
+    }
+
+    pub fn source_hash(&self) -> u64 {
+        self.source_hash
+    }
+
+    /// Generates the counter expressions, in the order expected by LLVM (the index of each
+    /// expression is its `InjectedExpressionIndex`), and the code regions with the counter or
+    /// expression counting them, sorted by file and position.
+    ///
+    /// Expressions removed from the MIR (for example, along with an unreachable block) are
+    /// replaced by `Zero - Zero`, since other expressions may still refer to them by index.
+    pub fn get_expressions_and_counter_regions(
+        &self,
+    ) -> (Vec<CounterExpression>, Vec<(Counter, &CodeRegion)>) {
+        let counter_expressions = self
+            .expressions
+            .iter()
+            .map(|expression| match expression {
+                Some(Expression { lhs, op, rhs, .. }) => CounterExpression::new(
+                    Counter::from_term(*lhs),
+                    ExprKind::from(*op),
+                    Counter::from_term(*rhs),
+                ),
+                None => {
+                    CounterExpression::new(Counter::zero(), ExprKind::Subtract, Counter::zero())
+                }
+            })
+            .collect();
+
+        let counter_regions = self.counters.iter_enumerated().flat_map(|(id, regions)| {
+            regions.iter().map(move |region| (Counter::counter_value_reference(id), region))
+        });
+        let expression_regions = self.expressions.iter_enumerated().flat_map(|(id, expression)| {
+            let regions = expression.as_ref().map_or(&[][..], |expression| &expression.regions[..]);
+            regions.iter().map(move |region| (Counter::expression(id.as_u32()), region))
+        });
+        let unreachable_regions =
+            self.unreachable_regions.iter().map(|region| (Counter::zero(), region));
+
+        let mut regions: Vec<_> =
+            counter_regions.chain(expression_regions).chain(unreachable_regions).collect();
+        regions.sort_unstable_by_key(|(_, region)| *region);
+        (counter_expressions, regions)
+    }
+}
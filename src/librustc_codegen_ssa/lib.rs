@@ -27,13 +27,14 @@ use rustc_middle::dep_graph::WorkProduct;
 use rustc_middle::middle::cstore::{CrateSource, LibSource, NativeLib};
 use rustc_middle::middle::dependency_format::Dependencies;
 use rustc_middle::ty::query::Providers;
-use rustc_session::config::{OutputFilenames, OutputType, RUST_CGU_EXT};
+use rustc_session::config::{OutputFilenames, OutputType, DWARF_OBJECT_EXT, RUST_CGU_EXT};
 use rustc_span::symbol::Symbol;
 use std::path::{Path, PathBuf};
 
 pub mod back;
 pub mod base;
 pub mod common;
+pub mod coverageinfo;
 pub mod debuginfo;
 pub mod glue;
 pub mod meth;
@@ -60,13 +61,16 @@ impl<M> ModuleCodegen<M> {
     pub fn into_compiled_module(
         self,
         emit_obj: bool,
+        emit_dwarf_obj: bool,
         emit_bc: bool,
         outputs: &OutputFilenames,
     ) -> CompiledModule {
         let object = emit_obj.then(|| outputs.temp_path(OutputType::Object, Some(&self.name)));
+        let dwarf_object =
+            emit_dwarf_obj.then(|| outputs.temp_path_ext(DWARF_OBJECT_EXT, Some(&self.name)));
         let bytecode = emit_bc.then(|| outputs.temp_path(OutputType::Bitcode, Some(&self.name)));
 
-        CompiledModule { name: self.name.clone(), kind: self.kind, object, bytecode }
+        CompiledModule { name: self.name.clone(), kind: self.kind, object, dwarf_object, bytecode }
     }
 }
 
@@ -75,6 +79,8 @@ pub struct CompiledModule {
     pub name: String,
     pub kind: ModuleKind,
     pub object: Option<PathBuf>,
+    /// The split DWARF object file emitted alongside `object`, if debuginfo is split.
+    pub dwarf_object: Option<PathBuf>,
     pub bytecode: Option<PathBuf>,
 }
 
@@ -122,6 +128,8 @@ pub struct CrateInfo {
     pub lang_item_to_crate: FxHashMap<LangItem, CrateNum>,
     pub missing_lang_items: FxHashMap<CrateNum, Vec<LangItem>>,
     pub dependency_formats: Lrc<Dependencies>,
+    /// The call graph of the crate, for `-Z print-stack-usage`.
+    pub stack_usage: Option<back::stack_usage::CallGraph>,
 }
 
 #[derive(RustcEncodable, RustcDecodable)]
@@ -139,6 +147,7 @@ pub struct CodegenResults {
 
 pub fn provide(providers: &mut Providers<'_>) {
     crate::back::symbol_export::provide(providers);
+    crate::base::provide(providers);
     crate::base::provide_both(providers);
 }
 
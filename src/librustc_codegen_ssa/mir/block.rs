@@ -17,7 +17,9 @@ use rustc_middle::mir::interpret::{AllocId, ConstValue, Pointer, Scalar};
 use rustc_middle::mir::AssertKind;
 use rustc_middle::ty::layout::{FnAbiExt, HasTyCtxt};
 use rustc_middle::ty::{self, Instance, Ty, TypeFoldable};
+use rustc_session::config::SanitizerSet;
 use rustc_span::{source_map::Span, symbol::Symbol};
+use rustc_symbol_mangling::{typeid_for_fn_sig, typeid_for_virtual_call};
 use rustc_target::abi::call::{ArgAbi, FnAbi, PassMode};
 use rustc_target::abi::{self, LayoutOf};
 use rustc_target::spec::abi::Abi;
@@ -341,7 +343,14 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                 let fn_abi = FnAbi::of_instance(&bx, virtual_drop, &[]);
                 let vtable = args[1];
                 args = &args[..1];
-                (meth::DESTRUCTOR.get_fn(&mut bx, vtable, &fn_abi), fn_abi)
+                let drop_fn = meth::DESTRUCTOR.get_fn(&mut bx, vtable, &fn_abi);
+                // The destructor in a vtable is checked like any other virtual call.
+                if bx.tcx().sess.opts.debugging_opts.sanitizer.contains(SanitizerSet::CFI) {
+                    let sig = virtual_drop.monomorphic_ty(bx.tcx()).fn_sig(bx.tcx());
+                    let typeid = typeid_for_virtual_call(bx.tcx(), sig);
+                    bx = self.codegen_type_test(bx, drop_fn, &typeid);
+                }
+                (drop_fn, fn_abi)
             }
             _ => (bx.get_fn_addr(drop_fn), FnAbi::of_instance(&bx, drop_fn, &[])),
         };
@@ -357,6 +366,18 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
         );
     }
 
+    /// Checks with `-Zsanitizer=cfi` that `fn_ptr` has the type identifier `typeid`, and aborts
+    /// if it doesn't. Returns the builder of the block in which the call can be made.
+    fn codegen_type_test(&mut self, mut bx: Bx, fn_ptr: Bx::Value, typeid: &str) -> Bx {
+        let cond = bx.type_test(fn_ptr, typeid);
+        let mut fail = bx.build_sibling_block("type_test.fail");
+        fail.abort();
+        fail.unreachable();
+        let pass = bx.build_sibling_block("type_test.pass");
+        bx.cond_br(cond, pass.llbb(), fail.llbb());
+        pass
+    }
+
     fn codegen_assert_terminator(
         &mut self,
         helper: TerminatorCodegenHelper<'tcx>,
@@ -695,7 +716,6 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                 &args,
                 dest,
                 terminator.source_info.span,
-                self.instance,
             );
 
             if let ReturnDest::IndirectOperand(dst, _) = ret_dest {
@@ -818,6 +838,21 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
             _ => span_bug!(span, "no llfn for call"),
         };
 
+        // With `-Zsanitizer=cfi`, check that the target of an indirect call
+        // has the type the call site expects, and abort if it doesn't.
+        if bx.tcx().sess.opts.debugging_opts.sanitizer.contains(SanitizerSet::CFI) {
+            let typeid = match (llfn, def) {
+                (Some(_), Some(ty::InstanceDef::Virtual(..))) => {
+                    Some(typeid_for_virtual_call(bx.tcx(), sig))
+                }
+                (Some(_), None) => Some(typeid_for_fn_sig(bx.tcx(), sig)),
+                _ => None,
+            };
+            if let Some(typeid) = typeid {
+                bx = self.codegen_type_test(bx, fn_ptr, &typeid);
+            }
+        }
+
         if let Some((_, target)) = destination.as_ref() {
             helper.maybe_sideeffect(self.mir, &mut bx, &[*target]);
         }
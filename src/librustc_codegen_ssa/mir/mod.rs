@@ -481,6 +481,7 @@ fn arg_local_refs<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
 mod analyze;
 mod block;
 pub mod constant;
+mod coverageinfo;
 pub mod debuginfo;
 pub mod operand;
 pub mod place;
@@ -0,0 +1,35 @@
+use super::BackendTypes;
+use rustc_middle::mir::coverage::*;
+use rustc_middle::ty::Instance;
+
+pub trait CoverageInfoMethods: BackendTypes {
+    fn coverageinfo_finalize(&self);
+}
+
+pub trait CoverageInfoBuilderMethods<'tcx>: BackendTypes {
+    /// Returns the variable holding the function's name, the first argument of
+    /// `llvm.instrprof.increment`.
+    fn create_pgo_func_name_var(&self, instance: Instance<'tcx>) -> Self::Value;
+
+    /// Records the code regions counted by a counter. Returns `false` if the backend is not
+    /// collecting coverage for this codegen unit, in which case the counter should not be
+    /// incremented either.
+    fn add_counter_region(
+        &mut self,
+        instance: Instance<'tcx>,
+        function_source_hash: u64,
+        id: CounterValueReference,
+        regions: Vec<CodeRegion>,
+    ) -> bool;
+
+    /// Records a counter expression, and the code regions whose count it computes, if any.
+    fn add_counter_expression_region(
+        &mut self,
+        instance: Instance<'tcx>,
+        id: InjectedExpressionIndex,
+        lhs: CoverageTerm,
+        op: Op,
+        rhs: CoverageTerm,
+        regions: Vec<CodeRegion>,
+    ) -> bool;
+}
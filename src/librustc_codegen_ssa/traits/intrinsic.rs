@@ -15,12 +15,14 @@ pub trait IntrinsicCallMethods<'tcx>: BackendTypes {
         args: &[OperandRef<'tcx, Self::Value>],
         llresult: Self::Value,
         span: Span,
-        caller_instance: ty::Instance<'tcx>,
     );
 
     fn abort(&mut self);
     fn assume(&mut self, val: Self::Value);
     fn expect(&mut self, cond: Self::Value, expected: bool) -> Self::Value;
+    /// Trait method used to test whether a given pointer is associated with a
+    /// `-Z sanitizer=cfi` type identifier.
+    fn type_test(&mut self, pointer: Self::Value, typeid: &str) -> Self::Value;
     fn sideeffect(&mut self);
     /// Trait method used to inject `va_start` on the "spoofed" `VaListImpl` in
     /// Rust defined C-variadic functions.
@@ -0,0 +1,152 @@
+//! `-Z verify-determinism`: compiles the crate a second time, in a temporary output directory
+//! and with different parallelism, and reports the outputs that differ between the two
+//! compilations, down to the metadata table or object file section that differs.
+
+use rustc_codegen_ssa::back::determinism::compare_outputs;
+use rustc_interface::{interface, Queries};
+use rustc_session::config::{Input, OutputType, OutputTypes};
+use rustc_session::lint;
+use rustc_session::output::{filename_for_metadata, out_filename};
+use rustc_session::{early_error, DiagnosticOutput, Session};
+
+use std::mem;
+use std::path::PathBuf;
+
+/// The second compilation of `-Z verify-determinism`, which is set up before the first one.
+pub struct Verifier {
+    config: interface::Config,
+    out_dir: tempfile::TempDir,
+}
+
+impl Verifier {
+    /// Copies the configuration of the first compilation, except for the location of its
+    /// outputs, its incremental compilation directory and its parallelism.
+    pub fn new(config: &interface::Config) -> Verifier {
+        let out_dir = tempfile::Builder::new()
+            .prefix("rustc-determinism")
+            .tempdir()
+            .unwrap_or_else(|err| {
+                let msg = format!("couldn't create a temp dir: {}", err);
+                early_error(config.opts.error_format, &msg)
+            });
+        let in_out_dir = |path: &PathBuf| out_dir.path().join(path.file_name().unwrap_or_default());
+
+        let mut opts = config.opts.clone();
+        let output_types: Vec<_> = opts
+            .output_types
+            .keys()
+            .zip(opts.output_types.values())
+            .map(|(&output_type, path)| (output_type, path.as_ref().map(in_out_dir)))
+            .collect();
+        opts.output_types = OutputTypes::new(&output_types);
+        opts.incremental = None;
+        // The diagnostics of the crate have already been reported by the first compilation.
+        opts.lint_cap = Some(lint::Level::Allow);
+        opts.json_artifact_notifications = false;
+        opts.debugging_opts.no_parallel_llvm = !opts.debugging_opts.no_parallel_llvm;
+        if cfg!(parallel_compiler) {
+            opts.debugging_opts.threads = if opts.debugging_opts.threads == 1 { 4 } else { 1 };
+        }
+
+        let input = match &config.input {
+            Input::File(path) => Input::File(path.clone()),
+            Input::Str { name, input } => Input::Str { name: name.clone(), input: input.clone() },
+        };
+        let config = interface::Config {
+            opts,
+            crate_cfg: config.crate_cfg.clone(),
+            input,
+            input_path: config.input_path.clone(),
+            output_dir: Some(out_dir.path().to_path_buf()),
+            output_file: config.output_file.as_ref().map(in_out_dir),
+            file_loader: None,
+            diagnostic_output: DiagnosticOutput::Default,
+            stderr: None,
+            crate_name: config.crate_name.clone(),
+            lint_caps: config.lint_caps.clone(),
+            register_lints: None,
+            override_queries: config.override_queries,
+            registry: config.registry.clone(),
+        };
+        Verifier { config, out_dir }
+    }
+
+    /// Runs the second compilation, and reports the outputs of the first compilation, `outputs`,
+    /// that differ from the outputs of the second one.
+    pub fn verify(self, sess: &Session, outputs: &[PathBuf]) -> interface::Result<()> {
+        if outputs.is_empty() {
+            return Ok(());
+        }
+        let second = interface::run_compiler(self.config, |compiler| {
+            let sess = compiler.session();
+            let (linker, outputs) = compiler.enter(|queries| {
+                queries.prepare_outputs()?;
+                queries.global_ctxt()?;
+                mem::drop(queries.expansion()?.take());
+                queries.ongoing_codegen()?;
+                let outputs = output_files(sess, queries)?;
+                Ok((queries.linker()?, outputs))
+            })?;
+            linker.link()?;
+            Ok((outputs, mem::take(&mut *sess.metadata_sections.lock())))
+        });
+        let (second_outputs, second_sections) = match second {
+            Ok(second) => second,
+            Err(_) => {
+                sess.err("the second compilation of `-Z verify-determinism` failed");
+                return sess.compile_status();
+            }
+        };
+        let first_sections = sess.metadata_sections.lock();
+
+        for (first, second) in outputs.iter().zip(&second_outputs) {
+            // Not every requested output is written, e.g. for crates with errors.
+            if !first.exists() {
+                continue;
+            }
+            match compare_outputs(first, second, (&first_sections[..], &second_sections[..])) {
+                Ok(differences) if differences.is_empty() => {}
+                Ok(differences) => {
+                    let mut err = sess.struct_err(&format!(
+                        "output `{}` differs between two compilations",
+                        first.display()
+                    ));
+                    for difference in differences {
+                        err.note(&difference);
+                    }
+                    err.emit();
+                }
+                Err(msg) => sess.err(&format!(
+                    "failed to compare output `{}` with the second compilation: {}",
+                    first.display(),
+                    msg
+                )),
+            }
+        }
+        drop(self.out_dir);
+        sess.compile_status()
+    }
+}
+
+/// Returns the files written by the compilation, in an order that only depends on the options,
+/// to pair the outputs of the two compilations.
+pub fn output_files(sess: &Session, queries: &Queries<'_>) -> interface::Result<Vec<PathBuf>> {
+    let outputs = queries.prepare_outputs()?.peek();
+    let crate_name = queries.crate_name()?.peek();
+    let mut files = Vec::new();
+    for &output_type in sess.opts.output_types.keys() {
+        match output_type {
+            // The dependency info is made of the paths of the inputs and of the outputs, the
+            // latter of which are different for the second compilation.
+            OutputType::DepInfo => {}
+            OutputType::Exe => {
+                for &crate_type in sess.crate_types().iter() {
+                    files.push(out_filename(sess, crate_type, &outputs, &crate_name));
+                }
+            }
+            OutputType::Metadata => files.push(filename_for_metadata(sess, &crate_name, &outputs)),
+            _ => files.push(outputs.path(output_type)),
+        }
+    }
+    Ok(files)
+}
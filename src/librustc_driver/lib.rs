@@ -20,7 +20,7 @@ use rustc_codegen_ssa::{traits::CodegenBackend, CodegenResults};
 use rustc_data_structures::profiling::print_time_passes_entry;
 use rustc_data_structures::sync::SeqCst;
 use rustc_errors::registry::{InvalidErrorCode, Registry};
-use rustc_errors::{ErrorReported, PResult};
+use rustc_errors::{Catalogue, ErrorReported, PResult};
 use rustc_feature::{find_gated_cfg, UnstableFeatures};
 use rustc_hir::def_id::LOCAL_CRATE;
 use rustc_interface::util::{collect_crate_types, get_builtin_codegen_backend};
@@ -56,6 +56,7 @@ use std::str;
 use std::time::Instant;
 
 mod args;
+mod determinism;
 pub mod pretty;
 
 /// Exit status code used for successful compilation and help output.
@@ -183,7 +184,8 @@ pub fn run_compiler(
     };
 
     if let Some(ref code) = matches.opt_str("explain") {
-        handle_explain(diagnostics_registry(), code, sopts.error_format);
+        let catalogue = sopts.translation_catalogue.as_deref();
+        handle_explain(diagnostics_registry(), catalogue, code, sopts.error_format);
         return Ok(());
     }
 
@@ -257,6 +259,12 @@ pub fn run_compiler(
 
     callbacks.config(&mut config);
 
+    let verifier = if config.opts.debugging_opts.verify_determinism {
+        Some(determinism::Verifier::new(&config))
+    } else {
+        None
+    };
+
     interface::run_compiler(config, |compiler| {
         let sess = compiler.session();
         let should_stop = RustcDefaultCalls::print_crate_info(
@@ -280,6 +288,7 @@ pub fn run_compiler(
             return sess.compile_status();
         }
 
+        let mut output_files = Vec::new();
         let linker = compiler.enter(|queries| {
             let early_exit = || sess.compile_status().map(|_| None);
             queries.parse()?;
@@ -389,6 +398,10 @@ pub fn run_compiler(
                 sess.code_stats.print_type_sizes();
             }
 
+            if verifier.is_some() {
+                output_files = determinism::output_files(sess, queries)?;
+            }
+
             let linker = queries.linker()?;
             Ok(Some(linker))
         })?;
@@ -398,6 +411,11 @@ pub fn run_compiler(
             linker.link()?
         }
 
+        if let Some(verifier) = verifier {
+            let _timer = sess.timer("verify_determinism");
+            verifier.verify(sess, &output_files)?;
+        }
+
         if sess.opts.debugging_opts.perf_stats {
             sess.print_perf_stats();
         }
@@ -506,10 +524,23 @@ fn stdout_isatty() -> bool {
     }
 }
 
-fn handle_explain(registry: Registry, code: &str, output: ErrorOutputType) {
+fn handle_explain(
+    registry: Registry,
+    catalogue: Option<&Catalogue>,
+    code: &str,
+    output: ErrorOutputType,
+) {
     let normalised =
         if code.starts_with('E') { code.to_string() } else { format!("E{0:0>4}", code) };
-    match registry.try_find_description(&normalised) {
+    let description = registry.try_find_description(&normalised).map(|description| {
+        // Prefer a translated explanation, but only for codes that have an English one.
+        description.map(|description| {
+            catalogue
+                .and_then(|catalogue| catalogue.explanation(&normalised))
+                .unwrap_or(description)
+        })
+    });
+    match description {
         Ok(Some(description)) => {
             let mut is_in_code_block = false;
             let mut text = String::new();
@@ -0,0 +1,298 @@
+//! Applying collected code suggestions to the source files they point into.
+//!
+//! This backs `-Z apply-suggestions`. Suggestions are resolved per file: every suggestion is
+//! applied as a whole or not at all, and when two suggestions touch overlapping code, the one
+//! starting earlier in the file wins. This keeps the result independent of the order in which
+//! the diagnostics were emitted.
+
+use crate::{Applicability, CodeSuggestion, DiagnosticId};
+
+use rustc_data_structures::sync::Lrc;
+use rustc_span::source_map::SourceMap;
+use rustc_span::{FileName, SourceFile};
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// A suggestion collected by the `Handler`, along with the code of the diagnostic it was
+/// attached to.
+#[derive(Clone, Debug)]
+pub struct CollectedSuggestion {
+    pub code: Option<DiagnosticId>,
+    pub suggestion: CodeSuggestion,
+}
+
+/// Whether suggestions of `applicability` should be applied when the user asked for
+/// suggestions at least as confident as `threshold`.
+pub fn is_applicable(applicability: Applicability, threshold: Applicability) -> bool {
+    match (applicability, threshold) {
+        (Applicability::MachineApplicable, _) => true,
+        (Applicability::MaybeIncorrect, Applicability::MaybeIncorrect) => true,
+        _ => false,
+    }
+}
+
+/// One replacement of the bytes `lo..hi` of a file by `snippet`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Replacement {
+    lo: usize,
+    hi: usize,
+    snippet: String,
+}
+
+impl Replacement {
+    /// Two replacements conflict if applying both would be ambiguous.
+    fn conflicts_with(&self, other: &Replacement) -> bool {
+        if self == other {
+            // Several diagnostics may suggest the very same edit.
+            return false;
+        }
+        if self.lo == self.hi && other.lo == other.hi {
+            // Two different insertions at the same position have no natural order.
+            return self.lo == other.lo;
+        }
+        // This also catches insertions strictly inside a replaced range.
+        self.lo < other.hi && other.lo < self.hi
+    }
+}
+
+/// The source of a file after applying the suggestions that point into it.
+pub struct FixedFile {
+    /// The path of the file on the local file system.
+    pub path: PathBuf,
+    /// The name the file is displayed with in diagnostics.
+    pub name: FileName,
+    /// The source of the file as the compiler saw it, with a BOM removed and
+    /// `\r\n` normalized to `\n`.
+    pub original: Lrc<String>,
+    pub fixed: String,
+    /// The number of suggestions applied to this file.
+    pub applied: usize,
+    /// The number of suggestions that were dropped because they overlapped with
+    /// suggestions applied before them.
+    pub skipped: usize,
+}
+
+impl FixedFile {
+    /// Rewrites the file on disk with the fixed source, keeping its byte order mark and
+    /// `\r\n` line endings.
+    pub fn write_in_place(&self) -> io::Result<()> {
+        let on_disk = fs::read_to_string(&self.path)?;
+        let has_bom = on_disk.starts_with('\u{feff}');
+        let body = if has_bom { &on_disk['\u{feff}'.len_utf8()..] } else { &on_disk[..] };
+        if body.replace("\r\n", "\n") != *self.original {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "the file was modified during compilation",
+            ));
+        }
+
+        // Only restore `\r\n` if the file used it consistently; we can't tell which of the
+        // lines of a file with mixed line endings the fixed lines correspond to.
+        let lines = body.matches('\n').count();
+        let crlf = lines > 0 && lines == body.matches("\r\n").count();
+        let mut fixed = String::with_capacity(on_disk.len());
+        if has_bom {
+            fixed.push('\u{feff}');
+        }
+        if crlf {
+            fixed.push_str(&self.fixed.replace('\n', "\r\n"));
+        } else {
+            fixed.push_str(&self.fixed);
+        }
+        fs::write(&self.path, fixed)
+    }
+
+    /// Returns the changes to this file in unified diff format, with `context` unchanged
+    /// lines around each hunk.
+    pub fn to_patch(&self, context: usize) -> String {
+        let old: Vec<&str> = self.original.split_terminator('\n').collect();
+        let new: Vec<&str> = self.fixed.split_terminator('\n').collect();
+
+        // Suggestions never touch much of a file, so instead of computing a minimal diff it
+        // is enough to resynchronize on the nearest line both sides have in common.
+        let mut hunks: Vec<(usize, usize, usize, usize)> = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < old.len() || j < new.len() {
+            if i < old.len() && j < new.len() && old[i] == new[j] {
+                i += 1;
+                j += 1;
+                continue;
+            }
+            // Find the next line both sides agree on again, looking for the shortest resync.
+            let (mut di, mut dj) = (old.len() - i, new.len() - j);
+            'resync: for k in 1..=(old.len() - i) + (new.len() - j) {
+                for a in 0..=k {
+                    let b = k - a;
+                    if i + a < old.len() && j + b < new.len() && old[i + a] == new[j + b] {
+                        di = a;
+                        dj = b;
+                        break 'resync;
+                    }
+                }
+            }
+            hunks.push((i, i + di, j, j + dj));
+            i += di;
+            j += dj;
+        }
+
+        let mut patch = String::new();
+        if hunks.is_empty() {
+            return patch;
+        }
+        let name = self.name.to_string();
+        let _ = writeln!(patch, "--- {}", name);
+        let _ = writeln!(patch, "+++ {}", name);
+
+        let mut idx = 0;
+        while idx < hunks.len() {
+            // Merge hunks whose context would overlap.
+            let mut end = idx;
+            while end + 1 < hunks.len() && hunks[end + 1].0 <= hunks[end].1 + 2 * context {
+                end += 1;
+            }
+            let (first, last) = (hunks[idx], hunks[end]);
+            let old_lo = first.0.saturating_sub(context);
+            let old_hi = (last.1 + context).min(old.len());
+            let new_lo = first.2 - (first.0 - old_lo);
+            let new_hi = last.3 + (old_hi - last.1);
+            let _ = writeln!(
+                patch,
+                "@@ -{},{} +{},{} @@",
+                old_lo + 1,
+                old_hi - old_lo,
+                new_lo + 1,
+                new_hi - new_lo
+            );
+
+            let mut line = old_lo;
+            for &(o_lo, o_hi, n_lo, n_hi) in &hunks[idx..=end] {
+                for l in &old[line..o_lo] {
+                    let _ = writeln!(patch, " {}", l);
+                }
+                for l in &old[o_lo..o_hi] {
+                    let _ = writeln!(patch, "-{}", l);
+                }
+                for l in &new[n_lo..n_hi] {
+                    let _ = writeln!(patch, "+{}", l);
+                }
+                line = o_hi;
+            }
+            for l in &old[line..old_hi] {
+                let _ = writeln!(patch, " {}", l);
+            }
+            idx = end + 1;
+        }
+        patch
+    }
+}
+
+/// Resolves the `suggestions` against the source files in `sm`, keeping only those emitted
+/// by one of `lints` if given.
+///
+/// Suggestions offering several alternatives, and suggestions pointing into macro expansions
+/// or files that weren't read from the local file system are ignored.
+pub fn fix_files(
+    sm: &SourceMap,
+    suggestions: &[CollectedSuggestion],
+    lints: Option<&[String]>,
+) -> Vec<FixedFile> {
+    let mut fixes_by_file: BTreeMap<_, (Lrc<SourceFile>, Vec<Vec<Replacement>>)> =
+        BTreeMap::new();
+
+    for collected in suggestions {
+        if let Some(lints) = lints {
+            match &collected.code {
+                Some(DiagnosticId::Lint(name))
+                    if lints.iter().any(|lint| lint.replace('-', "_") == *name) => {}
+                _ => continue,
+            }
+        }
+        let substitution = match &collected.suggestion.substitutions[..] {
+            [substitution] => substitution,
+            _ => continue,
+        };
+
+        let mut file: Option<Lrc<SourceFile>> = None;
+        let mut replacements = vec![];
+        let mut valid = !substitution.parts.is_empty();
+        for part in &substitution.parts {
+            if part.span.is_dummy() || part.span.from_expansion() {
+                valid = false;
+                break;
+            }
+            let lo = sm.lookup_byte_offset(part.span.lo());
+            let hi = sm.lookup_byte_offset(part.span.hi());
+            let same_file = file.as_ref().map_or(true, |sf| Lrc::ptr_eq(sf, &lo.sf));
+            if !Lrc::ptr_eq(&lo.sf, &hi.sf) || !same_file {
+                valid = false;
+                break;
+            }
+            replacements.push(Replacement {
+                lo: lo.pos.0 as usize,
+                hi: hi.pos.0 as usize,
+                snippet: part.snippet.clone(),
+            });
+            file = Some(lo.sf);
+        }
+        let file = match file {
+            Some(file) if valid => file,
+            _ => continue,
+        };
+        if file.is_imported() || file.src.is_none() {
+            continue;
+        }
+        replacements.sort();
+        replacements.dedup();
+        if replacements.windows(2).any(|w| w[0].conflicts_with(&w[1])) {
+            continue;
+        }
+        fixes_by_file
+            .entry(file.start_pos)
+            .or_insert_with(|| (file.clone(), vec![]))
+            .1
+            .push(replacements);
+    }
+
+    fixes_by_file
+        .into_iter()
+        .filter_map(|(_, (file, mut fixes))| {
+            fixes.sort();
+            fixes.dedup();
+
+            let mut accepted: Vec<Replacement> = vec![];
+            let (mut applied, mut skipped) = (0, 0);
+            for fix in fixes {
+                let conflicts =
+                    fix.iter().any(|r| accepted.iter().any(|other| r.conflicts_with(other)));
+                if conflicts {
+                    skipped += 1;
+                    continue;
+                }
+                applied += 1;
+                accepted.extend(fix);
+            }
+            accepted.sort();
+            accepted.dedup();
+
+            let original = file.src.clone()?;
+            let mut fixed = String::with_capacity(original.len());
+            let mut pos = 0;
+            for r in &accepted {
+                fixed.push_str(&original[pos..r.lo]);
+                fixed.push_str(&r.snippet);
+                pos = r.hi;
+            }
+            fixed.push_str(&original[pos..]);
+
+            let path = match &file.name {
+                FileName::Real(name) => name.local_path().to_path_buf(),
+                _ => return None,
+            };
+            Some(FixedFile { path, name: file.name.clone(), original, fixed, applied, skipped })
+        })
+        .collect()
+}
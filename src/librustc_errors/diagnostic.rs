@@ -107,7 +107,11 @@ impl Diagnostic {
         match self.level {
             Level::Bug | Level::Fatal | Level::Error | Level::FailureNote => true,
 
-            Level::Warning | Level::Note | Level::Help | Level::Cancelled => false,
+            Level::Warning
+            | Level::Note
+            | Level::Help
+            | Level::Cancelled
+            | Level::Expect(_) => false,
         }
     }
 
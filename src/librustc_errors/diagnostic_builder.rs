@@ -1,5 +1,5 @@
 use crate::{Applicability, Handler, Level, StashKey};
-use crate::{Diagnostic, DiagnosticId, DiagnosticStyledString};
+use crate::{Diagnostic, DiagnosticId, DiagnosticMessage, DiagnosticStyledString};
 
 use log::debug;
 use rustc_span::{MultiSpan, Span};
@@ -194,6 +194,13 @@ impl<'a> DiagnosticBuilder<'a> {
         self
     }
 
+    /// Like `span_label`, but with a label translated through the loaded message catalogue.
+    pub fn span_label_msg(&mut self, span: Span, msg: &DiagnosticMessage) -> &mut Self {
+        let label = self.0.handler.translate(msg);
+        self.0.diagnostic.span_label(span, label);
+        self
+    }
+
     /// Labels all the given spans with the provided label.
     /// See `span_label` for more information.
     pub fn span_labels(
@@ -233,6 +240,14 @@ impl<'a> DiagnosticBuilder<'a> {
     ) -> &mut Self);
 
     forward!(pub fn note(&mut self, msg: &str) -> &mut Self);
+
+    /// Like `note`, but with a message translated through the loaded message catalogue.
+    pub fn note_msg(&mut self, msg: &DiagnosticMessage) -> &mut Self {
+        let msg = self.0.handler.translate(msg);
+        self.0.diagnostic.note(&msg);
+        self
+    }
+
     forward!(pub fn span_note<S: Into<MultiSpan>>(
         &mut self,
         sp: S,
@@ -241,6 +256,14 @@ impl<'a> DiagnosticBuilder<'a> {
     forward!(pub fn warn(&mut self, msg: &str) -> &mut Self);
     forward!(pub fn span_warn<S: Into<MultiSpan>>(&mut self, sp: S, msg: &str) -> &mut Self);
     forward!(pub fn help(&mut self, msg: &str) -> &mut Self);
+
+    /// Like `help`, but with a message translated through the loaded message catalogue.
+    pub fn help_msg(&mut self, msg: &DiagnosticMessage) -> &mut Self {
+        let msg = self.0.handler.translate(msg);
+        self.0.diagnostic.help(&msg);
+        self
+    }
+
     forward!(pub fn span_help<S: Into<MultiSpan>>(
         &mut self,
         sp: S,
@@ -442,6 +465,20 @@ macro_rules! struct_span_err {
     })
 }
 
+/// Like `struct_span_err!`, but takes a `DiagnosticMessage` that is translated through
+/// the loaded message catalogue.
+#[macro_export]
+macro_rules! struct_span_err_msg {
+    ($session:expr, $span:expr, $code:ident, $message:expr) => ({
+        let session = &$session;
+        session.struct_span_err_with_code(
+            $span,
+            &session.translate(&$message),
+            $crate::error_code!($code),
+        )
+    })
+}
+
 #[macro_export]
 macro_rules! error_code {
     ($code:ident) => {{ $crate::DiagnosticId::Error(stringify!($code).to_owned()) }};
@@ -12,6 +12,7 @@ pub use emitter::ColorConfig;
 use log::debug;
 use Level::*;
 
+use apply_suggestions::CollectedSuggestion;
 use emitter::{is_case_difference, Emitter, EmitterWriter};
 use registry::Registry;
 use rustc_data_structures::fx::{FxHashSet, FxIndexMap};
@@ -29,6 +30,7 @@ use std::{error, fmt};
 use termcolor::{Color, ColorSpec};
 
 pub mod annotate_snippet_emitter_writer;
+pub mod apply_suggestions;
 mod diagnostic;
 mod diagnostic_builder;
 pub mod emitter;
@@ -37,7 +39,9 @@ mod lock;
 pub mod registry;
 mod snippet;
 mod styled_buffer;
+pub mod translation;
 pub use snippet::Style;
+pub use translation::{Catalogue, DiagnosticMessage};
 
 pub type PResult<'a, T> = Result<T, DiagnosticBuilder<'a>>;
 
@@ -282,6 +286,8 @@ pub use diagnostic_builder::DiagnosticBuilder;
 pub struct Handler {
     flags: HandlerFlags,
     inner: Lock<HandlerInner>,
+    /// The message catalogue selected with `-Z translation-lang`, if any.
+    catalogue: Option<Lrc<Catalogue>>,
 }
 
 /// This inner struct exists to keep it all behind a single lock;
@@ -319,6 +325,14 @@ struct HandlerInner {
 
     /// The warning count, used for a recap upon finishing
     deduplicated_warn_count: usize,
+
+    /// The spans of all `#[expect]` lint attributes whose lint has been
+    /// emitted (and suppressed) at least once.
+    fulfilled_expectations: FxHashSet<Span>,
+
+    /// Code suggestions kept to be applied to the source files once compilation
+    /// finishes, if `flags.apply_suggestions` is set.
+    collected_suggestions: Vec<CollectedSuggestion>,
 }
 
 /// A key denoting where from a diagnostic was stashed.
@@ -351,6 +365,10 @@ pub struct HandlerFlags {
     pub macro_backtrace: bool,
     /// If true, identical diagnostics are reported only once.
     pub deduplicate_diagnostics: bool,
+    /// If set, code suggestions at least this confident are kept so they can be
+    /// applied to the source files.
+    /// (rustc: see `-Z apply-suggestions`)
+    pub apply_suggestions: Option<Applicability>,
 }
 
 impl Drop for HandlerInner {
@@ -428,10 +446,29 @@ impl Handler {
                 emitted_diagnostic_codes: Default::default(),
                 emitted_diagnostics: Default::default(),
                 stashed_diagnostics: Default::default(),
+                fulfilled_expectations: Default::default(),
+                collected_suggestions: Vec::new(),
             }),
+            catalogue: None,
         }
     }
 
+    /// Translates `DiagnosticMessage`s through `catalogue` instead of using their
+    /// built-in English text.
+    pub fn with_catalogue(mut self, catalogue: Lrc<Catalogue>) -> Self {
+        self.catalogue = Some(catalogue);
+        self
+    }
+
+    pub fn catalogue(&self) -> Option<&Catalogue> {
+        self.catalogue.as_deref()
+    }
+
+    /// Formats `msg` in the language of the loaded message catalogue, falling back to English.
+    pub fn translate(&self, msg: &DiagnosticMessage) -> String {
+        translation::translate(self.catalogue(), msg)
+    }
+
     // This is here to not allow mutation of flags;
     // as of this writing it's only used in tests in librustc_middle.
     pub fn can_emit_warnings(&self) -> bool {
@@ -488,6 +525,14 @@ impl Handler {
         DiagnosticBuilder::new(self, Level::Cancelled, "")
     }
 
+    /// Construct a builder at the `Expect` level for the `#[expect]` attribute at `expectation`.
+    ///
+    /// Emitting the builder reports nothing to the user; it only marks the expectation as
+    /// fulfilled.
+    pub fn struct_expect(&self, msg: &str, expectation: Span) -> DiagnosticBuilder<'_> {
+        DiagnosticBuilder::new(self, Level::Expect(expectation), msg)
+    }
+
     /// Construct a builder at the `Warning` level at the given `span` and with the `msg`.
     pub fn struct_span_warn(&self, span: impl Into<MultiSpan>, msg: &str) -> DiagnosticBuilder<'_> {
         let mut result = self.struct_warn(msg);
@@ -711,6 +756,16 @@ impl Handler {
         self.inner.borrow_mut().emit_artifact_notification(path, artifact_type)
     }
 
+    /// Takes the set of `#[expect]` attribute spans whose expectation has been fulfilled.
+    pub fn steal_fulfilled_expectations(&self) -> FxHashSet<Span> {
+        std::mem::take(&mut self.inner.borrow_mut().fulfilled_expectations)
+    }
+
+    /// Takes the code suggestions kept for `-Z apply-suggestions`.
+    pub fn take_collected_suggestions(&self) -> Vec<CollectedSuggestion> {
+        std::mem::take(&mut self.inner.borrow_mut().collected_suggestions)
+    }
+
     pub fn delay_as_bug(&self, diagnostic: Diagnostic) {
         self.inner.borrow_mut().delay_as_bug(diagnostic)
     }
@@ -742,10 +797,28 @@ impl HandlerInner {
 
         (*TRACK_DIAGNOSTICS)(diagnostic);
 
+        // Expectations are tracked above so that they are replayed from the incremental
+        // cache, but are never shown to the user.
+        if let Expect(expectation) = diagnostic.level {
+            self.fulfilled_expectations.insert(expectation);
+            return;
+        }
+
         if let Some(ref code) = diagnostic.code {
             self.emitted_diagnostic_codes.insert(code.clone());
         }
 
+        if let Some(threshold) = self.flags.apply_suggestions {
+            for suggestion in &diagnostic.suggestions {
+                if apply_suggestions::is_applicable(suggestion.applicability, threshold) {
+                    self.collected_suggestions.push(CollectedSuggestion {
+                        code: diagnostic.code.clone(),
+                        suggestion: suggestion.clone(),
+                    });
+                }
+            }
+        }
+
         let already_emitted = |this: &mut Self| {
             use std::hash::Hash;
             let mut hasher = StableHasher::new();
@@ -954,6 +1027,8 @@ pub enum Level {
     Help,
     Cancelled,
     FailureNote,
+    /// A lint that was suppressed by the `#[expect]` attribute at the given span.
+    Expect(Span),
 }
 
 impl fmt::Display for Level {
@@ -979,7 +1054,7 @@ impl Level {
                 spec.set_fg(Some(Color::Cyan)).set_intense(true);
             }
             FailureNote => {}
-            Cancelled => unreachable!(),
+            Cancelled | Expect(_) => unreachable!(),
         }
         spec
     }
@@ -993,6 +1068,7 @@ impl Level {
             Help => "help",
             FailureNote => "failure-note",
             Cancelled => panic!("Shouldn't call on cancelled error"),
+            Expect(_) => "expect",
         }
     }
 
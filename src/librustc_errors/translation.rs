@@ -0,0 +1,311 @@
+//! Message catalogues for translating diagnostics (`-Z translation-lang`).
+//!
+//! A catalogue is a directory named after its language, holding any number of `.ftl` files
+//! with translated messages, and optionally an `error_codes` directory with translated error
+//! code explanations named after their code, e.g. `E0062.md`. Messages use a small subset of
+//! the Fluent syntax:
+//!
+//! ```text
+//! # Comments start with a `#`.
+//! typeck-field-multiply-specified = field `{$ident}` specified more than once
+//! long-message =
+//!     continuation lines are indented
+//!     and joined with a newline
+//! ```
+//!
+//! Each `DiagnosticMessage` carries the built-in English text of the message, which is used
+//! whenever no catalogue is loaded or the catalogue lacks the message.
+//!
+//! Only the diagnostics built from a `DiagnosticMessage` are translated, which so far are most of
+//! the errors reported while type checking expressions, in `rustc_typeck::check::expr`. The other
+//! diagnostics are always in English, while every error code explanation can be translated.
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_span::symbol::{Ident, Symbol};
+
+use std::borrow::Cow;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+/// The value of a named argument of a `DiagnosticMessage`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DiagnosticArgValue {
+    Str(Cow<'static, str>),
+    Number(i128),
+}
+
+impl fmt::Display for DiagnosticArgValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiagnosticArgValue::Str(s) => s.fmt(f),
+            DiagnosticArgValue::Number(n) => n.fmt(f),
+        }
+    }
+}
+
+/// Converts a value into an argument of a `DiagnosticMessage`.
+pub trait IntoDiagnosticArg {
+    fn into_diagnostic_arg(self) -> DiagnosticArgValue;
+}
+
+impl IntoDiagnosticArg for String {
+    fn into_diagnostic_arg(self) -> DiagnosticArgValue {
+        DiagnosticArgValue::Str(Cow::Owned(self))
+    }
+}
+
+impl IntoDiagnosticArg for &str {
+    fn into_diagnostic_arg(self) -> DiagnosticArgValue {
+        DiagnosticArgValue::Str(Cow::Owned(self.to_string()))
+    }
+}
+
+impl IntoDiagnosticArg for Symbol {
+    fn into_diagnostic_arg(self) -> DiagnosticArgValue {
+        self.to_string().into_diagnostic_arg()
+    }
+}
+
+impl IntoDiagnosticArg for Ident {
+    fn into_diagnostic_arg(self) -> DiagnosticArgValue {
+        self.to_string().into_diagnostic_arg()
+    }
+}
+
+macro_rules! into_diagnostic_arg_for_number {
+    ($($ty:ty),*) => {
+        $(
+            impl IntoDiagnosticArg for $ty {
+                fn into_diagnostic_arg(self) -> DiagnosticArgValue {
+                    DiagnosticArgValue::Number(self as i128)
+                }
+            }
+        )*
+    }
+}
+
+into_diagnostic_arg_for_number!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// A translatable message, identified by its key in the message catalogues.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiagnosticMessage {
+    pub id: &'static str,
+    /// The English text of the message, used when no translation is available.
+    pub fallback: &'static str,
+    pub args: Vec<(&'static str, DiagnosticArgValue)>,
+}
+
+impl DiagnosticMessage {
+    pub fn new(id: &'static str, fallback: &'static str) -> DiagnosticMessage {
+        DiagnosticMessage { id, fallback, args: vec![] }
+    }
+
+    /// Adds the argument `name`, which the message refers to as `{$name}`.
+    pub fn arg(mut self, name: &'static str, value: impl IntoDiagnosticArg) -> DiagnosticMessage {
+        self.args.push((name, value.into_diagnostic_arg()));
+        self
+    }
+}
+
+/// The translated messages and error code explanations of one language.
+#[derive(Debug, Default)]
+pub struct Catalogue {
+    lang: String,
+    messages: FxHashMap<String, String>,
+    explanations: FxHashMap<String, String>,
+    /// The `.ftl` files that the messages were read from.
+    message_files: Vec<PathBuf>,
+}
+
+/// Hashes the contents of the catalogue in a stable order, for the dep-tracking hash of the
+/// options: translated diagnostics may be replayed from the incremental cache.
+impl Hash for Catalogue {
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        self.lang.hash(hasher);
+        let mut messages: Vec<_> = self.messages.iter().collect();
+        messages.sort();
+        messages.hash(hasher);
+        let mut explanations: Vec<_> = self.explanations.iter().collect();
+        explanations.sort();
+        explanations.hash(hasher);
+    }
+}
+
+impl Catalogue {
+    /// Loads the catalogue for `lang` from `dir`. If there is no catalogue for a regional
+    /// variant like `pt-BR`, the catalogue of the base language (`pt`) is used instead.
+    pub fn load(dir: &Path, lang: &str) -> Result<Catalogue, String> {
+        let base = lang.split('-').next().unwrap_or(lang);
+        let lang_dir = [lang, base]
+            .iter()
+            .map(|lang| dir.join(lang))
+            .find(|dir| dir.is_dir())
+            .ok_or_else(|| {
+                format!("no message catalogue for `{}` in `{}`", lang, dir.display())
+            })?;
+
+        let mut catalogue = Catalogue { lang: lang.to_string(), ..Default::default() };
+        for path in read_dir(&lang_dir)? {
+            if path.extension().map_or(false, |ext| ext == "ftl") {
+                let src = read_file(&path)?;
+                parse_messages(&src, &mut catalogue.messages)
+                    .map_err(|(line, msg)| format!("{}:{}: {}", path.display(), line, msg))?;
+                catalogue.message_files.push(path);
+            }
+        }
+
+        let error_codes = lang_dir.join("error_codes");
+        if error_codes.is_dir() {
+            for path in read_dir(&error_codes)? {
+                if path.extension().map_or(false, |ext| ext == "md") {
+                    if let Some(code) = path.file_stem().and_then(|stem| stem.to_str()) {
+                        catalogue.explanations.insert(code.to_string(), read_file(&path)?);
+                    }
+                }
+            }
+        }
+
+        Ok(catalogue)
+    }
+
+    pub fn lang(&self) -> &str {
+        &self.lang
+    }
+
+    /// Returns the `.ftl` files that the messages were read from, which the translated
+    /// diagnostics depend on.
+    pub fn message_files(&self) -> &[PathBuf] {
+        &self.message_files
+    }
+
+    /// Returns the translated explanation of the error `code`, like `E0062`.
+    pub fn explanation(&self, code: &str) -> Option<&str> {
+        self.explanations.get(code).map(|s| &s[..])
+    }
+}
+
+/// Returns the paths in `dir`, sorted so that messages defined in several files are
+/// overridden in a stable order.
+fn read_dir(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .and_then(|entries| entries.map(|entry| entry.map(|entry| entry.path())).collect())
+        .map_err(|e| format!("failed to read `{}`: {}", dir.display(), e))?;
+    paths.sort();
+    Ok(paths)
+}
+
+fn read_file(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("failed to read `{}`: {}", path.display(), e))
+}
+
+/// Parses the messages of an `.ftl` file into `messages`, returning the line number
+/// and a description of the first syntax error. Messages of `messages` that were read from
+/// other files are overridden, but a file may only define each message once.
+fn parse_messages(
+    src: &str,
+    messages: &mut FxHashMap<String, String>,
+) -> Result<(), (usize, &'static str)> {
+    let mut parsed: Vec<(usize, &str, String)> = Vec::new();
+    let mut in_message = false;
+    for (i, line) in src.lines().enumerate() {
+        if line.starts_with(|c: char| c == ' ' || c == '\t') && !line.trim().is_empty() {
+            match parsed.last_mut() {
+                Some((_, _, text)) if in_message => {
+                    if !text.is_empty() {
+                        text.push('\n');
+                    }
+                    text.push_str(line.trim());
+                }
+                _ => return Err((i + 1, "continuation line without a message")),
+            }
+            continue;
+        }
+
+        in_message = false;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let eq = line.find('=').ok_or((i + 1, "expected `identifier = message`"))?;
+        let id = line[..eq].trim();
+        let valid_id = id.starts_with(|c: char| c.is_ascii_alphabetic())
+            && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid_id {
+            return Err((i + 1, "invalid message identifier"));
+        }
+        if parsed.iter().any(|&(_, other, _)| other == id) {
+            return Err((i + 1, "message identifier defined twice"));
+        }
+        parsed.push((i + 1, id, line[eq + 1..].trim().to_string()));
+        in_message = true;
+    }
+
+    for (line, _, text) in &parsed {
+        let mut rest = &text[..];
+        while let Some(open) = rest.find('{') {
+            rest = &rest[open..];
+            match rest.find('}') {
+                Some(close) => rest = &rest[close + 1..],
+                None => return Err((*line, "unterminated placeable")),
+            }
+        }
+    }
+    messages.extend(parsed.into_iter().map(|(_, id, text)| (id.to_string(), text)));
+    Ok(())
+}
+
+/// Formats `msg` in the language of `catalogue`, or in English if there is none.
+pub fn translate(catalogue: Option<&Catalogue>, msg: &DiagnosticMessage) -> String {
+    let pattern = catalogue
+        .and_then(|catalogue| catalogue.messages.get(msg.id))
+        .map_or(msg.fallback, |s| &s[..]);
+
+    let mut out = String::with_capacity(pattern.len());
+    let mut rest = pattern;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        rest = &rest[open..];
+        let close = match rest.find('}') {
+            Some(close) => close,
+            None => break,
+        };
+        let placeholder = rest[1..close].trim();
+        let arg = if placeholder.starts_with('$') {
+            msg.args.iter().find(|(name, _)| *name == &placeholder[1..])
+        } else {
+            None
+        };
+        match arg {
+            Some((_, value)) => out.push_str(&value.to_string()),
+            // Leave anything we don't understand as it is, so that mistakes in a
+            // catalogue are visible rather than silently dropping text.
+            None => out.push_str(&rest[..=close]),
+        }
+        rest = &rest[close + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Returns the language of the current locale, as set by the `LC_ALL`, `LC_MESSAGES` or
+/// `LANG` environment variables, in the form used for catalogue names (e.g. `pt-BR`).
+pub fn locale_from_env() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|value| {
+            // Strip the encoding and modifier, as in `pt_BR.UTF-8@latin`.
+            let lang = value.split(|c| c == '.' || c == '@').next().unwrap_or("");
+            match lang {
+                "" | "C" | "POSIX" => None,
+                lang => Some(lang.replace('_', "-")),
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests;
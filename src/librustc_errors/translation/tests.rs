@@ -0,0 +1,104 @@
+use super::*;
+
+use std::collections::hash_map::DefaultHasher;
+
+fn parse(src: &str) -> Result<FxHashMap<String, String>, (usize, &'static str)> {
+    let mut messages = FxHashMap::default();
+    parse_messages(src, &mut messages).map(|()| messages)
+}
+
+fn catalogue(src: &str) -> Catalogue {
+    Catalogue { lang: "fr".to_string(), messages: parse(src).unwrap(), ..Default::default() }
+}
+
+fn hash(catalogue: &Catalogue) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    catalogue.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn parse_messages_and_comments() {
+    let messages = parse(
+        "# a comment\n\
+         simple = a message\n\
+         \n\
+         long =\n    first line\n    second {$arg}\n\
+         empty-text =\n",
+    )
+    .unwrap();
+    assert_eq!(messages.len(), 3);
+    assert_eq!(messages["simple"], "a message");
+    assert_eq!(messages["long"], "first line\nsecond {$arg}");
+    assert_eq!(messages["empty-text"], "");
+}
+
+#[test]
+fn parse_missing_equals() {
+    assert_eq!(parse("ok = fine\nmissing-equals\n"), Err((2, "expected `identifier = message`")));
+}
+
+#[test]
+fn parse_invalid_identifier() {
+    assert_eq!(parse("1st = message\n"), Err((1, "invalid message identifier")));
+    assert_eq!(parse(" = message\n"), Err((1, "continuation line without a message")));
+    assert_eq!(parse("= message\n"), Err((1, "invalid message identifier")));
+}
+
+#[test]
+fn parse_continuation_without_message() {
+    assert_eq!(parse("    orphan line\n"), Err((1, "continuation line without a message")));
+    assert_eq!(
+        parse("msg = text\n# comment\n    orphan line\n"),
+        Err((3, "continuation line without a message"))
+    );
+}
+
+#[test]
+fn parse_duplicate_identifier() {
+    assert_eq!(
+        parse("msg = first\nother = text\nmsg = second\n"),
+        Err((3, "message identifier defined twice"))
+    );
+}
+
+#[test]
+fn parse_unterminated_placeable() {
+    assert_eq!(parse("msg = field `{$ident` is wrong\n"), Err((1, "unterminated placeable")));
+    assert_eq!(
+        parse("ok = {$a} and {$b}\nmsg =\n    first {$a}\n    second {$b\n"),
+        Err((2, "unterminated placeable"))
+    );
+}
+
+#[test]
+fn parse_error_keeps_messages() {
+    let mut messages = FxHashMap::default();
+    messages.insert("msg".to_string(), "from another file".to_string());
+    assert!(parse_messages("msg = overridden\nbroken\n", &mut messages).is_err());
+    assert_eq!(messages["msg"], "from another file");
+    assert!(parse_messages("msg = overridden\n", &mut messages).is_ok());
+    assert_eq!(messages["msg"], "overridden");
+}
+
+#[test]
+fn translate_with_args() {
+    let catalogue = catalogue("msg = champ `{$ident}` répété {$count} fois, {$unknown}\n");
+    let msg = DiagnosticMessage::new("msg", "field `{$ident}` repeated {$count} times")
+        .arg("ident", "x")
+        .arg("count", 2u32);
+    assert_eq!(translate(None, &msg), "field `x` repeated 2 times");
+    assert_eq!(translate(Some(&catalogue), &msg), "champ `x` répété 2 fois, {$unknown}");
+
+    let missing = DiagnosticMessage::new("missing", "not translated");
+    assert_eq!(translate(Some(&catalogue), &missing), "not translated");
+}
+
+#[test]
+fn hash_catalogue_contents() {
+    let a = catalogue("one = 1\ntwo = 2\nthree = 3\n");
+    let b = catalogue("three = 3\none = 1\ntwo = 2\n");
+    let c = catalogue("one = 1\ntwo = 2\nthree = trois\n");
+    assert_eq!(hash(&a), hash(&b));
+    assert_ne!(hash(&a), hash(&c));
+}
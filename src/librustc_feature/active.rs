@@ -577,6 +577,21 @@ declare_features! (
     /// Be more precise when looking for live drops in a const context.
     (active, const_precise_live_drops, "1.46.0", Some(73255), None),
 
+    /// Allows `#[must_not_suspend]` on types, warning when they are held across await points.
+    (active, must_not_suspend, "1.46.0", None, None),
+
+    /// Allows `#[no_panic]` on functions, checking that they can never panic.
+    (active, no_panic, "1.46.0", None, None),
+
+    /// Allows `#[stack_limit = N]` on functions, bounding their worst-case stack usage.
+    (active, stack_limit, "1.46.0", None, None),
+
+    /// Allows `#![move_size_limit]`, which enables the `large_assignments` lint.
+    (active, large_assignments, "1.46.0", None, None),
+
+    /// Allows specifying modifiers in the link attribute: `#[link(modifiers = "...")]`
+    (active, native_link_modifiers, "1.46.0", None, None),
+
     // -------------------------------------------------------------------------
     // feature-group-end: actual feature gates
     // -------------------------------------------------------------------------
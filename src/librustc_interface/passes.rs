@@ -33,7 +33,7 @@ use rustc_session::output::{filename_for_input, filename_for_metadata};
 use rustc_session::search_paths::PathKind;
 use rustc_session::Session;
 use rustc_span::symbol::Symbol;
-use rustc_span::{FileName, RealFileName};
+use rustc_span::{FileName, RealFileName, Span};
 use rustc_trait_selection::traits;
 use rustc_typeck as typeck;
 
@@ -430,7 +430,15 @@ fn configure_and_expand_inner<'a>(
     // Add all buffered lints from the `ParseSess` to the `Session`.
     sess.parse_sess.buffered_lints.with_lock(|buffered_lints| {
         info!("{} parse sess buffered_lints", buffered_lints.len());
-        for early_lint in buffered_lints.drain(..) {
+        for mut early_lint in buffered_lints.drain(..) {
+            // Lints buffered before node ids were assigned, e.g. by the lexer, are attached to
+            // the innermost node containing them.
+            if early_lint.node_id == ast::DUMMY_NODE_ID {
+                early_lint.node_id = match early_lint.span.primary_span() {
+                    Some(span) => InnermostNodeFinder::find(&krate, span),
+                    None => ast::CRATE_NODE_ID,
+                };
+            }
             resolver.lint_buffer().add_early_lint(early_lint);
         }
     });
@@ -438,6 +446,79 @@ fn configure_and_expand_inner<'a>(
     Ok((krate, resolver))
 }
 
+/// Finds the innermost node that contains a span and can have lint attributes, so that lints
+/// buffered at that span honour the lint levels of the node and of its parents.
+struct InnermostNodeFinder {
+    span: Span,
+    node_id: ast::NodeId,
+}
+
+impl InnermostNodeFinder {
+    fn find(krate: &ast::Crate, span: Span) -> ast::NodeId {
+        let mut finder = InnermostNodeFinder { span, node_id: ast::CRATE_NODE_ID };
+        visit::walk_crate(&mut finder, krate);
+        finder.node_id
+    }
+
+    /// Records the node if it contains the span, and returns whether to look inside it. Outer
+    /// attributes, like doc comments, aren't part of the span of the node they belong to.
+    fn enter(&mut self, id: ast::NodeId, span: Span, attrs: &[ast::Attribute]) -> bool {
+        let contains = |outer: Span| !outer.from_expansion() && outer.contains(self.span);
+        if contains(span) || attrs.iter().any(|attr| contains(attr.span)) {
+            self.node_id = id;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl<'a> visit::Visitor<'a> for InnermostNodeFinder {
+    fn visit_item(&mut self, item: &'a ast::Item) {
+        if self.enter(item.id, item.span, &item.attrs) {
+            visit::walk_item(self, item);
+        }
+    }
+
+    fn visit_foreign_item(&mut self, item: &'a ast::ForeignItem) {
+        if self.enter(item.id, item.span, &item.attrs) {
+            visit::walk_foreign_item(self, item);
+        }
+    }
+
+    fn visit_assoc_item(&mut self, item: &'a ast::AssocItem, ctxt: visit::AssocCtxt) {
+        if self.enter(item.id, item.span, &item.attrs) {
+            visit::walk_assoc_item(self, item, ctxt);
+        }
+    }
+
+    fn visit_variant(&mut self, variant: &'a ast::Variant) {
+        if self.enter(variant.id, variant.span, &variant.attrs) {
+            visit::walk_variant(self, variant);
+        }
+    }
+
+    fn visit_struct_field(&mut self, field: &'a ast::StructField) {
+        if self.enter(field.id, field.span, &field.attrs) {
+            visit::walk_struct_field(self, field);
+        }
+    }
+
+    fn visit_local(&mut self, local: &'a ast::Local) {
+        if self.enter(local.id, local.span, &local.attrs) {
+            visit::walk_local(self, local);
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'a ast::Expr) {
+        if self.enter(expr.id, expr.span, &expr.attrs) {
+            visit::walk_expr(self, expr);
+        }
+    }
+
+    fn visit_mac(&mut self, _mac: &'a ast::MacCall) {}
+}
+
 pub fn lower_to_hir<'res, 'tcx>(
     sess: &'tcx Session,
     lint_store: &LintStore,
@@ -573,6 +654,30 @@ fn write_out_deps(
             .map(|fmap| escape_dep_filename(&fmap.unmapped_path.as_ref().unwrap_or(&fmap.name)))
             .collect();
 
+        // The sample profile affects the generated code like a source file does.
+        if let Some(ref path) = sess.opts.cg.profile_sample_use {
+            files.push(escape_dep_filename(&FileName::Real(RealFileName::Named(path.clone()))));
+        }
+
+        // Only the lint levels read from the lint config file are tracked, so the file has to be
+        // a dependency for changes to it to be picked up.
+        if let Some(ref path) = sess.opts.debugging_opts.lint_config {
+            files.push(escape_dep_filename(&FileName::Real(RealFileName::Named(path.clone()))));
+        }
+
+        // The export list decides which symbols the linked library exports, and with which
+        // versions.
+        if let Some(ref path) = sess.opts.debugging_opts.export_symbols {
+            files.push(escape_dep_filename(&FileName::Real(RealFileName::Named(path.clone()))));
+        }
+
+        // Diagnostics are translated with the messages of the catalogue.
+        if let Some(ref catalogue) = sess.opts.translation_catalogue {
+            for path in catalogue.message_files() {
+                files.push(escape_dep_filename(&FileName::Real(RealFileName::Named(path.clone()))));
+            }
+        }
+
         if sess.binary_dep_depinfo() {
             boxed_resolver.borrow().borrow_mut().access(|resolver| {
                 for cnum in resolver.cstore().crates_untracked() {
@@ -991,6 +1096,10 @@ pub fn start_codegen<'tcx>(
         codegen_backend.codegen_crate(tcx, metadata, need_metadata_module)
     });
 
+    // MIR lints are emitted while collecting mono items during codegen, so `#[expect]`
+    // attributes can only be checked once that is done.
+    tcx.sess.time("check_lint_expectations", || rustc_lint::check_expectations(tcx));
+
     if log_enabled!(::log::Level::Info) {
         println!("Post-codegen");
         tcx.print_debug_stats();
@@ -1,23 +1,23 @@
 use crate::interface::parse_cfgspecs;
 
 use rustc_data_structures::fx::FxHashSet;
-use rustc_errors::{emitter::HumanReadableErrorType, registry, ColorConfig};
-use rustc_session::config::Strip;
+use rustc_errors::{emitter::HumanReadableErrorType, registry, Applicability, ColorConfig};
+use rustc_session::config::{DebugInfoCompression, SplitDebuginfo, Strip};
 use rustc_session::config::{build_configuration, build_session_options, to_crate_config};
 use rustc_session::config::{rustc_optgroups, ErrorOutputType, ExternLocation, Options, Passes};
 use rustc_session::config::{CFGuard, ExternEntry, LinkerPluginLto, LtoCli, SwitchWithOptPath};
 use rustc_session::config::{
-    Externs, OutputType, OutputTypes, SanitizerSet, SymbolManglingVersion,
+    Externs, LintConfig, OutputType, OutputTypes, SanitizerSet, SymbolManglingVersion,
 };
 use rustc_session::lint::Level;
 use rustc_session::search_paths::SearchPath;
-use rustc_session::utils::NativeLibKind;
+use rustc_session::utils::{NativeLibKind, NativeLibModifiers};
 use rustc_session::{build_session, getopts, DiagnosticOutput, Session};
 use rustc_span::edition::{Edition, DEFAULT_EDITION};
 use rustc_span::symbol::sym;
 use rustc_span::SourceFileHashAlgorithm;
 use rustc_target::spec::{CodeModel, LinkerFlavor, MergeFunctions, PanicStrategy};
-use rustc_target::spec::{RelocModel, RelroLevel, TlsModel};
+use rustc_target::spec::{RelocModel, RelroLevel, StackProtector, TlsModel};
 use std::collections::{BTreeMap, BTreeSet};
 use std::iter::FromIterator;
 use std::path::PathBuf;
@@ -225,6 +225,41 @@ fn test_lints_tracking_hash_different_values() {
     assert_eq!(v3.dep_tracking_hash(), v3.clone().dep_tracking_hash());
 }
 
+#[test]
+fn test_lint_config_tracking_hash_order() {
+    let mut v1 = Options::default();
+    let mut v2 = Options::default();
+
+    // Unlike for `-A`/`-W`/`-D`, the order of the entries of the lint config file matters.
+    v1.lint_config = LintConfig(vec![
+        (String::from("unused"), Level::Deny),
+        (String::from("unused_imports"), Level::Allow),
+    ]);
+    v2.lint_config = LintConfig(vec![
+        (String::from("unused_imports"), Level::Allow),
+        (String::from("unused"), Level::Deny),
+    ]);
+
+    assert!(v1.dep_tracking_hash() != v2.dep_tracking_hash());
+    assert_eq!(v1.dep_tracking_hash(), v1.clone().dep_tracking_hash());
+}
+
+#[test]
+fn test_translation_lang_tracking_hash() {
+    let mut v1 = Options::default();
+    let mut v2 = Options::default();
+
+    // `-Z translation-lang=system` is tracked through the language it resolves to.
+    v1.debugging_opts.translation_lang = Some(String::from("system"));
+    v2.debugging_opts.translation_lang = Some(String::from("system"));
+    v1.translation_lang = Some(String::from("fr-FR"));
+    v2.translation_lang = Some(String::from("de-DE"));
+
+    assert!(v1.dep_tracking_hash() != v2.dep_tracking_hash());
+    v2.translation_lang = Some(String::from("fr-FR"));
+    assert_eq!(v1.dep_tracking_hash(), v2.dep_tracking_hash());
+}
+
 #[test]
 fn test_lints_tracking_hash_different_construction_order() {
     let mut v1 = Options::default();
@@ -305,44 +340,60 @@ fn test_native_libs_tracking_hash_different_values() {
     let mut v2 = Options::default();
     let mut v3 = Options::default();
     let mut v4 = Options::default();
+    let mut v5 = Options::default();
 
     // Reference
     v1.libs = vec![
-        (String::from("a"), None, NativeLibKind::StaticBundle),
-        (String::from("b"), None, NativeLibKind::Framework),
-        (String::from("c"), None, NativeLibKind::Unspecified),
+        (String::from("a"), None, NativeLibKind::StaticBundle, NativeLibModifiers::default()),
+        (String::from("b"), None, NativeLibKind::Framework, NativeLibModifiers::default()),
+        (String::from("c"), None, NativeLibKind::Unspecified, NativeLibModifiers::default()),
     ];
 
     // Change label
     v2.libs = vec![
-        (String::from("a"), None, NativeLibKind::StaticBundle),
-        (String::from("X"), None, NativeLibKind::Framework),
-        (String::from("c"), None, NativeLibKind::Unspecified),
+        (String::from("a"), None, NativeLibKind::StaticBundle, NativeLibModifiers::default()),
+        (String::from("X"), None, NativeLibKind::Framework, NativeLibModifiers::default()),
+        (String::from("c"), None, NativeLibKind::Unspecified, NativeLibModifiers::default()),
     ];
 
     // Change kind
     v3.libs = vec![
-        (String::from("a"), None, NativeLibKind::StaticBundle),
-        (String::from("b"), None, NativeLibKind::StaticBundle),
-        (String::from("c"), None, NativeLibKind::Unspecified),
+        (String::from("a"), None, NativeLibKind::StaticBundle, NativeLibModifiers::default()),
+        (String::from("b"), None, NativeLibKind::StaticBundle, NativeLibModifiers::default()),
+        (String::from("c"), None, NativeLibKind::Unspecified, NativeLibModifiers::default()),
     ];
 
     // Change new-name
     v4.libs = vec![
-        (String::from("a"), None, NativeLibKind::StaticBundle),
-        (String::from("b"), Some(String::from("X")), NativeLibKind::Framework),
-        (String::from("c"), None, NativeLibKind::Unspecified),
+        (String::from("a"), None, NativeLibKind::StaticBundle, NativeLibModifiers::default()),
+        (
+            String::from("b"),
+            Some(String::from("X")),
+            NativeLibKind::Framework,
+            NativeLibModifiers::default(),
+        ),
+        (String::from("c"), None, NativeLibKind::Unspecified, NativeLibModifiers::default()),
+    ];
+
+    // Change modifiers
+    let verbatim = NativeLibModifiers { verbatim: true, ..Default::default() };
+    v5.libs = vec![
+        (String::from("a"), None, NativeLibKind::StaticBundle, NativeLibModifiers::default()),
+        (String::from("b"), None, NativeLibKind::Framework, verbatim),
+        (String::from("c"), None, NativeLibKind::Unspecified, NativeLibModifiers::default()),
     ];
 
     assert!(v1.dep_tracking_hash() != v2.dep_tracking_hash());
     assert!(v1.dep_tracking_hash() != v3.dep_tracking_hash());
     assert!(v1.dep_tracking_hash() != v4.dep_tracking_hash());
+    assert!(v1.dep_tracking_hash() != v5.dep_tracking_hash());
 
     // Check clone
     assert_eq!(v1.dep_tracking_hash(), v1.clone().dep_tracking_hash());
     assert_eq!(v2.dep_tracking_hash(), v2.clone().dep_tracking_hash());
     assert_eq!(v3.dep_tracking_hash(), v3.clone().dep_tracking_hash());
     assert_eq!(v4.dep_tracking_hash(), v4.clone().dep_tracking_hash());
+    assert_eq!(v5.dep_tracking_hash(), v5.clone().dep_tracking_hash());
 }
 
 #[test]
@@ -353,21 +404,21 @@ fn test_native_libs_tracking_hash_different_order() {
 
     // Reference
     v1.libs = vec![
-        (String::from("a"), None, NativeLibKind::StaticBundle),
-        (String::from("b"), None, NativeLibKind::Framework),
-        (String::from("c"), None, NativeLibKind::Unspecified),
+        (String::from("a"), None, NativeLibKind::StaticBundle, NativeLibModifiers::default()),
+        (String::from("b"), None, NativeLibKind::Framework, NativeLibModifiers::default()),
+        (String::from("c"), None, NativeLibKind::Unspecified, NativeLibModifiers::default()),
     ];
 
     v2.libs = vec![
-        (String::from("b"), None, NativeLibKind::Framework),
-        (String::from("a"), None, NativeLibKind::StaticBundle),
-        (String::from("c"), None, NativeLibKind::Unspecified),
+        (String::from("b"), None, NativeLibKind::Framework, NativeLibModifiers::default()),
+        (String::from("a"), None, NativeLibKind::StaticBundle, NativeLibModifiers::default()),
+        (String::from("c"), None, NativeLibKind::Unspecified, NativeLibModifiers::default()),
     ];
 
     v3.libs = vec![
-        (String::from("c"), None, NativeLibKind::Unspecified),
-        (String::from("a"), None, NativeLibKind::StaticBundle),
-        (String::from("b"), None, NativeLibKind::Framework),
+        (String::from("c"), None, NativeLibKind::Unspecified, NativeLibModifiers::default()),
+        (String::from("a"), None, NativeLibKind::StaticBundle, NativeLibModifiers::default()),
+        (String::from("b"), None, NativeLibKind::Framework, NativeLibModifiers::default()),
     ];
 
     assert!(v1.dep_tracking_hash() == v2.dep_tracking_hash());
@@ -402,6 +453,7 @@ fn test_codegen_options_tracking_hash() {
     // `link_arg` is omitted because it just forwards to `link_args`.
     untracked!(link_args, vec![String::from("abc"), String::from("def")]);
     untracked!(link_dead_code, true);
+    untracked!(link_self_contained, Some(true));
     untracked!(linker, Some(PathBuf::from("linker")));
     untracked!(linker_flavor, Some(LinkerFlavor::Gcc));
     untracked!(no_stack_check, true);
@@ -440,9 +492,11 @@ fn test_codegen_options_tracking_hash() {
     tracked!(passes, vec![String::from("1"), String::from("2")]);
     tracked!(prefer_dynamic, true);
     tracked!(profile_generate, SwitchWithOptPath::Enabled(None));
+    tracked!(profile_sample_use, Some(PathBuf::from("abc")));
     tracked!(profile_use, Some(PathBuf::from("abc")));
     tracked!(relocation_model, Some(RelocModel::Pic));
     tracked!(soft_float, true);
+    tracked!(split_debuginfo, SplitDebuginfo::Packed);
     tracked!(target_cpu, Some(String::from("abc")));
     tracked!(target_feature, String::from("all the features, all of them"));
 }
@@ -461,6 +515,9 @@ fn test_debugging_options_tracking_hash() {
 
     // Make sure that changing an [UNTRACKED] option leaves the hash unchanged.
     // This list is in alphabetical order.
+    untracked!(apply_suggestions, Some(Applicability::MaybeIncorrect));
+    untracked!(apply_suggestions_lints, Some(vec![String::from("unused_imports")]));
+    untracked!(apply_suggestions_output, Some(PathBuf::from("fixes.patch")));
     untracked!(ast_json, true);
     untracked!(ast_json_noexpand, true);
     untracked!(borrowck, String::from("other"));
@@ -475,6 +532,7 @@ fn test_debugging_options_tracking_hash() {
     untracked!(dump_mir_exclude_pass_number, true);
     untracked!(dump_mir_graphviz, true);
     untracked!(emit_stack_sizes, true);
+    untracked!(export_symbols, Some(PathBuf::from("abc")));
     untracked!(hir_stats, true);
     untracked!(identify_regions, true);
     untracked!(incremental_ignore_spans, true);
@@ -483,6 +541,7 @@ fn test_debugging_options_tracking_hash() {
     untracked!(input_stats, true);
     untracked!(keep_hygiene_data, true);
     untracked!(link_native_libraries, false);
+    untracked!(lint_config, Some(PathBuf::from("abc")));
     untracked!(llvm_time_trace, true);
     untracked!(ls, true);
     untracked!(macro_backtrace, true);
@@ -516,11 +575,14 @@ fn test_debugging_options_tracking_hash() {
     untracked!(time_llvm_passes, true);
     untracked!(time_passes, true);
     untracked!(trace_macros, true);
+    untracked!(translation_dir, Some(PathBuf::from("locale")));
+    untracked!(translation_lang, Some(String::from("system")));
     untracked!(ui_testing, true);
     untracked!(unpretty, Some("expanded".to_string()));
     untracked!(unstable_options, true);
     untracked!(validate_mir, true);
     untracked!(verbose, true);
+    untracked!(verify_determinism, true);
 
     macro_rules! tracked {
         ($name: ident, $non_default_value: expr) => {
@@ -541,6 +603,7 @@ fn test_debugging_options_tracking_hash() {
     tracked!(control_flow_guard, CFGuard::Checks);
     tracked!(crate_attr, vec!["abc".to_string()]);
     tracked!(debug_macros, true);
+    tracked!(debuginfo_compression, DebugInfoCompression::Zlib);
     tracked!(dep_info_omit_d_target, true);
     tracked!(dual_proc_macros, true);
     tracked!(fewer_names, true);
@@ -556,6 +619,7 @@ fn test_debugging_options_tracking_hash() {
     tracked!(merge_functions, Some(MergeFunctions::Disabled));
     tracked!(mir_emit_retag, true);
     tracked!(mir_opt_level, 3);
+    tracked!(move_size_limit, Some(4096));
     tracked!(mutable_noalias, true);
     tracked!(new_llvm_pass_manager, true);
     tracked!(no_codegen, true);
@@ -566,10 +630,13 @@ fn test_debugging_options_tracking_hash() {
     tracked!(panic_abort_tests, true);
     tracked!(plt, Some(true));
     tracked!(print_fuel, Some("abc".to_string()));
+    tracked!(print_stack_usage, true);
     tracked!(profile, true);
     tracked!(profile_emit, Some(PathBuf::from("abc")));
+    tracked!(record_item_uses, true);
     tracked!(relro_level, Some(RelroLevel::Full));
     tracked!(report_delayed_bugs, true);
+    tracked!(report_unused_pub_items, Some(vec![String::from("abc")]));
     tracked!(run_dsymutil, false);
     tracked!(sanitizer, SanitizerSet::ADDRESS);
     tracked!(sanitizer_memory_track_origins, 2);
@@ -578,6 +645,7 @@ fn test_debugging_options_tracking_hash() {
     tracked!(share_generics, Some(true));
     tracked!(show_span, Some(String::from("abc")));
     tracked!(src_hash_algorithm, Some(SourceFileHashAlgorithm::Sha1));
+    tracked!(stack_protector, StackProtector::All);
     tracked!(symbol_mangling_version, SymbolManglingVersion::V0);
     tracked!(teach, true);
     tracked!(thinlto, Some(true));
@@ -598,3 +666,12 @@ fn test_edition_parsing() {
     let (sessopts, _) = build_session_options_and_crate_config(matches);
     assert!(sessopts.edition == Edition::Edition2018)
 }
+
+#[test]
+fn test_lint_level_names() {
+    for &level in &[Level::Allow, Level::Expect, Level::Warn, Level::Deny, Level::Forbid] {
+        assert_eq!(Level::from_str(level.as_str()), Some(level));
+    }
+    assert_eq!(Level::from_str("expect"), Some(Level::Expect));
+    assert_eq!(Level::from_str("Expect"), None);
+}
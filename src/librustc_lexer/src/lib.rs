@@ -275,6 +275,37 @@ pub fn is_id_continue(c: char) -> bool {
         || (c > '\x7f' && unicode_xid::UnicodeXID::is_xid_continue(c))
 }
 
+/// True if `c` is a unicode bidirectional override or isolate codepoint.
+/// These change the order in which text is displayed, so source code containing them
+/// may look different from how it is compiled.
+pub fn is_text_flow_control_char(c: char) -> bool {
+    match c {
+        // Embeddings and overrides
+        | '\u{202A}' // LEFT-TO-RIGHT EMBEDDING
+        | '\u{202B}' // RIGHT-TO-LEFT EMBEDDING
+        | '\u{202C}' // POP DIRECTIONAL FORMATTING
+        | '\u{202D}' // LEFT-TO-RIGHT OVERRIDE
+        | '\u{202E}' // RIGHT-TO-LEFT OVERRIDE
+
+        // Isolates
+        | '\u{2066}' // LEFT-TO-RIGHT ISOLATE
+        | '\u{2067}' // RIGHT-TO-LEFT ISOLATE
+        | '\u{2068}' // FIRST STRONG ISOLATE
+        | '\u{2069}' // POP DIRECTIONAL ISOLATE
+        => true,
+        _ => false,
+    }
+}
+
+/// True if `s` contains any codepoint accepted by [`is_text_flow_control_char`].
+///
+/// [`is_text_flow_control_char`]: fn.is_text_flow_control_char.html
+pub fn contains_text_flow_control_chars(s: &str) -> bool {
+    // All of the codepoints are encoded as three bytes starting with `0xE2`, so it's
+    // cheap to rule out most text without decoding it.
+    s.as_bytes().contains(&0xE2) && s.chars().any(is_text_flow_control_char)
+}
+
 impl Cursor<'_> {
     /// Parses a token from the input string.
     fn advance_token(&mut self) -> Token {
@@ -134,4 +134,13 @@ mod tests {
         let input = "#!/bin/rust-scripts\n#![allow_unused(true)]";
         assert_eq!(strip_shebang(input), Some(19));
     }
+
+    #[test]
+    fn test_text_flow_control_chars() {
+        assert!(contains_text_flow_control_chars("/* \u{202E} } \u{2066}if admin\u{2069} */"));
+        assert!(contains_text_flow_control_chars("\"\u{202A}\""));
+        assert!(!contains_text_flow_control_chars("// \u{200E} is only a mark"));
+        assert!(!contains_text_flow_control_chars("\"\\u{202E}\""));
+        assert!(!contains_text_flow_control_chars("\"\u{2026}\""));
+    }
 }
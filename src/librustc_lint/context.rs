@@ -19,6 +19,7 @@ use self::TargetLint::*;
 use crate::levels::LintLevelsBuilder;
 use crate::passes::{EarlyLintPassObject, LateLintPassObject};
 use rustc_ast::ast;
+use rustc_ast::attr;
 use rustc_ast::util::lev_distance::find_best_match_for_name;
 use rustc_data_structures::fx::FxHashMap;
 use rustc_data_structures::sync;
@@ -34,7 +35,8 @@ use rustc_middle::ty::{self, print::Printer, subst::GenericArg, Ty, TyCtxt};
 use rustc_session::lint::{add_elided_lifetime_in_path_suggestion, BuiltinLintDiagnostics};
 use rustc_session::lint::{FutureIncompatibleInfo, Level, Lint, LintBuffer, LintId};
 use rustc_session::Session;
-use rustc_span::{symbol::Symbol, MultiSpan, Span, DUMMY_SP};
+use rustc_span::symbol::{Ident, Symbol};
+use rustc_span::{BytePos, MultiSpan, Span, DUMMY_SP};
 use rustc_target::abi::LayoutOf;
 
 use std::slice;
@@ -275,8 +277,51 @@ impl LintStore {
         }
     }
 
+    /// Checks the validity of lint names set in the lint config file of `-Z lint-config`.
+    pub fn check_lint_name_config_file(&self, sess: &Session, lint_name: &str) {
+        if let Some(pos) = lint_name.find("::") {
+            let tool_name = &lint_name[..pos];
+            if !attr::is_known_lint_tool(Ident::from_str(tool_name)) {
+                sess.err(&format!(
+                    "unknown tool name `{}` found in the lint config file",
+                    tool_name
+                ));
+            }
+            // As in attributes, the lints of known tools can't be checked unless the tool
+            // is running, e.g. `clippy::` lints under rustc, so they're silently ignored.
+            return;
+        }
+        self.emit_lint_name_error(
+            sess,
+            lint_name,
+            || format!("set in the lint config file with `{}`", lint_name),
+        );
+    }
+
     /// Checks the validity of lint names derived from the command line
     pub fn check_lint_name_cmdline(&self, sess: &Session, lint_name: &str, level: Level) {
+        self.emit_lint_name_error(sess, lint_name, || {
+            format!(
+                "requested on the command line with `{} {}`",
+                match level {
+                    Level::Allow => "-A",
+                    Level::Expect => unreachable!(),
+                    Level::Warn => "-W",
+                    Level::Deny => "-D",
+                    Level::Forbid => "-F",
+                },
+                lint_name
+            )
+        });
+    }
+
+    /// Reports unknown, renamed and removed lints, with a note saying where they came from.
+    fn emit_lint_name_error(
+        &self,
+        sess: &Session,
+        lint_name: &str,
+        origin: impl FnOnce() -> String,
+    ) {
         let db = match self.check_lint_name(lint_name, None) {
             CheckLintNameResult::Ok(_) => None,
             CheckLintNameResult::Warning(ref msg, _) => Some(sess.struct_warn(msg)),
@@ -302,17 +347,7 @@ impl LintStore {
         };
 
         if let Some(mut db) = db {
-            let msg = format!(
-                "requested on the command line with `{} {}`",
-                match level {
-                    Level::Allow => "-A",
-                    Level::Warn => "-W",
-                    Level::Deny => "-D",
-                    Level::Forbid => "-F",
-                },
-                lint_name
-            );
-            db.note(&msg);
+            db.note(&origin());
             db.emit();
         }
     }
@@ -570,6 +605,45 @@ pub trait LintContext: Sized {
                     db.help("to document an item produced by a macro, \
                                   the macro must produce the documentation as part of its expansion");
                 }
+                BuiltinLintDiagnostics::UnicodeTextFlow(span, content, applicability) => {
+                    let spans: Vec<_> = content
+                        .char_indices()
+                        .filter(|&(_, c)| rustc_lexer::is_text_flow_control_char(c))
+                        .map(|(i, c)| {
+                            let lo = span.lo() + BytePos(i as u32);
+                            (c, span.with_lo(lo).with_hi(lo + BytePos(c.len_utf8() as u32)))
+                        })
+                        .collect();
+                    let what = if content.starts_with("//") || content.starts_with("/*") {
+                        "comment"
+                    } else {
+                        "literal"
+                    };
+                    let (an, s) = if spans.len() == 1 { ("an ", "") } else { ("", "s") };
+                    db.span_label(
+                        span,
+                        format!(
+                            "this {} contains {}invisible unicode text flow control codepoint{}",
+                            what, an, s,
+                        ),
+                    );
+                    for &(c, span) in &spans {
+                        db.span_label(span, c.escape_unicode().to_string());
+                    }
+                    db.note(
+                        "these kinds of unicode codepoints change the way text flows on \
+                         applications that support them, but can cause confusion because they \
+                         change the order of characters on the screen",
+                    );
+                    db.multipart_suggestion(
+                        "if their presence wasn't intentional, you can replace them with escapes",
+                        spans
+                            .into_iter()
+                            .map(|(c, span)| (span, c.escape_unicode().to_string()))
+                            .collect(),
+                        applicability,
+                    );
+                }
             }
             // Rewrap `db`, and pass control to the user.
             decorate(LintDiagnosticBuilder::new(db));
@@ -0,0 +1,76 @@
+//! Reports `#[expect]` attributes whose lint was never emitted.
+//!
+//! A lint suppressed by `#[expect]` is still emitted as a diagnostic at the
+//! `Expect` level, which the diagnostic handler records instead of printing.
+//! Once every lint has had a chance to fire (late lints, MIR lints and, for
+//! rustdoc, documentation lints), this compares the expectations declared in
+//! the crate against that record. The expectations of lints emitted while generating
+//! code are only checked if code was generated.
+
+use rustc_hir::def_id::LOCAL_CRATE;
+use rustc_middle::lint::{struct_lint_level, LintSet, LintSource};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::lint::builtin::{ARITHMETIC_OVERFLOW, CONST_ERR, LARGE_ASSIGNMENTS};
+use rustc_session::lint::builtin::{UNCONDITIONAL_PANIC, UNFULFILLED_LINT_EXPECTATIONS};
+use rustc_session::lint::{Level, Lint, LintId};
+
+/// Lints emitted by passes that only run on all the code when it is generated: constant
+/// propagation on the optimized MIR, and the collection of mono items.
+const CODEGEN_LINTS: &[&Lint] =
+    &[ARITHMETIC_OVERFLOW, CONST_ERR, LARGE_ASSIGNMENTS, UNCONDITIONAL_PANIC];
+
+pub fn check_expectations(tcx: TyCtxt<'_>) {
+    let fulfilled = tcx.sess.diagnostic().steal_fulfilled_expectations();
+    let lint_levels = tcx.lint_levels(LOCAL_CRATE);
+    let codegen = tcx.sess.opts.output_types.should_codegen()
+        && !tcx.sess.opts.debugging_opts.no_codegen;
+
+    let mut unfulfilled = Vec::new();
+    for (idx, set) in lint_levels.sets.list.iter().enumerate() {
+        let specs = match set {
+            LintSet::Node { specs, .. } => specs,
+            LintSet::CommandLine { .. } => continue,
+        };
+        for (id, &(level, src)) in specs {
+            let (span, reason) = match src {
+                LintSource::Node(_, span, reason) if level == Level::Expect => (span, reason),
+                _ => continue,
+            };
+            // Lints capped to `allow` by the driver can never fire, so they can't fulfill
+            // an expectation either. Rustdoc does this for all non-documentation lints.
+            if tcx.sess.driver_lint_caps.get(id) == Some(&Level::Allow) {
+                continue;
+            }
+            // Without codegen, e.g. with `--emit=metadata`, these lints may not have run.
+            if !codegen && CODEGEN_LINTS.iter().any(|&lint| LintId::of(lint) == *id) {
+                continue;
+            }
+            if !fulfilled.contains(&span) {
+                unfulfilled.push((span, reason, idx as u32));
+            }
+        }
+    }
+
+    // A single `#[expect]` of a lint group produces one spec per lint in the group.
+    unfulfilled.sort_by_key(|&(span, ..)| span);
+    unfulfilled.dedup_by_key(|&mut (span, ..)| span);
+
+    for (span, reason, idx) in unfulfilled {
+        let (level, src) =
+            lint_levels.sets.get_lint_level(UNFULFILLED_LINT_EXPECTATIONS, idx, None, tcx.sess);
+        struct_lint_level(
+            tcx.sess,
+            UNFULFILLED_LINT_EXPECTATIONS,
+            level,
+            src,
+            Some(span.into()),
+            |lint| {
+                let mut err = lint.build("this lint expectation is unfulfilled");
+                if let Some(rationale) = reason {
+                    err.note(&rationale.as_str());
+                }
+                err.emit();
+            },
+        );
+    }
+}
@@ -71,6 +71,25 @@ impl<'s> LintLevelsBuilder<'s> {
         let mut specs = FxHashMap::default();
         self.sets.lint_cap = sess.opts.lint_cap.unwrap_or(Level::Forbid);
 
+        // The levels of the lint config file come first, so that command-line flags override
+        // them, just like attributes override both.
+        for &(ref lint_name, level) in &sess.opts.lint_config.0 {
+            store.check_lint_name_config_file(sess, &lint_name);
+
+            let level = cmp::min(level, self.sets.lint_cap);
+            let lint_config_val = Symbol::intern(lint_name);
+            let ids = match store.find_lints(&lint_name) {
+                Ok(ids) => ids,
+                // Errors are handled in `check_lint_name_config_file` above, and the lints of
+                // tools that aren't running are ignored, as in attributes.
+                Err(_) => continue,
+            };
+            for id in ids {
+                self.check_gated_lint(id, DUMMY_SP);
+                specs.insert(id, (level, LintSource::ConfigFile(lint_config_val)));
+            }
+        }
+
         for &(ref lint_name, level) in &sess.opts.lint_opts {
             store.check_lint_name_cmdline(sess, &lint_name, level);
 
@@ -383,7 +402,7 @@ impl<'s> LintLevelsBuilder<'s> {
             let forbidden_lint_name = match forbid_src {
                 LintSource::Default => id.to_string(),
                 LintSource::Node(name, _, _) => name.to_string(),
-                LintSource::CommandLine(name) => name.to_string(),
+                LintSource::CommandLine(name) | LintSource::ConfigFile(name) => name.to_string(),
             };
             let (lint_attr_name, lint_attr_span) = match *src {
                 LintSource::Node(name, span, _) => (name, span),
@@ -410,6 +429,9 @@ impl<'s> LintLevelsBuilder<'s> {
                 LintSource::CommandLine(_) => {
                     diag_builder.note("`forbid` lint level was set on command line");
                 }
+                LintSource::ConfigFile(_) => {
+                    diag_builder.note("`forbid` lint level was set in the lint config file");
+                }
             }
             diag_builder.emit();
             // don't set a separate error for every lint in the group
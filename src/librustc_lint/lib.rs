@@ -45,6 +45,7 @@ mod array_into_iter;
 pub mod builtin;
 mod context;
 mod early;
+mod expect;
 mod internal;
 mod late;
 mod levels;
@@ -81,6 +82,7 @@ use unused::*;
 pub use builtin::SoftLints;
 pub use context::{CheckLintNameResult, EarlyContext, LateContext, LintContext, LintStore};
 pub use early::check_ast_crate;
+pub use expect::check_expectations;
 pub use late::check_crate;
 pub use passes::{EarlyLintPass, LateLintPass};
 pub use rustc_session::lint::Level::{self, *};
@@ -6,7 +6,7 @@ use rustc_hir::itemlikevisit::ItemLikeVisitor;
 use rustc_middle::middle::cstore::NativeLib;
 use rustc_middle::ty::TyCtxt;
 use rustc_session::parse::feature_err;
-use rustc_session::utils::NativeLibKind;
+use rustc_session::utils::{NativeLibKind, NativeLibModifiers};
 use rustc_session::Session;
 use rustc_span::source_map::Span;
 use rustc_span::symbol::{kw, sym, Symbol};
@@ -51,11 +51,13 @@ impl ItemLikeVisitor<'tcx> for Collector<'tcx> {
             let mut lib = NativeLib {
                 name: None,
                 kind: NativeLibKind::Unspecified,
+                modifiers: NativeLibModifiers::default(),
                 cfg: None,
                 foreign_module: Some(self.tcx.hir().local_def_id(it.hir_id).to_def_id()),
                 wasm_import_module: None,
             };
             let mut kind_specified = false;
+            let mut modifiers = None;
 
             for item in items.iter() {
                 if item.check_name(sym::kind) {
@@ -84,6 +86,14 @@ impl ItemLikeVisitor<'tcx> for Collector<'tcx> {
                             NativeLibKind::Unspecified
                         }
                     };
+                } else if item.check_name(sym::modifiers) {
+                    match item.value_str() {
+                        Some(value) => modifiers = Some((value, item.span())),
+                        None => {
+                            let msg = "must be of the form `#[link(modifiers = \"...\")]`";
+                            self.tcx.sess.span_err(item.span(), msg);
+                        }
+                    }
                 } else if item.check_name(sym::name) {
                     lib.name = item.value_str();
                 } else if item.check_name(sym::cfg) {
@@ -112,6 +122,23 @@ impl ItemLikeVisitor<'tcx> for Collector<'tcx> {
                 }
             }
 
+            // Modifiers are applied once the kind they may depend on is known.
+            if let Some((value, span)) = modifiers {
+                if !self.tcx.features().native_link_modifiers {
+                    feature_err(
+                        &self.tcx.sess.parse_sess,
+                        sym::native_link_modifiers,
+                        span,
+                        "linking modifiers are experimental",
+                    )
+                    .emit();
+                }
+                match NativeLibModifiers::parse(&mut lib.kind, &value.as_str()) {
+                    Ok(modifiers) => lib.modifiers = modifiers,
+                    Err(msg) => self.tcx.sess.span_err(span, &msg),
+                }
+            }
+
             // In general we require #[link(name = "...")] but we allow
             // #[link(wasm_import_module = "...")] without the `name`.
             let requires_name = kind_specified || lib.wasm_import_module.is_none();
@@ -171,7 +198,11 @@ impl Collector<'tcx> {
             )
             .emit();
         }
-        if lib.kind == NativeLibKind::StaticNoBundle && !self.tcx.features().static_nobundle {
+        // `-bundle` is an alternative to `static-nobundle` gated by `native_link_modifiers`.
+        if lib.kind == NativeLibKind::StaticNoBundle
+            && !self.tcx.features().static_nobundle
+            && !self.tcx.features().native_link_modifiers
+        {
             feature_err(
                 &self.tcx.sess.parse_sess,
                 sym::static_nobundle,
@@ -196,7 +227,7 @@ impl Collector<'tcx> {
     fn process_command_line(&mut self) {
         // First, check for errors
         let mut renames = FxHashSet::default();
-        for &(ref name, ref new_name, _) in &self.tcx.sess.opts.libs {
+        for &(ref name, ref new_name, _, _) in &self.tcx.sess.opts.libs {
             if let &Some(ref new_name) = new_name {
                 let any_duplicate = self
                     .libs
@@ -232,7 +263,7 @@ impl Collector<'tcx> {
         // it.  (This ensures that the linker is able to see symbols from
         // all possible dependent libraries before linking in the library
         // in question.)
-        for &(ref name, ref new_name, kind) in &self.tcx.sess.opts.libs {
+        for &(ref name, ref new_name, kind, modifiers) in &self.tcx.sess.opts.libs {
             // If we've already added any native libraries with the same
             // name, they will be pulled out into `existing`, so that we
             // can move them to the end of the list below.
@@ -243,6 +274,7 @@ impl Collector<'tcx> {
                         if lib_name.as_str() == *name {
                             if kind != NativeLibKind::Unspecified {
                                 lib.kind = kind;
+                                lib.modifiers = modifiers;
                             }
                             if let &Some(ref new_name) = new_name {
                                 lib.name = Some(Symbol::intern(new_name));
@@ -259,6 +291,7 @@ impl Collector<'tcx> {
                 let lib = NativeLib {
                     name: Some(Symbol::intern(new_name.unwrap_or(name))),
                     kind,
+                    modifiers,
                     cfg: None,
                     foreign_module: None,
                     wasm_import_module: None,
@@ -1361,6 +1361,11 @@ impl<'a, 'tcx> CrateMetadataRef<'a> {
         }
     }
 
+    fn get_referenced_items(&self, tcx: TyCtxt<'tcx>) -> Option<&'tcx [DefId]> {
+        let referenced_items = self.root.referenced_items?;
+        Some(tcx.arena.alloc_from_iter(referenced_items.decode(self)))
+    }
+
     fn get_rendered_const(&self, id: DefIndex) -> String {
         match self.kind(id) {
             EntryKind::AnonConst(_, data)
@@ -239,6 +239,8 @@ provide! { <'tcx> tcx, def_id, other, cdata,
 
         syms
     }
+
+    referenced_items => { cdata.get_referenced_items(tcx) }
 }
 
 pub fn provide(providers: &mut Providers<'_>) {
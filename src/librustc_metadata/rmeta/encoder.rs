@@ -429,49 +429,65 @@ impl<'tcx> EncodeContext<'tcx> {
     fn encode_crate_root(&mut self) -> Lazy<CrateRoot<'tcx>> {
         let is_proc_macro = self.tcx.sess.crate_types().contains(&CrateType::ProcMacro);
 
+        // The parts of the metadata, for `-Z verify-determinism`. The position of the root,
+        // which follows the header, is left out, as it changes with the size of everything else.
+        let header = METADATA_HEADER.len();
+        let mut sections =
+            vec![("header", 0..header), ("rustc_version", header + 4..self.position())];
+
         let mut i = self.position();
 
         let crate_deps = self.encode_crate_deps();
         let dylib_dependency_formats = self.encode_dylib_dependency_formats();
         let dep_bytes = self.position() - i;
+        sections.push(("crate_deps", i..self.position()));
 
         // Encode the lib features.
         i = self.position();
         let lib_features = self.encode_lib_features();
         let lib_feature_bytes = self.position() - i;
+        sections.push(("lib_features", i..self.position()));
 
         // Encode the language items.
         i = self.position();
         let lang_items = self.encode_lang_items();
         let lang_items_missing = self.encode_lang_items_missing();
         let lang_item_bytes = self.position() - i;
+        sections.push(("lang_items", i..self.position()));
 
         // Encode the diagnostic items.
         i = self.position();
         let diagnostic_items = self.encode_diagnostic_items();
         let diagnostic_item_bytes = self.position() - i;
+        sections.push(("diagnostic_items", i..self.position()));
 
         // Encode the native libraries used
         i = self.position();
         let native_libraries = self.encode_native_libraries();
         let native_lib_bytes = self.position() - i;
+        sections.push(("native_libraries", i..self.position()));
 
+        i = self.position();
         let foreign_modules = self.encode_foreign_modules();
+        sections.push(("foreign_modules", i..self.position()));
 
         // Encode source_map
         i = self.position();
         let source_map = self.encode_source_map();
         let source_map_bytes = self.position() - i;
+        sections.push(("source_map", i..self.position()));
 
         // Encode DefPathTable
         i = self.position();
         let def_path_table = self.encode_def_path_table();
         let def_path_table_bytes = self.position() - i;
+        sections.push(("def_path_table", i..self.position()));
 
         // Encode the def IDs of impls, for coherence checking.
         i = self.position();
         let impls = self.encode_impls();
         let impl_bytes = self.position() - i;
+        sections.push(("impls", i..self.position()));
 
         let tcx = self.tcx;
 
@@ -479,8 +495,10 @@ impl<'tcx> EncodeContext<'tcx> {
         i = self.position();
         self.encode_info_for_items();
         let item_bytes = self.position() - i;
+        sections.push(("items", i..self.position()));
 
         // Encode the allocation index
+        i = self.position();
         let interpret_alloc_index = {
             let mut interpret_alloc_index = Vec::new();
             let mut n = 0;
@@ -503,15 +521,18 @@ impl<'tcx> EncodeContext<'tcx> {
             }
             self.lazy(interpret_alloc_index)
         };
+        sections.push(("interpret_alloc_index", i..self.position()));
 
         i = self.position();
         let tables = self.tables.encode(&mut self.opaque);
         let tables_bytes = self.position() - i;
+        sections.extend(tables.sections());
 
         // Encode the proc macro data
         i = self.position();
         let proc_macro_data = self.encode_proc_macros();
         let proc_macro_data_bytes = self.position() - i;
+        sections.push(("proc_macro_data", i..self.position()));
 
         // Encode exported symbols info. This is prefetched in `encode_metadata` so we encode
         // this last to give the prefetching as much time as possible to complete.
@@ -519,6 +540,13 @@ impl<'tcx> EncodeContext<'tcx> {
         let exported_symbols = self.tcx.exported_symbols(LOCAL_CRATE);
         let exported_symbols = self.encode_exported_symbols(&exported_symbols);
         let exported_symbols_bytes = self.position() - i;
+        sections.push(("exported_symbols", i..self.position()));
+
+        i = self.position();
+        let referenced_items = self.encode_referenced_items();
+        let referenced_items_bytes = self.position() - i;
+        sections.push(("referenced_items", i..self.position()));
+        i = self.position();
 
         let attrs = tcx.hir().krate_attrs();
         let has_default_lib_allocator = attr::contains_name(&attrs, sym::default_lib_allocator);
@@ -567,11 +595,16 @@ impl<'tcx> EncodeContext<'tcx> {
             def_path_table,
             impls,
             exported_symbols,
+            referenced_items,
             interpret_alloc_index,
             tables,
         });
 
         let total_bytes = self.position();
+        sections.push(("root", i..total_bytes));
+        if self.tcx.sess.opts.debugging_opts.verify_determinism {
+            *self.tcx.sess.metadata_sections.lock() = sections;
+        }
 
         if self.tcx.sess.meta_stats() {
             let mut zero_bytes = 0;
@@ -590,6 +623,7 @@ impl<'tcx> EncodeContext<'tcx> {
             println!("         source_map bytes: {}", source_map_bytes);
             println!("            impl bytes: {}", impl_bytes);
             println!("    exp. symbols bytes: {}", exported_symbols_bytes);
+            println!("referenced items bytes: {}", referenced_items_bytes);
             println!("  def-path table bytes: {}", def_path_table_bytes);
             println!(" proc-macro-data-bytes: {}", proc_macro_data_bytes);
             println!("            item bytes: {}", item_bytes);
@@ -1511,6 +1545,14 @@ impl EncodeContext<'tcx> {
         )
     }
 
+    fn encode_referenced_items(&mut self) -> Option<Lazy<[DefId]>> {
+        if !self.tcx.sess.opts.debugging_opts.record_item_uses {
+            return None;
+        }
+        let referenced_items = self.tcx.referenced_items(LOCAL_CRATE)?;
+        Some(self.lazy(referenced_items.iter()))
+    }
+
     fn encode_dylib_dependency_formats(&mut self) -> Lazy<[Option<LinkagePreference>]> {
         let formats = self.tcx.dependency_formats(LOCAL_CRATE);
         for (ty, arr) in formats.iter() {
@@ -25,6 +25,7 @@ use rustc_target::spec::{PanicStrategy, TargetTriple};
 
 use std::marker::PhantomData;
 use std::num::NonZeroUsize;
+use std::ops::Range;
 
 pub use decoder::{provide, provide_extern};
 crate use decoder::{CrateMetadata, CrateNumMap, MetadataBlob};
@@ -204,6 +205,10 @@ crate struct CrateRoot<'tcx> {
 
     exported_symbols: Lazy!([(ExportedSymbol<'tcx>, SymbolExportLevel)]),
 
+    /// The items referenced from the live code of this crate, if it was compiled with
+    /// `-Z record-item-uses`.
+    referenced_items: Option<Lazy<[DefId]>>,
+
     compiler_builtins: bool,
     needs_allocator: bool,
     needs_panic_runtime: bool,
@@ -248,6 +253,16 @@ macro_rules! define_tables {
                 }
             }
         }
+
+        impl LazyTables<'tcx> {
+            /// The name and the byte range of each encoded table.
+            fn sections(&self) -> Vec<(&'static str, Range<usize>)> {
+                vec![$({
+                    let start = self.$name.position.get();
+                    (concat!("tables.", stringify!($name)), start..start + self.$name.meta)
+                }),+]
+            }
+        }
     }
 }
 
@@ -23,6 +23,9 @@ pub enum LintSource {
 
     /// Lint level was set by a command-line flag.
     CommandLine(Symbol),
+
+    /// Lint level was set in the lint config file given with `-Z lint-config`.
+    ConfigFile(Symbol),
 }
 
 pub type LevelSource = (Level, LintSource);
@@ -211,6 +214,17 @@ pub fn struct_lint_level<'s, 'd>(
             (Level::Allow, _) => {
                 return;
             }
+            (Level::Expect, span) => {
+                let expectation = match src {
+                    LintSource::Node(_, expectation, _) => expectation,
+                    _ => bug!("`expect` lint level set outside of an attribute"),
+                };
+                let mut err = sess.diagnostic().struct_expect("", expectation);
+                if let Some(span) = span {
+                    err.set_span(span);
+                }
+                err
+            }
             (Level::Warn, Some(span)) => sess.struct_span_warn(span, ""),
             (Level::Warn, None) => sess.struct_warn(""),
             (Level::Deny | Level::Forbid, Some(span)) => sess.struct_span_err(span, ""),
@@ -240,6 +254,13 @@ pub fn struct_lint_level<'s, 'd>(
             }
         }
 
+        // The lint is suppressed by `#[expect]`. Emitting the diagnostic only records the
+        // expectation as fulfilled, so there's no point in decorating it.
+        if level == Level::Expect {
+            err.emit();
+            return;
+        }
+
         let name = lint.name_lower();
         match src {
             LintSource::Default => {
@@ -254,7 +275,7 @@ pub fn struct_lint_level<'s, 'd>(
                     Level::Warn => "-W",
                     Level::Deny => "-D",
                     Level::Forbid => "-F",
-                    Level::Allow => panic!(),
+                    Level::Allow | Level::Expect => panic!(),
                 };
                 let hyphen_case_lint_name = name.replace("_", "-");
                 if lint_flag_val.as_str() == name {
@@ -278,6 +299,24 @@ pub fn struct_lint_level<'s, 'd>(
                     );
                 }
             }
+            LintSource::ConfigFile(lint_config_name) => {
+                let file = match &sess.opts.debugging_opts.lint_config {
+                    Some(path) => format!("the lint config file `{}`", path.display()),
+                    None => "the lint config file".to_string(),
+                };
+                let msg = if lint_config_name.as_str() == name {
+                    format!("set to `{}` in {}", level.as_str(), file)
+                } else {
+                    format!(
+                        "`{}` set to `{}` by `{}` in {}",
+                        name,
+                        level.as_str(),
+                        lint_config_name,
+                        file
+                    )
+                };
+                sess.diag_note_once(&mut err, DiagnosticMessageId::from(lint), &msg);
+            }
             LintSource::Node(lint_attr_name, src, reason) => {
                 if let Some(rationale) = reason {
                     err.note(&rationale.as_str());
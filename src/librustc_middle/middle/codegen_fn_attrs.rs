@@ -34,6 +34,9 @@ pub struct CodegenFnAttrs {
     /// The `#[no_sanitize(...)]` attribute. Indicates sanitizers for which
     /// instrumentation should be disabled inside the annotated function.
     pub no_sanitize: SanitizerSet,
+    /// The `#[stack_limit = N]` attribute: the number of bytes of stack the
+    /// function and everything it calls may use, checked by `-Z print-stack-usage`.
+    pub stack_limit: Option<u64>,
 }
 
 bitflags! {
@@ -79,6 +82,9 @@ bitflags! {
         /// #[ffi_const]: applies clang's `const` attribute to a foreign function
         /// declaration.
         const FFI_CONST                 = 1 << 13;
+        /// `#[no_panic]`: the monomorphization collector checks that the function
+        /// can't reach a panic.
+        const NO_PANIC                  = 1 << 14;
     }
 }
 
@@ -95,6 +101,7 @@ impl CodegenFnAttrs {
             linkage: None,
             link_section: None,
             no_sanitize: SanitizerSet::empty(),
+            stack_limit: None,
         }
     }
 
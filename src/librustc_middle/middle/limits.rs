@@ -1,4 +1,5 @@
-//! Registering limits, recursion_limit, type_length_limit and const_eval_limit
+//! Registering limits, recursion_limit, type_length_limit, const_eval_limit and
+//! move_size_limit
 //!
 //! There are various parts of the compiler that must impose arbitrary limits
 //! on how deeply they recurse to prevent stack overflow. Users can override
@@ -17,6 +18,8 @@ pub fn update_limits(sess: &Session, krate: &ast::Crate) {
     update_limit(sess, krate, &sess.recursion_limit, sym::recursion_limit, 128);
     update_limit(sess, krate, &sess.type_length_limit, sym::type_length_limit, 1048576);
     update_limit(sess, krate, &sess.const_eval_limit, sym::const_eval_limit, 1_000_000);
+    let move_size_limit = sess.opts.debugging_opts.move_size_limit.unwrap_or(0);
+    update_limit(sess, krate, &sess.move_size_limit, sym::move_size_limit, move_size_limit);
 }
 
 fn update_limit(
@@ -0,0 +1,131 @@
+//! Metadata from source code coverage analysis and instrumentation.
+
+use rustc_macros::HashStable;
+use rustc_span::Symbol;
+
+use std::fmt::{self, Debug, Formatter};
+
+rustc_index::newtype_index! {
+    /// An index of a physical counter, incremented at runtime by `llvm.instrprof.increment`.
+    /// Counters are numbered from zero, separately for each instrumented function.
+    pub struct CounterValueReference {
+        derive [HashStable]
+        DEBUG_FORMAT = "CounterValueReference({})",
+    }
+}
+
+rustc_index::newtype_index! {
+    /// An index of a counter expression. Expressions are not incremented at runtime; their
+    /// values are computed from counters (and other expressions) when the coverage report is
+    /// generated. Expressions are numbered from zero, separately for each instrumented function.
+    pub struct InjectedExpressionIndex {
+        derive [HashStable]
+        DEBUG_FORMAT = "InjectedExpressionIndex({})",
+    }
+}
+
+/// An operand of a counter expression, or the count of a code region.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, RustcEncodable, RustcDecodable, HashStable)]
+pub enum CoverageTerm {
+    /// A count that is always zero.
+    Zero,
+    Counter(CounterValueReference),
+    Expression(InjectedExpressionIndex),
+}
+
+impl Debug for CoverageTerm {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CoverageTerm::Zero => write!(fmt, "Zero"),
+            CoverageTerm::Counter(id) => write!(fmt, "Counter({})", id.index()),
+            CoverageTerm::Expression(id) => write!(fmt, "Expression({})", id.index()),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, RustcEncodable, RustcDecodable, HashStable)]
+pub enum Op {
+    Subtract,
+    Add,
+}
+
+impl Debug for Op {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Op::Subtract => write!(fmt, "-"),
+            Op::Add => write!(fmt, "+"),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, RustcEncodable, RustcDecodable, HashStable)]
+pub enum CoverageKind {
+    /// Increments the counter `id` when executed. `function_source_hash` identifies the
+    /// version of the function's source code the counters were computed from.
+    Counter { function_source_hash: u64, id: CounterValueReference },
+    /// Declares the expression `id` as `lhs op rhs`. Expressions have no runtime effect.
+    Expression { id: InjectedExpressionIndex, lhs: CoverageTerm, op: Op, rhs: CoverageTerm },
+}
+
+impl CoverageKind {
+    /// The term for the count computed by this counter or expression.
+    pub fn as_term(&self) -> CoverageTerm {
+        match *self {
+            CoverageKind::Counter { id, .. } => CoverageTerm::Counter(id),
+            CoverageKind::Expression { id, .. } => CoverageTerm::Expression(id),
+        }
+    }
+}
+
+impl Debug for CoverageKind {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CoverageKind::Counter { id, .. } => write!(fmt, "Counter({})", id.index()),
+            CoverageKind::Expression { id, lhs, op, rhs } => {
+                write!(fmt, "Expression({}) = {:?} {:?} {:?}", id.index(), lhs, op, rhs)
+            }
+        }
+    }
+}
+
+/// A source code region, in the form expected by LLVM's coverage mapping format. Lines and
+/// columns are 1-based, and the end position is exclusive.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, RustcEncodable, RustcDecodable, HashStable)]
+pub struct CodeRegion {
+    pub file_name: Symbol,
+    pub start_line: u32,
+    pub start_col: u32,
+    pub end_line: u32,
+    pub end_col: u32,
+}
+
+impl Debug for CodeRegion {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            fmt,
+            "{}:{}:{} - {}:{}",
+            self.file_name, self.start_line, self.start_col, self.end_line, self.end_col
+        )
+    }
+}
+
+/// The payload of `StatementKind::Coverage`. A counter or expression without `code_regions` is
+/// only used as an operand of other expressions.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable, HashStable)]
+pub struct Coverage {
+    pub kind: CoverageKind,
+    pub code_regions: Vec<CodeRegion>,
+}
+
+/// Coverage information summarized from a MIR body instrumented by `InstrumentCoverage`, when
+/// compiled with `-Zinstrument-coverage`. The query `tcx.coverageinfo(DefId)` computes these
+/// values on demand, during code generation.
+#[derive(Clone, RustcEncodable, RustcDecodable, Debug, HashStable)]
+pub struct CoverageInfo {
+    /// The number of counters referenced by the MIR `Body`, which is also the size of the
+    /// function's array of runtime counters.
+    pub num_counters: u32,
+
+    /// The number of counter expressions referenced by the MIR `Body`.
+    pub num_expressions: u32,
+}
@@ -5,6 +5,7 @@ use crate::ty;
 
 CloneTypeFoldableAndLiftImpls! {
     BlockTailInfo,
+    Coverage,
     MirPhase,
     SourceInfo,
     FakeReadCause,
@@ -1,3 +1,4 @@
+use crate::mir::coverage::Coverage;
 use crate::mir::*;
 use crate::ty::subst::SubstsRef;
 use crate::ty::{CanonicalUserTypeAnnotation, Ty};
@@ -134,6 +135,12 @@ macro_rules! make_mir_visitor {
                 self.super_ascribe_user_ty(place, variance, user_ty, location);
             }
 
+            fn visit_coverage(&mut self,
+                              coverage: & $($mutability)? Coverage,
+                              location: Location) {
+                self.super_coverage(coverage, location);
+            }
+
             fn visit_retag(&mut self,
                            kind: & $($mutability)? RetagKind,
                            place: & $($mutability)? Place<'tcx>,
@@ -389,6 +396,12 @@ macro_rules! make_mir_visitor {
                     ) => {
                         self.visit_ascribe_user_ty(place, variance, user_ty, location);
                     }
+                    StatementKind::Coverage(coverage) => {
+                        self.visit_coverage(
+                            coverage,
+                            location
+                        )
+                    }
                     StatementKind::Nop => {}
                 }
             }
@@ -733,6 +746,11 @@ macro_rules! make_mir_visitor {
                 self.visit_user_type_projection(user_ty);
             }
 
+            fn super_coverage(&mut self,
+                              _coverage: & $($mutability)? Coverage,
+                              _location: Location) {
+            }
+
             fn super_retag(&mut self,
                            _kind: & $($mutability)? RetagKind,
                            place: & $($mutability)? Place<'tcx>,
@@ -231,8 +231,10 @@ rustc_queries! {
             cache_on_disk_if { key.is_local() }
         }
 
-        query coverage_data(key: DefId) -> mir::CoverageData {
-            desc { |tcx| "retrieving coverage data from MIR for `{}`", tcx.def_path_str(key) }
+        /// Returns coverage summary info for a function, after executing the `InstrumentCoverage`
+        /// MIR pass (assuming the -Zinstrument-coverage option is enabled).
+        query coverageinfo(key: DefId) -> mir::coverage::CoverageInfo {
+            desc { |tcx| "retrieving coverage info from MIR for `{}`", tcx.def_path_str(key) }
             storage(ArenaCacheSelector<'tcx>)
             cache_on_disk_if { key.is_local() }
         }
@@ -1214,6 +1216,13 @@ rustc_queries! {
         query all_traits(_: CrateNum) -> &'tcx [DefId] {
             desc { "fetching all foreign and local traits" }
         }
+
+        /// The items of a crate and its dependencies that the code of the crate refers to,
+        /// whether that code is live or not. Upstream crates only record these in their
+        /// metadata with `-Z record-item-uses`, so this is `None` for crates compiled without it.
+        query referenced_items(_: CrateNum) -> Option<&'tcx [DefId]> {
+            desc { "collecting the items referenced by a crate" }
+        }
     }
 
     Linking {
@@ -1243,6 +1252,12 @@ rustc_queries! {
         query backend_optimization_level(_: CrateNum) -> OptLevel {
             desc { "optimization level used by backend" }
         }
+        /// The fingerprint of the contents of the `-C profile-sample-use` profile, read by
+        /// every codegen unit, so that they are not reused when only the profile has changed.
+        query profile_sample_use_fingerprint(_: CrateNum) -> Option<Fingerprint> {
+            eval_always
+            desc { "hashing the sample profile" }
+        }
     }
 
     Other {
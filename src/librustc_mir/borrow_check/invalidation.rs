@@ -94,10 +94,11 @@ impl<'cx, 'tcx> Visitor<'tcx> for InvalidationGenerator<'cx, 'tcx> {
             }
             StatementKind::Nop
             | StatementKind::AscribeUserType(..)
+            | StatementKind::Coverage(..)
             | StatementKind::Retag { .. }
             | StatementKind::StorageLive(..) => {
-                // `Nop`, `AscribeUserType`, `Retag`, and `StorageLive` are irrelevant
-                // to borrow check.
+                // `Nop`, `AscribeUserType`, `Coverage`, `Retag`, and `StorageLive` are
+                // irrelevant to borrow check.
             }
             StatementKind::StorageDead(local) => {
                 self.access_place(
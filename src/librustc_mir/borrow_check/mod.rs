@@ -625,10 +625,11 @@ impl<'cx, 'tcx> dataflow::ResultsVisitor<'cx, 'tcx> for MirBorrowckCtxt<'cx, 'tc
             }
             StatementKind::Nop
             | StatementKind::AscribeUserType(..)
+            | StatementKind::Coverage(..)
             | StatementKind::Retag { .. }
             | StatementKind::StorageLive(..) => {
-                // `Nop`, `AscribeUserType`, `Retag`, and `StorageLive` are irrelevant
-                // to borrow check.
+                // `Nop`, `AscribeUserType`, `Coverage`, `Retag`, and `StorageLive` are
+                // irrelevant to borrow check.
             }
             StatementKind::StorageDead(local) => {
                 self.access_place(
@@ -183,20 +183,23 @@ use rustc_hir as hir;
 use rustc_hir::def_id::{DefId, DefIdMap, LocalDefId, LOCAL_CRATE};
 use rustc_hir::itemlikevisit::ItemLikeVisitor;
 use rustc_hir::lang_items::{ExchangeMallocFnLangItem, StartFnLangItem};
-use rustc_index::bit_set::GrowableBitSet;
+use rustc_index::bit_set::{BitSet, GrowableBitSet};
 use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use rustc_middle::mir::interpret::{AllocId, ConstValue};
 use rustc_middle::mir::interpret::{ErrorHandled, GlobalAlloc, Scalar};
 use rustc_middle::mir::mono::{InstantiationMode, MonoItem};
 use rustc_middle::mir::visit::Visitor as MirVisitor;
-use rustc_middle::mir::{self, Local, Location};
+use rustc_middle::mir::{self, AssertKind, ClearCrossCrate, Local, LocalKind, Location};
 use rustc_middle::ty::adjustment::{CustomCoerceUnsized, PointerCast};
 use rustc_middle::ty::print::obsolete::DefPathBasedNames;
 use rustc_middle::ty::subst::{GenericArgKind, InternalSubsts};
 use rustc_middle::ty::{self, GenericParamDefKind, Instance, Ty, TyCtxt, TypeFoldable};
 use rustc_session::config::EntryFnType;
+use rustc_session::lint::builtin::LARGE_ASSIGNMENTS;
+use rustc_session::DiagnosticMessageId;
 use rustc_span::source_map::{dummy_spanned, respan, Span, Spanned, DUMMY_SP};
 use smallvec::SmallVec;
+use std::collections::VecDeque;
 use std::iter;
 
 #[derive(PartialEq)]
@@ -306,7 +309,12 @@ pub fn collect_crate_mono_items(
         });
     }
 
-    (visited.into_inner(), inlining_map.into_inner())
+    let visited = visited.into_inner();
+    tcx.sess.time("monomorphization_collector_no_panic_check", || {
+        check_no_panic_items(tcx, &visited);
+    });
+
+    (visited, inlining_map.into_inner())
 }
 
 // Find all non-generic items by walking the HIR. These items serve as roots to
@@ -511,6 +519,10 @@ struct MirNeighborCollector<'a, 'tcx> {
     body: &'a mir::Body<'tcx>,
     output: &'a mut Vec<Spanned<MonoItem<'tcx>>>,
     instance: Instance<'tcx>,
+    /// The temporaries holding a value moved or copied out of another place, like the
+    /// temporaries that hold the arguments of calls. Moving the value out of such a temporary
+    /// isn't linted by `large_assignments`, as moving it into the temporary already is.
+    moved_temps: BitSet<Local>,
 }
 
 impl<'a, 'tcx> MirNeighborCollector<'a, 'tcx> {
@@ -527,6 +539,26 @@ impl<'a, 'tcx> MirNeighborCollector<'a, 'tcx> {
     }
 }
 
+/// Returns the temporaries of `body` that are assigned a value moved or copied out of another
+/// place, see `MirNeighborCollector::moved_temps`.
+fn moved_temps(body: &mir::Body<'_>) -> BitSet<Local> {
+    let mut moved_temps = BitSet::new_empty(body.local_decls.len());
+    for statement in body.basic_blocks().iter().flat_map(|data| &data.statements) {
+        if let mir::StatementKind::Assign(box (
+            ref place,
+            mir::Rvalue::Use(mir::Operand::Move(_) | mir::Operand::Copy(_)),
+        )) = statement.kind
+        {
+            if let Some(local) = place.as_local() {
+                if body.local_kind(local) == LocalKind::Temp {
+                    moved_temps.insert(local);
+                }
+            }
+        }
+    }
+    moved_temps
+}
+
 impl<'a, 'tcx> MirVisitor<'tcx> for MirNeighborCollector<'a, 'tcx> {
     fn visit_rvalue(&mut self, rvalue: &mir::Rvalue<'tcx>, location: Location) {
         debug!("visiting rvalue {:?}", *rvalue);
@@ -689,6 +721,60 @@ impl<'a, 'tcx> MirVisitor<'tcx> for MirNeighborCollector<'a, 'tcx> {
         self.super_terminator(terminator, location);
     }
 
+    fn visit_operand(&mut self, operand: &mir::Operand<'tcx>, location: Location) {
+        self.super_operand(operand, location);
+
+        let limit = self.tcx.sess.move_size_limit();
+        if limit.0 == 0 {
+            return;
+        }
+        let (verb, participle) = match *operand {
+            mir::Operand::Move(place) | mir::Operand::Copy(place)
+                if place.as_local().map_or(false, |local| self.moved_temps.contains(local)) =>
+            {
+                return;
+            }
+            mir::Operand::Move(_) => ("moving", "moved"),
+            mir::Operand::Copy(_) => ("copying", "copied"),
+            mir::Operand::Constant(_) => return,
+        };
+        let ty = self.monomorphize(operand.ty(self.body, self.tcx));
+        let size = match self.tcx.layout_of(ty::ParamEnv::reveal_all().and(ty)) {
+            Ok(layout) => layout.size.bytes(),
+            Err(_) => return,
+        };
+        if limit.value_within_limit(size as usize) {
+            return;
+        }
+
+        let source_info = self.body.source_info(location);
+        let lint_root = match self.body.source_scopes[source_info.scope].local_data {
+            ClearCrossCrate::Set(ref data) => data.lint_root,
+            // The MIR of upstream crates and of shims doesn't know about lint levels.
+            ClearCrossCrate::Clear => return,
+        };
+        // Generic functions are visited once for each of their instantiations, but each of their
+        // moves is only linted once.
+        let fresh = self.tcx.sess.one_time_diagnostics.borrow_mut().insert((
+            DiagnosticMessageId::from(LARGE_ASSIGNMENTS),
+            Some(source_info.span),
+            String::new(),
+        ));
+        if !fresh {
+            return;
+        }
+        self.tcx.struct_span_lint_hir(LARGE_ASSIGNMENTS, lint_root, source_info.span, |lint| {
+            lint.build(&format!("{} {} bytes", verb, size))
+                .span_label(source_info.span, format!("value of type `{}` {} here", ty, participle))
+                .note(&format!(
+                    "the current maximum size is {}, but it can be customized with \
+                     `#![move_size_limit = \"...\"]` or `-Z move-size-limit`",
+                    limit
+                ))
+                .emit()
+        });
+    }
+
     fn visit_local(
         &mut self,
         _place_local: &Local,
@@ -1216,8 +1302,14 @@ fn collect_neighbours<'tcx>(
 ) {
     debug!("collect_neighbours: {:?}", instance.def_id());
     let body = tcx.instance_mir(instance.def);
+    // The temporaries are only needed by `large_assignments`.
+    let moved_temps = if tcx.sess.move_size_limit().0 == 0 {
+        BitSet::new_empty(0)
+    } else {
+        moved_temps(&body)
+    };
 
-    MirNeighborCollector { tcx, body: &body, output, instance }.visit_body(&body);
+    MirNeighborCollector { tcx, body: &body, output, instance, moved_temps }.visit_body(&body);
 }
 
 fn def_id_to_string(tcx: TyCtxt<'_>, def_id: LocalDefId) -> String {
@@ -1242,3 +1334,200 @@ fn collect_const_value<'tcx>(
         _ => {}
     }
 }
+
+/// How the body of an instance may lead to a panic, as far as `#[no_panic]` is concerned.
+enum PanicEdge<'tcx> {
+    /// A call of another instance, or drop glue, which has to be checked in turn.
+    Call(Instance<'tcx>),
+    /// A call of a function that always panics.
+    Panic(DefId),
+    /// A MIR assertion, which panics if it fails.
+    Assert(&'static str),
+    /// A call that can't be followed, which has to be assumed to panic.
+    Unknown(String),
+}
+
+/// Checks that no function marked `#[no_panic]` can reach a panic, by following all calls in
+/// the optimized MIR of the function and of everything it calls in turn. Calls that can't be
+/// followed, like calls of function pointers or of upstream functions whose MIR isn't
+/// available, are assumed to panic. Foreign functions are assumed not to.
+fn check_no_panic_items<'tcx>(tcx: TyCtxt<'tcx>, items: &FxHashSet<MonoItem<'tcx>>) {
+    let mut roots: Vec<_> = items
+        .iter()
+        .filter_map(|item| match *item {
+            MonoItem::Fn(instance) => match instance.def {
+                ty::InstanceDef::Item(def_id)
+                    if tcx.codegen_fn_attrs(def_id).flags.contains(CodegenFnAttrFlags::NO_PANIC) =>
+                {
+                    Some(instance)
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    if roots.is_empty() {
+        return;
+    }
+    // Report the errors in a stable order.
+    roots.sort_by_cached_key(|instance| instance.to_string());
+
+    let lang_items = tcx.lang_items();
+    let panic_fns: Vec<DefId> = [
+        lang_items.panic_fn(),
+        lang_items.panic_bounds_check_fn(),
+        lang_items.begin_panic_fn(),
+        lang_items.panic_impl(),
+    ]
+    .iter()
+    .filter_map(|&def_id| def_id)
+    .collect();
+
+    let mut edges: FxHashMap<Instance<'tcx>, Vec<(PanicEdge<'tcx>, Span)>> = Default::default();
+    for root in roots {
+        // Search breadth-first, so that the shortest call chain to a panic gets reported.
+        let mut callers: FxHashMap<Instance<'tcx>, Option<(Instance<'tcx>, Span)>> =
+            Default::default();
+        callers.insert(root, None);
+        let mut queue = VecDeque::new();
+        queue.push_back(root);
+        'search: while let Some(instance) = queue.pop_front() {
+            let instance_edges =
+                edges.entry(instance).or_insert_with(|| panic_edges(tcx, instance, &panic_fns));
+            for (edge, span) in instance_edges.iter() {
+                if let PanicEdge::Call(callee) = *edge {
+                    if !callers.contains_key(&callee) {
+                        callers.insert(callee, Some((instance, *span)));
+                        queue.push_back(callee);
+                    }
+                } else {
+                    report_no_panic_violation(tcx, root, &callers, instance, edge, *span);
+                    break 'search;
+                }
+            }
+        }
+    }
+}
+
+/// Collects the ways the optimized MIR of `instance` may panic.
+fn panic_edges<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: Instance<'tcx>,
+    panic_fns: &[DefId],
+) -> Vec<(PanicEdge<'tcx>, Span)> {
+    let body = tcx.instance_mir(instance.def);
+    let monomorphize = |ty: Ty<'tcx>| match instance.substs_for_mir_body() {
+        Some(substs) => {
+            tcx.subst_and_normalize_erasing_regions(substs, ty::ParamEnv::reveal_all(), &ty)
+        }
+        None => tcx.normalize_erasing_regions(ty::ParamEnv::reveal_all(), ty),
+    };
+
+    let mut edges = vec![];
+    for data in body.basic_blocks() {
+        // Cleanup blocks only run while unwinding from a panic that already happened.
+        if data.is_cleanup {
+            continue;
+        }
+        let terminator = data.terminator();
+        let edge = match terminator.kind {
+            mir::TerminatorKind::Call { ref func, .. } => {
+                match monomorphize(func.ty(body, tcx)).kind {
+                    ty::FnDef(def_id, _) if panic_fns.contains(&def_id) => {
+                        Some(PanicEdge::Panic(def_id))
+                    }
+                    ty::FnDef(def_id, substs) => {
+                        match Instance::resolve(tcx, ty::ParamEnv::reveal_all(), def_id, substs) {
+                            Ok(Some(callee)) => callee_panic_edge(tcx, callee),
+                            _ => Some(PanicEdge::Unknown(format!(
+                                "calls `{}`, which can't be resolved",
+                                tcx.def_path_str(def_id)
+                            ))),
+                        }
+                    }
+                    _ => Some(PanicEdge::Unknown("calls a function pointer".to_string())),
+                }
+            }
+            mir::TerminatorKind::Drop { ref place, .. }
+            | mir::TerminatorKind::DropAndReplace { ref place, .. } => {
+                let ty = monomorphize(place.ty(body, tcx).ty);
+                callee_panic_edge(tcx, Instance::resolve_drop_in_place(tcx, ty))
+            }
+            mir::TerminatorKind::Assert { ref msg, .. } => Some(PanicEdge::Assert(match msg {
+                AssertKind::BoundsCheck { .. } => "index out of bounds",
+                _ => msg.description(),
+            })),
+            _ => None,
+        };
+        if let Some(edge) = edge {
+            edges.push((edge, terminator.source_info.span));
+        }
+    }
+    edges
+}
+
+fn callee_panic_edge<'tcx>(tcx: TyCtxt<'tcx>, callee: Instance<'tcx>) -> Option<PanicEdge<'tcx>> {
+    match callee.def {
+        // Drop glue for types without drop glue does nothing.
+        ty::InstanceDef::DropGlue(_, None) => None,
+        ty::InstanceDef::Virtual(def_id, _) => Some(PanicEdge::Unknown(format!(
+            "calls the trait object method `{}`",
+            tcx.def_path_str(def_id)
+        ))),
+        ty::InstanceDef::Intrinsic(def_id) => {
+            // These intrinsics are lowered to a panic if the type they check is invalid.
+            match &*tcx.item_name(def_id).as_str() {
+                "assert_inhabited" | "assert_zero_valid" | "assert_uninit_valid" => {
+                    Some(PanicEdge::Panic(def_id))
+                }
+                _ => None,
+            }
+        }
+        ty::InstanceDef::Item(def_id) if tcx.is_foreign_item(def_id) => None,
+        ty::InstanceDef::Item(def_id) if !tcx.is_mir_available(def_id) => {
+            Some(PanicEdge::Unknown(format!(
+                "calls `{}`, whose MIR isn't available",
+                tcx.def_path_str(def_id)
+            )))
+        }
+        _ => Some(PanicEdge::Call(callee)),
+    }
+}
+
+fn report_no_panic_violation<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    root: Instance<'tcx>,
+    callers: &FxHashMap<Instance<'tcx>, Option<(Instance<'tcx>, Span)>>,
+    instance: Instance<'tcx>,
+    edge: &PanicEdge<'tcx>,
+    span: Span,
+) {
+    let name = |instance: Instance<'tcx>| tcx.def_path_str(instance.def_id());
+
+    let mut chain = vec![];
+    let mut callee = instance;
+    while let Some(&Some((caller, call_span))) = callers.get(&callee) {
+        chain.push((caller, callee, call_span));
+        callee = caller;
+    }
+
+    let mut err = tcx.sess.struct_span_err(
+        tcx.def_span(root.def_id()),
+        &format!("`{}` is marked `#[no_panic]`, but can panic", name(root)),
+    );
+    for &(caller, callee, call_span) in chain.iter().rev() {
+        err.span_note(call_span, &format!("`{}` calls `{}`", name(caller), name(callee)));
+    }
+    let msg = match edge {
+        PanicEdge::Panic(def_id) => {
+            format!("`{}` calls `{}`, which panics", name(instance), tcx.def_path_str(*def_id))
+        }
+        PanicEdge::Assert(what) => format!("`{}` may panic here: {}", name(instance), what),
+        PanicEdge::Unknown(what) => {
+            format!("`{}` {}, which is assumed to panic", name(instance), what)
+        }
+        PanicEdge::Call(_) => unreachable!(),
+    };
+    err.span_note(span, &msg);
+    err.emit();
+}
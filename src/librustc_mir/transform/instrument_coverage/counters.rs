@@ -0,0 +1,162 @@
+//! Chooses how the execution count of each `BasicCoverageBlock` is computed: by a physical
+//! counter, incremented at runtime, or by a counter expression, computed from other counts when
+//! the coverage report is generated.
+
+use super::graph::{BasicCoverageBlock, CoverageGraph, START_BCB};
+
+use rustc_data_structures::graph::WithNumNodes;
+use rustc_index::bit_set::BitSet;
+use rustc_index::vec::IndexVec;
+use rustc_middle::mir::coverage::*;
+use rustc_middle::mir::{self, TerminatorKind};
+
+/// Creates the counters and expressions for the `BasicCoverageBlock`s of a function, on demand.
+///
+/// A `BasicCoverageBlock`'s count is computed by an expression, instead of a counter, when:
+///
+/// * it is one of the successors of a `SwitchInt`, and is the only entry point of the successor:
+///   its count is the branching block's count, minus the counts of the other successors. Only
+///   one successor of each branch is counted this way.
+/// * it is the only successor of each of its predecessors, and none of its predecessors is
+///   reached through a loop back edge: its count is the sum of its predecessors' counts.
+///
+/// Counts are only computed for the blocks that have code regions, and for the blocks their
+/// expressions depend on.
+crate struct BcbCounters<'a, 'tcx> {
+    mir_body: &'a mir::Body<'tcx>,
+    graph: &'a CoverageGraph,
+    function_source_hash: u64,
+    terms: IndexVec<BasicCoverageBlock, Option<CoverageTerm>>,
+    /// The blocks whose term is being computed, to detect a term that depends on itself.
+    in_progress: BitSet<BasicCoverageBlock>,
+    /// The branching blocks with a successor whose count is derived from the branch's count.
+    derived_branches: BitSet<BasicCoverageBlock>,
+    next_counter_id: u32,
+    next_expression_id: u32,
+    /// The counters and expressions created so far, with the `BasicCoverageBlock` each one is
+    /// injected into.
+    coverage_kinds: Vec<(BasicCoverageBlock, CoverageKind)>,
+}
+
+impl<'a, 'tcx> BcbCounters<'a, 'tcx> {
+    crate fn new(
+        mir_body: &'a mir::Body<'tcx>,
+        graph: &'a CoverageGraph,
+        function_source_hash: u64,
+    ) -> Self {
+        let num_bcbs = graph.num_nodes();
+        Self {
+            mir_body,
+            graph,
+            function_source_hash,
+            terms: IndexVec::from_elem_n(None, num_bcbs),
+            in_progress: BitSet::new_empty(num_bcbs),
+            derived_branches: BitSet::new_empty(num_bcbs),
+            next_counter_id: 0,
+            next_expression_id: 0,
+            coverage_kinds: Vec::new(),
+        }
+    }
+
+    /// Returns the term whose value is the execution count of `bcb`, creating the counters and
+    /// expressions it requires.
+    crate fn term_for(&mut self, bcb: BasicCoverageBlock) -> CoverageTerm {
+        if let Some(term) = self.terms[bcb] {
+            return term;
+        }
+        if !self.in_progress.insert(bcb) {
+            // The count of `bcb` is an operand of its own expression (which can only happen in
+            // a control flow graph that is not reducible). Count it directly instead.
+            let term = self.make_counter(bcb);
+            self.terms[bcb] = Some(term);
+            return term;
+        }
+
+        let term = if let Some(branch) = self.derivable_from_branch(bcb) {
+            self.derived_branches.insert(branch);
+            let siblings: Vec<_> =
+                self.graph.successors[branch].iter().copied().filter(|&s| s != bcb).collect();
+            let mut term = self.term_for(branch);
+            for sibling in siblings {
+                let sibling_term = self.term_for(sibling);
+                term = self.make_expression(bcb, term, Op::Subtract, sibling_term);
+            }
+            term
+        } else if self.is_sum_of_predecessors(bcb) {
+            let predecessors = self.graph.predecessors[bcb].clone();
+            let mut term = self.term_for(predecessors[0]);
+            for &predecessor in &predecessors[1..] {
+                let predecessor_term = self.term_for(predecessor);
+                term = self.make_expression(bcb, term, Op::Add, predecessor_term);
+            }
+            term
+        } else {
+            self.make_counter(bcb)
+        };
+
+        self.in_progress.remove(bcb);
+        // If `bcb` was given a counter to break a cycle, the expression is left unused.
+        *self.terms[bcb].get_or_insert(term)
+    }
+
+    /// Creates an expression, injected into `bcb`.
+    fn make_expression(
+        &mut self,
+        bcb: BasicCoverageBlock,
+        lhs: CoverageTerm,
+        op: Op,
+        rhs: CoverageTerm,
+    ) -> CoverageTerm {
+        let id = InjectedExpressionIndex::from_u32(self.next_expression_id);
+        self.next_expression_id += 1;
+        self.coverage_kinds.push((bcb, CoverageKind::Expression { id, lhs, op, rhs }));
+        CoverageTerm::Expression(id)
+    }
+
+    crate fn into_coverage_kinds(self) -> Vec<(BasicCoverageBlock, CoverageKind)> {
+        self.coverage_kinds
+    }
+
+    fn make_counter(&mut self, bcb: BasicCoverageBlock) -> CoverageTerm {
+        let id = CounterValueReference::from_u32(self.next_counter_id);
+        self.next_counter_id += 1;
+        let function_source_hash = self.function_source_hash;
+        self.coverage_kinds.push((bcb, CoverageKind::Counter { function_source_hash, id }));
+        CoverageTerm::Counter(id)
+    }
+
+    /// Returns the `SwitchInt` block that `bcb`'s count can be derived from, if any. Every
+    /// successor of the branch must be entered only from the branch, so the successors' counts
+    /// add up to the branch's count.
+    fn derivable_from_branch(&self, bcb: BasicCoverageBlock) -> Option<BasicCoverageBlock> {
+        if bcb == START_BCB {
+            return None;
+        }
+        let branch = match self.graph.predecessors[bcb][..] {
+            [branch] => branch,
+            _ => return None,
+        };
+        if self.derived_branches.contains(branch) {
+            return None;
+        }
+        match self.mir_body[self.graph[branch].last_bb()].terminator().kind {
+            TerminatorKind::SwitchInt { .. } => {}
+            _ => return None,
+        }
+        let successors = &self.graph.successors[branch];
+        let is_exclusive = successors.iter().all(|&successor| {
+            successor != START_BCB && self.graph.predecessors[successor].len() == 1
+        });
+        if successors.len() > 1 && is_exclusive { Some(branch) } else { None }
+    }
+
+    fn is_sum_of_predecessors(&self, bcb: BasicCoverageBlock) -> bool {
+        let predecessors = &self.graph.predecessors[bcb];
+        bcb != START_BCB
+            && predecessors.len() > 1
+            && predecessors.iter().all(|&predecessor| {
+                self.graph.successors[predecessor].len() == 1
+                    && !self.graph.dominates(bcb, predecessor)
+            })
+    }
+}
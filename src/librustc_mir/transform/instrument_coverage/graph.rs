@@ -0,0 +1,226 @@
+//! A simplified view of a MIR body's control flow graph, used to decide where coverage counters
+//! are needed.
+
+use rustc_data_structures::graph::dominators::{self, Dominators};
+use rustc_data_structures::graph::{self, GraphPredecessors, GraphSuccessors};
+use rustc_index::bit_set::BitSet;
+use rustc_index::vec::IndexVec;
+use rustc_middle::mir::{self, BasicBlock, BasicBlockData, TerminatorKind, START_BLOCK};
+
+use std::ops::Index;
+use std::slice;
+
+rustc_index::newtype_index! {
+    /// A node in the `CoverageGraph`.
+    crate struct BasicCoverageBlock {
+        DEBUG_FORMAT = "bcb{}",
+        const START_BCB = 0,
+    }
+}
+
+/// A sequence of MIR `BasicBlock`s that always execute together, one after the other. Unwinding
+/// is ignored: a panic in the middle of a `BasicCoverageBlock` is not counted.
+crate struct BasicCoverageBlockData {
+    /// The MIR blocks in execution order. The first block is the one the counter is injected in.
+    crate basic_blocks: Vec<BasicBlock>,
+}
+
+impl BasicCoverageBlockData {
+    crate fn leader_bb(&self) -> BasicBlock {
+        self.basic_blocks[0]
+    }
+
+    crate fn last_bb(&self) -> BasicBlock {
+        *self.basic_blocks.last().unwrap()
+    }
+}
+
+/// The control flow graph of a MIR body, with chains of blocks merged into
+/// `BasicCoverageBlock`s, and without cleanup blocks or edges that are never taken at runtime.
+///
+/// Only blocks reachable from `START_BLOCK` are included. `BasicCoverageBlock`s are numbered in
+/// reverse postorder, so a `BasicCoverageBlock` always has a lower index than the blocks it
+/// dominates.
+crate struct CoverageGraph {
+    bcbs: IndexVec<BasicCoverageBlock, BasicCoverageBlockData>,
+    crate successors: IndexVec<BasicCoverageBlock, Vec<BasicCoverageBlock>>,
+    crate predecessors: IndexVec<BasicCoverageBlock, Vec<BasicCoverageBlock>>,
+    dominators: Option<Dominators<BasicCoverageBlock>>,
+}
+
+impl CoverageGraph {
+    crate fn from_mir(mir_body: &mir::Body<'_>) -> Self {
+        let basic_blocks = mir_body.basic_blocks();
+        let bb_successors: IndexVec<BasicBlock, Vec<BasicBlock>> =
+            basic_blocks.iter().map(|data| filtered_successors(basic_blocks, data)).collect();
+
+        let reachable = reverse_postorder(&bb_successors);
+        let mut bb_predecessors = IndexVec::from_elem(Vec::new(), basic_blocks);
+        for &bb in &reachable {
+            for &successor in &bb_successors[bb] {
+                bb_predecessors[successor].push(bb);
+            }
+        }
+
+        // A block starts a new `BasicCoverageBlock` unless it is the only successor of its only
+        // predecessor.
+        let is_leader = |bb: BasicBlock| {
+            bb == START_BLOCK
+                || match bb_predecessors[bb][..] {
+                    [predecessor] => bb_successors[predecessor].len() != 1,
+                    _ => true,
+                }
+        };
+
+        let mut bcbs = IndexVec::with_capacity(reachable.len());
+        let mut bb_to_bcb = IndexVec::from_elem(None, basic_blocks);
+        for &leader in reachable.iter().filter(|&&bb| is_leader(bb)) {
+            let bcb = bcbs.next_index();
+            let mut bb = leader;
+            let mut chain = vec![leader];
+            bb_to_bcb[leader] = Some(bcb);
+            while let [successor] = bb_successors[bb][..] {
+                if is_leader(successor) {
+                    break;
+                }
+                chain.push(successor);
+                bb_to_bcb[successor] = Some(bcb);
+                bb = successor;
+            }
+            bcbs.push(BasicCoverageBlockData { basic_blocks: chain });
+        }
+
+        // The successors of a `BasicCoverageBlock`'s last block are all leaders, so the edges
+        // between `BasicCoverageBlock`s are the edges between their last and leader blocks.
+        let successors: IndexVec<BasicCoverageBlock, Vec<BasicCoverageBlock>> = bcbs
+            .iter()
+            .map(|data| {
+                bb_successors[data.last_bb()]
+                    .iter()
+                    .map(|&successor| bb_to_bcb[successor].expect("reachable block has a BCB"))
+                    .collect()
+            })
+            .collect();
+        let mut predecessors = IndexVec::from_elem(Vec::new(), &bcbs);
+        for (bcb, bcb_successors) in successors.iter_enumerated() {
+            for &successor in bcb_successors {
+                predecessors[successor].push(bcb);
+            }
+        }
+
+        let mut graph = CoverageGraph { bcbs, successors, predecessors, dominators: None };
+        graph.dominators = Some(dominators::dominators(&graph));
+        graph
+    }
+
+    crate fn iter_enumerated(
+        &self,
+    ) -> impl Iterator<Item = (BasicCoverageBlock, &BasicCoverageBlockData)> {
+        self.bcbs.iter_enumerated()
+    }
+
+    crate fn dominates(&self, dom: BasicCoverageBlock, node: BasicCoverageBlock) -> bool {
+        self.dominators.as_ref().unwrap().is_dominated_by(node, dom)
+    }
+}
+
+impl Index<BasicCoverageBlock> for CoverageGraph {
+    type Output = BasicCoverageBlockData;
+
+    #[inline]
+    fn index(&self, index: BasicCoverageBlock) -> &BasicCoverageBlockData {
+        &self.bcbs[index]
+    }
+}
+
+impl graph::DirectedGraph for CoverageGraph {
+    type Node = BasicCoverageBlock;
+}
+
+impl graph::WithNumNodes for CoverageGraph {
+    #[inline]
+    fn num_nodes(&self) -> usize {
+        self.bcbs.len()
+    }
+}
+
+impl graph::WithStartNode for CoverageGraph {
+    #[inline]
+    fn start_node(&self) -> Self::Node {
+        START_BCB
+    }
+}
+
+impl graph::WithSuccessors for CoverageGraph {
+    #[inline]
+    fn successors(&self, node: Self::Node) -> <Self as GraphSuccessors<'_>>::Iter {
+        self.successors[node].iter().cloned()
+    }
+}
+
+impl graph::GraphSuccessors<'graph> for CoverageGraph {
+    type Item = BasicCoverageBlock;
+    type Iter = std::iter::Cloned<slice::Iter<'graph, BasicCoverageBlock>>;
+}
+
+impl graph::WithPredecessors for CoverageGraph {
+    #[inline]
+    fn predecessors(&self, node: Self::Node) -> <Self as GraphPredecessors<'_>>::Iter {
+        self.predecessors[node].iter().cloned()
+    }
+}
+
+impl graph::GraphPredecessors<'graph> for CoverageGraph {
+    type Item = BasicCoverageBlock;
+    type Iter = std::iter::Cloned<slice::Iter<'graph, BasicCoverageBlock>>;
+}
+
+/// The successors of a block that can be taken without unwinding, without duplicates.
+fn filtered_successors(
+    basic_blocks: &IndexVec<BasicBlock, BasicBlockData<'_>>,
+    data: &BasicBlockData<'_>,
+) -> Vec<BasicBlock> {
+    let terminator = data.terminator();
+    let mut successors = Vec::new();
+    match terminator.kind {
+        // The imaginary target of a `FalseEdge`, and the unwind target of a `FalseUnwind`, only
+        // exist for borrowck and are never taken at runtime.
+        TerminatorKind::FalseEdge { real_target, .. }
+        | TerminatorKind::FalseUnwind { real_target, .. } => successors.push(real_target),
+        _ => {
+            for &successor in terminator.successors() {
+                if !basic_blocks[successor].is_cleanup && !successors.contains(&successor) {
+                    successors.push(successor);
+                }
+            }
+        }
+    }
+    successors
+}
+
+/// The blocks reachable from `START_BLOCK`, in reverse postorder.
+fn reverse_postorder(successors: &IndexVec<BasicBlock, Vec<BasicBlock>>) -> Vec<BasicBlock> {
+    let mut visited = BitSet::new_empty(successors.len());
+    let mut postorder = Vec::with_capacity(successors.len());
+    let mut stack = vec![(START_BLOCK, successors[START_BLOCK].iter())];
+    visited.insert(START_BLOCK);
+    loop {
+        let next = match stack.last_mut() {
+            Some((_, iter)) => iter.next(),
+            None => break,
+        };
+        match next {
+            Some(&successor) => {
+                if visited.insert(successor) {
+                    stack.push((successor, successors[successor].iter()));
+                }
+            }
+            None => {
+                let (bb, _) = stack.pop().unwrap();
+                postorder.push(bb);
+            }
+        }
+    }
+    postorder.reverse();
+    postorder
+}
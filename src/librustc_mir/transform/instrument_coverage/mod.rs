@@ -0,0 +1,201 @@
+//! Injects source code coverage counters, when compiled with `-Zinstrument-coverage`.
+//!
+//! The pass merges the blocks of a function's MIR into `BasicCoverageBlock`s (see `graph`),
+//! assigns each part of the function's source code to the `BasicCoverageBlock` that executes it
+//! (see `spans`), and then chooses which `BasicCoverageBlock`s need a counter, and which can have
+//! their counts computed from other counts (see `counters`). The counters and expressions are
+//! injected as `StatementKind::Coverage` statements, which codegen turns into calls to
+//! `llvm.instrprof.increment` and the LLVM coverage map.
+
+mod counters;
+mod graph;
+mod spans;
+
+use counters::BcbCounters;
+use graph::CoverageGraph;
+
+use crate::transform::{MirPass, MirSource};
+use rustc_data_structures::fingerprint::Fingerprint;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
+use rustc_hir as hir;
+use rustc_index::vec::IndexVec;
+use rustc_middle::hir::map::associated_body;
+use rustc_middle::ich::StableHashingContext;
+use rustc_middle::mir::coverage::*;
+use rustc_middle::mir::visit::Visitor;
+use rustc_middle::mir::{self, Location, Statement, StatementKind};
+use rustc_middle::ty::query::Providers;
+use rustc_middle::ty::TyCtxt;
+use rustc_span::def_id::DefId;
+use rustc_span::source_map::SourceMap;
+use rustc_span::{Pos, Span, Symbol};
+
+/// Inserts `StatementKind::Coverage` statements that count the executions of each part of a
+/// function's source code.
+pub struct InstrumentCoverage;
+
+/// The `query` provider for `CoverageInfo`, requested by codegen when constructing the arguments
+/// of `llvm.instrprof.increment` and the function's coverage map.
+pub(crate) fn provide(providers: &mut Providers<'_>) {
+    providers.coverageinfo = |tcx, def_id| coverageinfo_from_mir(tcx, def_id);
+}
+
+/// Finds the number of counters and expressions referenced by a MIR body. MIR optimizations may
+/// remove some of the injected statements (for example, in unreachable blocks), so the numbers
+/// are one more than the highest IDs referenced, including IDs only used as expression operands.
+struct CoverageVisitor {
+    num_counters: u32,
+    num_expressions: u32,
+}
+
+impl CoverageVisitor {
+    fn update_num_terms(&mut self, term: CoverageTerm) {
+        match term {
+            CoverageTerm::Zero => {}
+            CoverageTerm::Counter(id) => {
+                self.num_counters = self.num_counters.max(id.as_u32() + 1);
+            }
+            CoverageTerm::Expression(id) => {
+                self.num_expressions = self.num_expressions.max(id.as_u32() + 1);
+            }
+        }
+    }
+}
+
+impl Visitor<'_> for CoverageVisitor {
+    fn visit_coverage(&mut self, coverage: &Coverage, _location: Location) {
+        self.update_num_terms(coverage.kind.as_term());
+        if let CoverageKind::Expression { lhs, rhs, .. } = coverage.kind {
+            self.update_num_terms(lhs);
+            self.update_num_terms(rhs);
+        }
+    }
+}
+
+fn coverageinfo_from_mir<'tcx>(tcx: TyCtxt<'tcx>, def_id: DefId) -> CoverageInfo {
+    let mir_body = tcx.optimized_mir(def_id);
+    let mut coverage_visitor = CoverageVisitor { num_counters: 0, num_expressions: 0 };
+    coverage_visitor.visit_body(mir_body);
+    CoverageInfo {
+        num_counters: coverage_visitor.num_counters,
+        num_expressions: coverage_visitor.num_expressions,
+    }
+}
+
+impl<'tcx> MirPass<'tcx> for InstrumentCoverage {
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, src: MirSource<'tcx>, mir_body: &mut mir::Body<'tcx>) {
+        if tcx.sess.opts.debugging_opts.instrument_coverage {
+            // If the InstrumentCoverage pass is called on promoted MIRs, skip them.
+            // See: https://github.com/rust-lang/rust/pull/73011#discussion_r438317601
+            if src.promoted.is_some() {
+                return;
+            }
+            // The initializers of constants and statics are evaluated at compile time, and
+            // have no code to count.
+            let hir_id = tcx.hir().as_local_hir_id(src.def_id().expect_local());
+            if !tcx.hir().body_owner_kind(hir_id).is_fn_or_closure() {
+                return;
+            }
+            debug!(
+                "instrumenting {:?}, span: {}",
+                src.def_id(),
+                tcx.sess.source_map().span_to_string(mir_body.span)
+            );
+            Instrumentor::new(tcx, src.def_id(), mir_body).inject_counters();
+        }
+    }
+}
+
+struct Instrumentor<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    def_id: DefId,
+    mir_body: &'a mut mir::Body<'tcx>,
+}
+
+impl<'a, 'tcx> Instrumentor<'a, 'tcx> {
+    fn new(tcx: TyCtxt<'tcx>, def_id: DefId, mir_body: &'a mut mir::Body<'tcx>) -> Self {
+        Self { tcx, def_id, mir_body }
+    }
+
+    fn inject_counters(&mut self) {
+        let tcx = self.tcx;
+        let source_map = tcx.sess.source_map();
+        let hir_body = hir_body(tcx, self.def_id);
+        let body_span = hir_body.value.span;
+        let function_source_hash = hash_mir_source(tcx, hir_body);
+        let source_file = source_map.lookup_char_pos(body_span.lo()).file;
+        let file_name = Symbol::intern(&source_file.name.to_string());
+
+        let graph = CoverageGraph::from_mir(self.mir_body);
+        let coverage_spans = spans::coverage_spans(self.mir_body, body_span, &graph);
+
+        // All the code regions of a `BasicCoverageBlock` are counted by the block's counter or
+        // expression.
+        let mut counters = BcbCounters::new(self.mir_body, &graph, function_source_hash);
+        let mut code_regions: FxHashMap<_, Vec<_>> = FxHashMap::default();
+        for coverage_span in coverage_spans {
+            let code_region = make_code_region(source_map, file_name, coverage_span.span);
+            code_regions.entry(counters.term_for(coverage_span.bcb)).or_default().push(code_region);
+        }
+        let coverage_kinds = counters.into_coverage_kinds();
+
+        // Inject the counters and expressions at the start of each `BasicCoverageBlock`'s first
+        // MIR block, in the order they were created.
+        let mut new_statements = IndexVec::from_elem(Vec::new(), self.mir_body.basic_blocks());
+        for (bcb, kind) in coverage_kinds {
+            let bb = graph[bcb].leader_bb();
+            let code_regions = code_regions.remove(&kind.as_term()).unwrap_or_default();
+            debug!("injecting {:?} with regions {:?} into {:?}", kind, code_regions, bb);
+            let data = &self.mir_body[bb];
+            let source_info = data
+                .statements
+                .first()
+                .map_or(data.terminator().source_info, |statement| statement.source_info);
+            new_statements[bb].push(Statement {
+                source_info,
+                kind: StatementKind::Coverage(box Coverage { kind, code_regions }),
+            });
+        }
+        for (bb, mut statements) in new_statements.into_iter_enumerated() {
+            if !statements.is_empty() {
+                let data = &mut self.mir_body[bb];
+                statements.append(&mut data.statements);
+                data.statements = statements;
+            }
+        }
+    }
+}
+
+/// Converts a span to a `CodeRegion`, with 1-based lines and columns, and an exclusive end.
+fn make_code_region(source_map: &SourceMap, file_name: Symbol, span: Span) -> CodeRegion {
+    let start = source_map.lookup_char_pos(span.lo());
+    let end = source_map.lookup_char_pos(span.hi());
+    CodeRegion {
+        file_name,
+        start_line: start.line as u32,
+        start_col: start.col.to_u32() + 1,
+        end_line: end.line as u32,
+        end_col: end.col.to_u32() + 1,
+    }
+}
+
+fn hir_body<'tcx>(tcx: TyCtxt<'tcx>, def_id: DefId) -> &'tcx hir::Body<'tcx> {
+    let hir_node = tcx.hir().get_if_local(def_id).expect("DefId is local");
+    let fn_body_id = associated_body(hir_node).expect("HIR node is a function with body");
+    tcx.hir().body(fn_body_id)
+}
+
+fn hash_mir_source<'tcx>(tcx: TyCtxt<'tcx>, hir_body: &'tcx hir::Body<'tcx>) -> u64 {
+    let mut hcx = tcx.create_no_span_stable_hashing_context();
+    hash(&mut hcx, &hir_body.value).to_smaller_hash()
+}
+
+fn hash(
+    hcx: &mut StableHashingContext<'tcx>,
+    node: &impl HashStable<StableHashingContext<'tcx>>,
+) -> Fingerprint {
+    let mut stable_hasher = StableHasher::new();
+    node.hash_stable(hcx, &mut stable_hasher);
+    stable_hasher.finish()
+}
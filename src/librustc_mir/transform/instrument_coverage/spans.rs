@@ -0,0 +1,165 @@
+//! Assigns the source code of a function body to the `BasicCoverageBlock`s that execute it.
+
+use super::graph::{BasicCoverageBlock, CoverageGraph};
+
+use rustc_middle::mir::{
+    self, AggregateKind, FakeReadCause, Rvalue, Statement, StatementKind, Terminator,
+    TerminatorKind,
+};
+use rustc_span::source_map::original_sp;
+use rustc_span::{BytePos, Span};
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// A span of source code, and the `BasicCoverageBlock` whose count is the number of times the
+/// code in the span was executed.
+#[derive(Copy, Clone, Debug)]
+crate struct CoverageSpan {
+    crate span: Span,
+    crate bcb: BasicCoverageBlock,
+}
+
+/// Returns non-overlapping `CoverageSpan`s, ordered by position, covering the executable code of
+/// the function body in `body_span`.
+///
+/// Each statement and terminator of a `BasicCoverageBlock` contributes its span. Spans overlap
+/// where expressions nest, so the body is split at every span boundary, and each piece is
+/// assigned to the innermost (shortest) span containing it. Identical spans from different
+/// `BasicCoverageBlock`s go to the one with the lowest index, which is the dominator if either
+/// dominates the other. The bodies of closures and generators are left out: they are counted
+/// separately, when their own MIR is instrumented.
+crate fn coverage_spans(
+    mir_body: &mir::Body<'_>,
+    body_span: Span,
+    graph: &CoverageGraph,
+) -> Vec<CoverageSpan> {
+    let mut candidates = Vec::new();
+    let mut holes = Vec::new();
+    for (bcb, data) in graph.iter_enumerated() {
+        for &bb in &data.basic_blocks {
+            let bb_data = &mir_body[bb];
+            for statement in &bb_data.statements {
+                if let Some(span) = statement_span(statement, body_span) {
+                    if is_closure_or_generator(statement) {
+                        holes.push(span);
+                    } else {
+                        candidates.push(CoverageSpan { span, bcb });
+                    }
+                }
+            }
+            if let Some(span) = terminator_span(bb_data.terminator(), body_span) {
+                candidates.push(CoverageSpan { span, bcb });
+            }
+        }
+    }
+
+    let mut boundaries = Vec::with_capacity(2 * (candidates.len() + holes.len()));
+    for span in candidates.iter().map(|candidate| candidate.span).chain(holes.iter().copied()) {
+        boundaries.push(span.lo());
+        boundaries.push(span.hi());
+    }
+    boundaries.sort_unstable();
+    boundaries.dedup();
+    candidates.sort_by_key(|candidate| candidate.span.lo());
+    holes.sort_by_key(|hole| hole.lo());
+
+    let mut coverage_spans: Vec<CoverageSpan> = Vec::new();
+    // The candidates starting at or before the current piece, shortest first. Candidates ending
+    // before the current piece are only removed once they reach the top of the heap.
+    let mut active = BinaryHeap::new();
+    let mut next_candidate = 0;
+    let mut next_hole = 0;
+    let mut holes_end = BytePos(0);
+    for piece in boundaries.windows(2) {
+        let (lo, hi) = (piece[0], piece[1]);
+        while next_candidate < candidates.len() && candidates[next_candidate].span.lo() <= lo {
+            let CoverageSpan { span, bcb } = candidates[next_candidate];
+            active.push(Reverse((span.hi().0 - span.lo().0, bcb, next_candidate)));
+            next_candidate += 1;
+        }
+        while next_hole < holes.len() && holes[next_hole].lo() <= lo {
+            holes_end = holes_end.max(holes[next_hole].hi());
+            next_hole += 1;
+        }
+        if hi <= holes_end {
+            continue;
+        }
+
+        while let Some(&Reverse((_, _, index))) = active.peek() {
+            if candidates[index].span.hi() >= hi {
+                break;
+            }
+            active.pop();
+        }
+        let (bcb, index) = match active.peek() {
+            Some(&Reverse((_, bcb, index))) => (bcb, index),
+            None => continue,
+        };
+        match coverage_spans.last_mut() {
+            Some(last) if last.bcb == bcb && last.span.hi() == lo => {
+                last.span = last.span.with_hi(hi);
+            }
+            _ => {
+                let span = candidates[index].span.with_lo(lo).with_hi(hi);
+                coverage_spans.push(CoverageSpan { span, bcb });
+            }
+        }
+    }
+    coverage_spans
+}
+
+fn statement_span(statement: &Statement<'_>, body_span: Span) -> Option<Span> {
+    match statement.kind {
+        // These statements are not executable code.
+        StatementKind::StorageLive(_)
+        | StatementKind::StorageDead(_)
+        | StatementKind::Coverage(_)
+        | StatementKind::Nop => None,
+
+        // The span of a guard binding's `FakeRead` is the binding in the arm's pattern. It is
+        // read while the guard is evaluated, before the arm is known to match.
+        StatementKind::FakeRead(FakeReadCause::ForGuardBinding, _) => None,
+
+        _ => filtered_span(statement.source_info.span, body_span),
+    }
+}
+
+fn terminator_span(terminator: &Terminator<'_>, body_span: Span) -> Option<Span> {
+    match terminator.kind {
+        // The spans of these terminators are those of the enclosing expression (an `if`, `match`
+        // or loop, a scope ending in a drop, or an overflow check), not code executed by the
+        // terminator's block.
+        TerminatorKind::Unreachable
+        | TerminatorKind::Assert { .. }
+        | TerminatorKind::Drop { .. }
+        | TerminatorKind::DropAndReplace { .. }
+        | TerminatorKind::SwitchInt { .. }
+        | TerminatorKind::FalseEdge { .. }
+        | TerminatorKind::FalseUnwind { .. }
+        | TerminatorKind::Goto { .. } => None,
+
+        // The span of a method call includes its receiver, which is evaluated by an earlier
+        // block.
+        TerminatorKind::Call { fn_span, .. } => filtered_span(fn_span, body_span),
+
+        _ => filtered_span(terminator.source_info.span, body_span),
+    }
+}
+
+/// Maps spans from macro expansions to the macro call in the function body, and drops spans that
+/// are empty or outside the body.
+fn filtered_span(span: Span, body_span: Span) -> Option<Span> {
+    let span = original_sp(span, body_span);
+    if span.lo() < span.hi() && body_span.contains(span) { Some(span) } else { None }
+}
+
+fn is_closure_or_generator(statement: &Statement<'_>) -> bool {
+    match statement.kind {
+        StatementKind::Assign(box (_, Rvalue::Aggregate(box ref kind, _))) => match kind {
+            AggregateKind::Closure(..) | AggregateKind::Generator(..) => true,
+            _ => false,
+        },
+        _ => false,
+    }
+}
@@ -1,9 +1,14 @@
+use rustc_ast::ast;
 use rustc_ast::token::{self, Token, TokenKind};
 use rustc_ast::util::comments;
 use rustc_data_structures::sync::Lrc;
 use rustc_errors::{error_code, Applicability, DiagnosticBuilder, FatalError};
 use rustc_lexer::Base;
 use rustc_lexer::{unescape, RawStrError};
+use rustc_session::lint::builtin::{
+    TEXT_DIRECTION_CODEPOINT_IN_COMMENT, TEXT_DIRECTION_CODEPOINT_IN_LITERAL,
+};
+use rustc_session::lint::BuiltinLintDiagnostics;
 use rustc_session::parse::ParseSess;
 use rustc_span::symbol::{sym, Symbol};
 use rustc_span::{BytePos, Pos, Span};
@@ -170,6 +175,12 @@ impl<'a> StringReader<'a> {
         match token {
             rustc_lexer::TokenKind::LineComment => {
                 let string = self.str_from(start);
+                self.lint_text_flow_control_chars(
+                    start,
+                    string,
+                    true,
+                    Applicability::MachineApplicable,
+                );
                 // comments with only more "/"s are not doc comments
                 if comments::is_line_doc_comment(string) {
                     self.forbid_bare_cr(start, string, "bare CR not allowed in doc-comment");
@@ -202,6 +213,13 @@ impl<'a> StringReader<'a> {
                     FatalError.raise();
                 }
 
+                self.lint_text_flow_control_chars(
+                    start,
+                    string,
+                    true,
+                    Applicability::MachineApplicable,
+                );
+
                 if is_doc_comment {
                     self.forbid_bare_cr(start, string, "bare CR not allowed in block doc-comment");
                     token::DocComment(Symbol::intern(string))
@@ -230,6 +248,18 @@ impl<'a> StringReader<'a> {
             rustc_lexer::TokenKind::Literal { kind, suffix_start } => {
                 let suffix_start = start + BytePos(suffix_start as u32);
                 let (kind, symbol) = self.cook_lexer_literal(start, suffix_start, kind);
+                // Escapes are not processed in raw strings, so replacing the codepoints with
+                // escapes there would change the value of the literal.
+                let applicability = match kind {
+                    token::StrRaw(_) | token::ByteStrRaw(_) => Applicability::MaybeIncorrect,
+                    _ => Applicability::MachineApplicable,
+                };
+                self.lint_text_flow_control_chars(
+                    start,
+                    self.str_from_to(start, suffix_start),
+                    false,
+                    applicability,
+                );
                 let suffix = if suffix_start < self.pos {
                     let string = self.str_from(suffix_start);
                     if string == "_" {
@@ -458,6 +488,37 @@ impl<'a> StringReader<'a> {
         &self.src[self.src_index(start)..self.src_index(end)]
     }
 
+    /// Buffers a lint if the comment or literal `s` starting at `start` contains
+    /// bidirectional text flow control codepoints.
+    fn lint_text_flow_control_chars(
+        &self,
+        start: BytePos,
+        s: &str,
+        is_comment: bool,
+        applicability: Applicability,
+    ) {
+        // Tokens with an overridden span don't come from the source text, so there are no
+        // codepoint positions to point at.
+        if self.override_span.is_some() || !rustc_lexer::contains_text_flow_control_chars(s) {
+            return;
+        }
+        let span = self.mk_sp(start, start + BytePos::from_usize(s.len()));
+        let (lint, what) = if is_comment {
+            (&TEXT_DIRECTION_CODEPOINT_IN_COMMENT, "comment")
+        } else {
+            (&TEXT_DIRECTION_CODEPOINT_IN_LITERAL, "literal")
+        };
+        // The tokens don't belong to any node yet: the lint is attached to the innermost node
+        // containing `span` once the crate is expanded, so that its lint attributes apply.
+        self.sess.buffer_lint_with_diagnostic(
+            lint,
+            span,
+            ast::DUMMY_NODE_ID,
+            &format!("unicode codepoint changing visible direction of text present in {}", what),
+            BuiltinLintDiagnostics::UnicodeTextFlow(span, s.to_string(), applicability),
+        );
+    }
+
     fn forbid_bare_cr(&self, start: BytePos, s: &str, errmsg: &str) {
         let mut idx = 0;
         loop {
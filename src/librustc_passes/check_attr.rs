@@ -67,6 +67,10 @@ impl CheckAttrVisitor<'tcx> {
                 self.check_target_feature(attr, span, target)
             } else if attr.check_name(sym::track_caller) {
                 self.check_track_caller(&attr.span, attrs, span, target)
+            } else if attr.check_name(sym::no_panic) {
+                self.check_no_panic(attr, span, target)
+            } else if attr.check_name(sym::stack_limit) {
+                self.check_stack_limit(attr, span, target)
             } else {
                 true
             };
@@ -164,6 +168,38 @@ impl CheckAttrVisitor<'tcx> {
         }
     }
 
+    /// Checks if a `#[no_panic]` is applied to a function with a body. Returns `true` if valid.
+    fn check_no_panic(&self, attr: &Attribute, span: &Span, target: Target) -> bool {
+        match target {
+            Target::Fn
+            | Target::Method(MethodKind::Trait { body: true } | MethodKind::Inherent) => true,
+            _ => {
+                self.tcx
+                    .sess
+                    .struct_span_err(attr.span, "attribute should be applied to a function body")
+                    .span_label(*span, "not a function with a body")
+                    .emit();
+                false
+            }
+        }
+    }
+
+    /// Checks if a `#[stack_limit]` is applied to a function with a body. Returns `true` if valid.
+    fn check_stack_limit(&self, attr: &Attribute, span: &Span, target: Target) -> bool {
+        match target {
+            Target::Fn
+            | Target::Method(MethodKind::Trait { body: true } | MethodKind::Inherent) => true,
+            _ => {
+                self.tcx
+                    .sess
+                    .struct_span_err(attr.span, "attribute should be applied to a function body")
+                    .span_label(*span, "not a function with a body")
+                    .emit();
+                false
+            }
+        }
+    }
+
     /// Checks if the `#[non_exhaustive]` attribute on an `item` is valid. Returns `true` if valid.
     fn check_non_exhaustive(&self, attr: &Attribute, span: &Span, target: Target) -> bool {
         match target {
@@ -5,13 +5,14 @@
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_hir as hir;
 use rustc_hir::def::{CtorOf, DefKind, Res};
-use rustc_hir::def_id::{DefId, LOCAL_CRATE};
+use rustc_hir::def_id::{CrateNum, DefId, CRATE_DEF_INDEX, LOCAL_CRATE};
 use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
 use rustc_hir::itemlikevisit::ItemLikeVisitor;
 use rustc_hir::{Node, PatKind, TyKind};
 use rustc_middle::hir::map::Map;
 use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use rustc_middle::middle::privacy;
+use rustc_middle::ty::query::Providers;
 use rustc_middle::ty::{self, DefIdTree, TyCtxt};
 use rustc_session::lint;
 
@@ -692,4 +693,248 @@ pub fn check_crate(tcx: TyCtxt<'_>) {
     let live_symbols = find_live(tcx, access_levels, krate);
     let mut visitor = DeadVisitor { tcx, live_symbols };
     intravisit::walk_crate(&mut visitor, krate);
+
+    if let Some(crate_names) = &tcx.sess.opts.debugging_opts.report_unused_pub_items {
+        check_unused_pub_items(tcx, crate_names);
+    }
+}
+
+/// Collects the items that the code of the local crate refers to, whether that code is live or
+/// not: removing an item referenced from dead code breaks the build all the same.
+struct ReferencedItemsVisitor<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    tables: &'a ty::TypeckTables<'tcx>,
+    referenced_items: FxHashSet<DefId>,
+}
+
+impl ReferencedItemsVisitor<'_, '_> {
+    fn handle_res(&mut self, res: Res) {
+        match res {
+            Res::Def(DefKind::Ctor(CtorOf::Variant, ..), ctor_def_id) => {
+                // The enum is only named by the path of the variant.
+                let variant_id = self.tcx.parent(ctor_def_id).unwrap();
+                self.referenced_items.insert(self.tcx.parent(variant_id).unwrap());
+                self.referenced_items.insert(ctor_def_id);
+            }
+            Res::Def(_, def_id) => {
+                self.referenced_items.insert(def_id);
+            }
+            Res::SelfTy(t, i) => self.referenced_items.extend(t.into_iter().chain(i)),
+            _ => {}
+        }
+    }
+
+    /// Records the item that a type-relative path, a method call or an overloaded operator
+    /// resolves to.
+    fn handle_type_dependent_def(&mut self, hir_id: hir::HirId) {
+        if let Some(def_id) = self.tables.type_dependent_def_id(hir_id) {
+            self.referenced_items.insert(def_id);
+        }
+    }
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for ReferencedItemsVisitor<'a, 'tcx> {
+    type Map = Map<'tcx>;
+
+    fn nested_visit_map(&mut self) -> intravisit::NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::All(self.tcx.hir())
+    }
+
+    fn visit_nested_body(&mut self, body: hir::BodyId) {
+        let old_tables = self.tables;
+        self.tables = self.tcx.body_tables(body);
+        let body = self.tcx.hir().body(body);
+        self.visit_body(body);
+        self.tables = old_tables;
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        self.handle_type_dependent_def(expr.hir_id);
+        intravisit::walk_expr(self, expr);
+    }
+
+    fn visit_pat(&mut self, pat: &'tcx hir::Pat<'tcx>) {
+        self.handle_type_dependent_def(pat.hir_id);
+        intravisit::walk_pat(self, pat);
+    }
+
+    fn visit_path(&mut self, path: &'tcx hir::Path<'tcx>, _: hir::HirId) {
+        self.handle_res(path.res);
+        intravisit::walk_path(self, path);
+    }
+
+    fn visit_path_segment(
+        &mut self,
+        span: rustc_span::Span,
+        segment: &'tcx hir::PathSegment<'tcx>,
+    ) {
+        // The modules, types and traits that the path goes through.
+        if let Some(res) = segment.res {
+            self.handle_res(res);
+        }
+        intravisit::walk_path_segment(self, span, segment);
+    }
+}
+
+fn referenced_items(tcx: TyCtxt<'_>, cnum: CrateNum) -> Option<&[DefId]> {
+    assert_eq!(cnum, LOCAL_CRATE);
+    let mut visitor = ReferencedItemsVisitor {
+        tcx,
+        tables: &ty::TypeckTables::empty(None),
+        referenced_items: Default::default(),
+    };
+    intravisit::walk_crate(&mut visitor, tcx.hir().krate());
+
+    let mut referenced: Vec<_> = visitor.referenced_items.into_iter().collect();
+    // Sort the items so that they're encoded in the crate metadata in a stable order.
+    referenced.sort_by_cached_key(|&def_id| tcx.def_path_hash(def_id));
+    Some(tcx.arena.alloc_from_iter(referenced))
+}
+
+/// Reports the public items of the crates in `crate_names` that neither the local crate nor
+/// any of its dependencies reference, according to the records of `referenced_items`.
+///
+/// Unlike the rest of this pass, which has to assume that exported items are used
+/// elsewhere, this can see all uses of the items of crates that are only ever used within
+/// a workspace, if every crate of the workspace is a dependency of the local crate.
+///
+/// The items belong to other crates, so the lint level of the local crate root applies to them,
+/// unless the items or their modules have `#[allow(unused_pub_items)]` in their own crate.
+fn check_unused_pub_items(tcx: TyCtxt<'_>, crate_names: &[String]) {
+    let mut referenced = FxHashSet::default();
+    let mut checked_crates = vec![];
+    for &cnum in std::iter::once(&LOCAL_CRATE).chain(tcx.crates()) {
+        let items = tcx.referenced_items(cnum);
+        if let Some(items) = items {
+            referenced.extend(items.iter().copied());
+        }
+
+        let name = tcx.crate_name(cnum);
+        if cnum == LOCAL_CRATE || !crate_names.iter().any(|n| *n == *name.as_str()) {
+            continue;
+        }
+        if items.is_none() {
+            // The crate may use any item of itself or of the other crates.
+            tcx.sess.warn(&format!(
+                "not looking for unused public items, as crate `{}` was compiled \
+                 without `-Z record-item-uses`",
+                name
+            ));
+            return;
+        }
+        checked_crates.push(cnum);
+    }
+
+    let any_item_referenced =
+        |def_id: DefId| tcx.associated_item_def_ids(def_id).iter().any(|i| referenced.contains(i));
+    let is_referenced = |def_id: DefId| {
+        if referenced.contains(&def_id) {
+            return true;
+        }
+        match tcx.def_kind(def_id) {
+            // A trait may only ever be used through calls of its methods.
+            DefKind::Trait => any_item_referenced(def_id),
+            DefKind::Struct | DefKind::Enum | DefKind::Union => {
+                // Tuple structs may only ever be named through their constructor.
+                let adt = tcx.adt_def(def_id);
+                if adt.is_struct() {
+                    if let Some(ctor_def_id) = adt.non_enum_variant().ctor_def_id {
+                        if referenced.contains(&ctor_def_id) {
+                            return true;
+                        }
+                    }
+                }
+                // As in `DeadVisitor::symbol_is_live`, a type whose inherent items are used
+                // is used, too.
+                tcx.inherent_impls(def_id).iter().any(|&impl_did| any_item_referenced(impl_did))
+            }
+            _ => false,
+        }
+    };
+    let report = |def_id: DefId| {
+        if allows_unused_pub_items(tcx, def_id) {
+            return;
+        }
+        tcx.struct_span_lint_hir(
+            lint::builtin::UNUSED_PUB_ITEMS,
+            hir::CRATE_HIR_ID,
+            tcx.def_span(def_id),
+            |lint| {
+                let descr = tcx.def_kind(def_id).descr(def_id);
+                lint.build(&format!(
+                    "public {} `{}` is never used by any crate",
+                    descr,
+                    tcx.def_path_str(def_id)
+                ))
+                .emit()
+            },
+        );
+    };
+
+    for cnum in checked_crates {
+        let mut seen = FxHashSet::default();
+        let mut modules = vec![DefId { krate: cnum, index: CRATE_DEF_INDEX }];
+        while let Some(module) = modules.pop() {
+            for child in tcx.item_children(module) {
+                let (kind, def_id) = match child.res {
+                    // Re-exports of other crates' items are checked along with those crates.
+                    Res::Def(kind, def_id) if def_id.krate == cnum => (kind, def_id),
+                    _ => continue,
+                };
+                if child.vis != ty::Visibility::Public || !seen.insert(def_id) {
+                    continue;
+                }
+                match kind {
+                    DefKind::Mod => modules.push(def_id),
+                    DefKind::Static
+                    | DefKind::Const
+                    | DefKind::Fn
+                    | DefKind::TyAlias
+                    | DefKind::Trait => {
+                        if !is_referenced(def_id) {
+                            report(def_id);
+                        }
+                    }
+                    DefKind::Struct | DefKind::Enum | DefKind::Union => {
+                        if !is_referenced(def_id) {
+                            report(def_id);
+                            continue;
+                        }
+                        for &impl_did in tcx.inherent_impls(def_id) {
+                            for &item in tcx.associated_item_def_ids(impl_did) {
+                                let is_pub = tcx.visibility(item) == ty::Visibility::Public;
+                                if is_pub && !referenced.contains(&item) {
+                                    report(item);
+                                }
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// Whether `def_id`, or one of the items or modules it is in, has `#[allow(unused_pub_items)]`.
+fn allows_unused_pub_items(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    let lint_name = lint::builtin::UNUSED_PUB_ITEMS.name_lower();
+    let mut def_id = Some(def_id);
+    while let Some(id) = def_id {
+        let allowed = tcx.get_attrs(id).iter().any(|attr| {
+            attr.check_name(sym::allow)
+                && attr.meta_item_list().map_or(false, |list| {
+                    list.iter().any(|item| item.ident().map_or(false, |i| i.as_str() == lint_name))
+                })
+        });
+        if allowed {
+            return true;
+        }
+        def_id = tcx.parent(id);
+    }
+    false
+}
+
+pub fn provide(providers: &mut Providers<'_>) {
+    *providers = Providers { referenced_items, ..*providers };
 }
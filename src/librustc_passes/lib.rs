@@ -39,6 +39,7 @@ mod weak_lang_items;
 pub fn provide(providers: &mut Providers<'_>) {
     check_attr::provide(providers);
     check_const::provide(providers);
+    dead::provide(providers);
     diagnostic_items::provide(providers);
     entry::provide(providers);
     lang_items::provide(providers);
@@ -862,6 +862,15 @@ pub struct WorkProduct {
     pub cgu_name: String,
     /// Saved file associated with this CGU.
     pub saved_file: Option<String>,
+    /// Saved split DWARF object file associated with this CGU.
+    pub saved_dwarf_file: Option<String>,
+}
+
+impl WorkProduct {
+    /// Iterates over the names of all saved files associated with this CGU.
+    pub fn saved_files(&self) -> impl Iterator<Item = &String> {
+        self.saved_file.iter().chain(self.saved_dwarf_file.iter())
+    }
 }
 
 #[derive(Clone)]
@@ -5,12 +5,13 @@ pub use crate::options::*;
 
 use crate::lint;
 use crate::search_paths::SearchPath;
-use crate::utils::NativeLibKind;
-use crate::{early_error, early_warn, Session};
+use crate::utils::{NativeLibKind, NativeLibModifiers};
+use crate::{early_error, early_warn, filesearch, Session};
 
 use rustc_data_structures::fx::FxHashSet;
 use rustc_data_structures::impl_stable_hash_via_hash;
 use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
+use rustc_data_structures::sync::Lrc;
 
 use rustc_target::spec::{Target, TargetTriple};
 
@@ -22,7 +23,7 @@ use rustc_span::symbol::{sym, Symbol};
 use rustc_span::SourceFileHashAlgorithm;
 
 use rustc_errors::emitter::HumanReadableErrorType;
-use rustc_errors::{ColorConfig, HandlerFlags};
+use rustc_errors::{Catalogue, ColorConfig, HandlerFlags};
 
 use std::collections::btree_map::{
     Iter as BTreeMapIter, Keys as BTreeMapKeysIter, Values as BTreeMapValuesIter,
@@ -45,6 +46,7 @@ bitflags! {
         const LEAK    = 1 << 1;
         const MEMORY  = 1 << 2;
         const THREAD  = 1 << 3;
+        const CFI     = 1 << 4;
     }
 }
 
@@ -58,6 +60,7 @@ impl fmt::Display for SanitizerSet {
                 SanitizerSet::LEAK => "leak",
                 SanitizerSet::MEMORY => "memory",
                 SanitizerSet::THREAD => "thread",
+                SanitizerSet::CFI => "cfi",
                 _ => panic!("unrecognized sanitizer {:?}", s),
             };
             if !first {
@@ -75,12 +78,18 @@ impl IntoIterator for SanitizerSet {
     type IntoIter = std::vec::IntoIter<SanitizerSet>;
 
     fn into_iter(self) -> Self::IntoIter {
-        [SanitizerSet::ADDRESS, SanitizerSet::LEAK, SanitizerSet::MEMORY, SanitizerSet::THREAD]
-            .iter()
-            .copied()
-            .filter(|&s| self.contains(s))
-            .collect::<Vec<_>>()
-            .into_iter()
+        [
+            SanitizerSet::ADDRESS,
+            SanitizerSet::LEAK,
+            SanitizerSet::MEMORY,
+            SanitizerSet::THREAD,
+            SanitizerSet::CFI,
+        ]
+        .iter()
+        .copied()
+        .filter(|&s| self.contains(s))
+        .collect::<Vec<_>>()
+        .into_iter()
     }
 }
 
@@ -103,6 +112,31 @@ pub enum Strip {
     Symbols,
 }
 
+/// The different settings that the `-Z debuginfo-compression` flag can have.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum DebugInfoCompression {
+    /// Do not compress debuginfo sections.
+    None,
+
+    /// Compress debuginfo sections with zlib.
+    Zlib,
+}
+
+/// The different settings that the `-C split-debuginfo` flag can have.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum SplitDebuginfo {
+    /// Keep debuginfo in the object files, and link it into the output.
+    Off,
+
+    /// Split debuginfo into a `.dwo` file per object file, and package those
+    /// into a single `.dwp` file next to the linked output.
+    Packed,
+
+    /// Split debuginfo into a `.dwo` file per object file, and leave them in
+    /// the output directory.
+    Unpacked,
+}
+
 /// The different settings that the `-Z control-flow-guard` flag can have.
 #[derive(Clone, Copy, PartialEq, Hash, Debug)]
 pub enum CFGuard {
@@ -503,6 +537,7 @@ impl_stable_hash_via_hash!(OutputFilenames);
 
 pub const RLINK_EXT: &str = "rlink";
 pub const RUST_CGU_EXT: &str = "rcgu";
+pub const DWARF_OBJECT_EXT: &str = "dwo";
 
 impl OutputFilenames {
     pub fn new(
@@ -558,6 +593,21 @@ impl OutputFilenames {
         self.with_extension(&extension)
     }
 
+    /// Gets the path of the split DWARF object file of the given codegen unit, or `None` if
+    /// debuginfo is not split.
+    pub fn split_dwarf_path(
+        &self,
+        split_debuginfo: SplitDebuginfo,
+        codegen_unit_name: Option<&str>,
+    ) -> Option<PathBuf> {
+        match split_debuginfo {
+            SplitDebuginfo::Off => None,
+            SplitDebuginfo::Packed | SplitDebuginfo::Unpacked => {
+                Some(self.temp_path_ext(DWARF_OBJECT_EXT, codegen_unit_name))
+            }
+        }
+    }
+
     pub fn with_extension(&self, extension: &str) -> PathBuf {
         let mut path = self.out_directory.join(&self.filestem);
         path.set_extension(extension);
@@ -584,7 +634,10 @@ impl Default for Options {
             optimize: OptLevel::No,
             debuginfo: DebugInfo::None,
             lint_opts: Vec::new(),
+            lint_config: LintConfig::default(),
             lint_cap: None,
+            translation_lang: None,
+            translation_catalogue: None,
             describe_lints: false,
             output_types: OutputTypes(BTreeMap::new()),
             search_paths: vec![],
@@ -659,6 +712,7 @@ impl DebuggingOptions {
             report_delayed_bugs: self.report_delayed_bugs,
             macro_backtrace: self.macro_backtrace,
             deduplicate_diagnostics: self.deduplicate_diagnostics,
+            apply_suggestions: self.apply_suggestions,
         }
     }
 }
@@ -929,8 +983,11 @@ pub fn rustc_short_optgroups() -> Vec<RustcOptGroup> {
             "",
             "Link the generated crate(s) to the specified native
                              library NAME. The optional KIND can be one of
-                             static, framework, or dylib (the default).",
-            "[KIND=]NAME",
+                             static, framework, or dylib (the default).
+                             Optional comma separated MODIFIERS may be specified
+                             each with a prefix of either '+' to enable or '-' to
+                             disable.",
+            "[KIND[:MODIFIERS]=]NAME[:RENAME]",
         ),
         make_crate_type_option(),
         opt::opt_s("", "crate-name", "Specify the name of the crate being built", "NAME"),
@@ -1073,13 +1130,121 @@ pub fn get_cmd_lint_options(
         .map(|(_, lint_name, level)| (lint_name, level))
         .collect();
 
-    let lint_cap = matches.opt_str("cap-lints").map(|cap| {
-        lint::Level::from_str(&cap)
-            .unwrap_or_else(|| early_error(error_format, &format!("unknown lint level: `{}`", cap)))
+    // Lints can't be capped at `expect`, which only applies to the code it's attached to.
+    let lint_cap = matches.opt_str("cap-lints").map(|cap| match lint::Level::from_str(&cap) {
+        Some(lint::Expect) | None => {
+            early_error(error_format, &format!("unknown lint level: `{}`", cap))
+        }
+        Some(level) => level,
     });
     (lint_opts, describe_lints, lint_cap)
 }
 
+/// Lint levels read from the file given with `-Z lint-config`, in the order they appear in it.
+///
+/// Like `Options::lint_opts`, later entries override earlier ones, so that a lint group can be
+/// set before individual lints of the group. Unlike `lint_opts`, the order is tracked, as
+/// the file may be reordered without changing any of its entries.
+#[derive(Clone, Debug, Default, Hash)]
+pub struct LintConfig(pub Vec<(String, lint::Level)>);
+
+/// Parses the lint config file of `-Z lint-config`. Each line of the file sets the level of
+/// a lint, lint group or tool lint, e.g. `unused-imports = "deny"` or `clippy::all = "warn"`.
+/// Blank lines and lines starting with `#` are ignored.
+pub fn parse_lint_config(path: &Path, error_format: ErrorOutputType) -> LintConfig {
+    let src = std::fs::read_to_string(path).unwrap_or_else(|e| {
+        early_error(
+            error_format,
+            &format!("failed to read lint config file `{}`: {}", path.display(), e),
+        )
+    });
+
+    let mut levels = vec![];
+    for (i, line) in src.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match parse_lint_config_line(line) {
+            Ok(level) => levels.push(level),
+            Err(msg) => {
+                early_error(error_format, &format!("{}:{}: {}", path.display(), i + 1, msg))
+            }
+        }
+    }
+    LintConfig(levels)
+}
+
+/// Resolves the language of `-Z translation-lang`. `system` stands for the language of the
+/// current locale, which is read here so that it is tracked like the other options.
+fn parse_translation_lang(debugging_opts: &DebuggingOptions) -> Option<String> {
+    match debugging_opts.translation_lang.as_deref()? {
+        "system" => rustc_errors::translation::locale_from_env(),
+        lang => Some(lang.to_string()),
+    }
+}
+
+/// Loads the message catalogue of `lang`, from the directory given with `-Z translation-dir` or
+/// else from `<sysroot>/share/locale`. Diagnostics stay in English if there is no language or
+/// its catalogue couldn't be loaded.
+fn load_translation_catalogue(
+    debugging_opts: &DebuggingOptions,
+    lang: Option<&str>,
+    sysroot: Option<&Path>,
+    error_format: ErrorOutputType,
+) -> Option<Lrc<Catalogue>> {
+    let lang = lang?;
+    let from_locale = debugging_opts.translation_lang.as_deref() == Some("system");
+    let base = lang.split('-').next().unwrap_or(lang);
+    if base == "en" {
+        return None;
+    }
+    let dir = match (&debugging_opts.translation_dir, sysroot) {
+        (Some(dir), _) => dir.clone(),
+        (None, Some(sysroot)) => sysroot.join("share").join("locale"),
+        (None, None) => filesearch::get_or_default_sysroot().join("share").join("locale"),
+    };
+    // Most locales have no catalogue; that's only worth a warning if it was asked for explicitly.
+    if from_locale && !dir.join(lang).is_dir() && !dir.join(base).is_dir() {
+        return None;
+    }
+    match Catalogue::load(&dir, lang) {
+        Ok(catalogue) => Some(Lrc::new(catalogue)),
+        Err(err) => {
+            early_warn(error_format, &format!("failed to load translations: {}", err));
+            None
+        }
+    }
+}
+
+fn parse_lint_config_line(line: &str) -> Result<(String, lint::Level), String> {
+    let unquote = |s: &str| {
+        let s = s.trim();
+        if s.len() >= 2 && s.starts_with('"') && s.ends_with('"') {
+            s[1..s.len() - 1].to_string()
+        } else {
+            s.to_string()
+        }
+    };
+
+    let eq = line.find('=').ok_or_else(|| "expected `<lint> = \"<level>\"`".to_string())?;
+    let lint_name = unquote(&line[..eq]);
+    let level = unquote(&line[eq + 1..]);
+    let valid_name = lint_name.split("::").all(|part| {
+        !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    });
+    if !valid_name {
+        return Err(format!("invalid lint name `{}`", lint_name));
+    }
+    match lint::Level::from_str(&level) {
+        Some(lint::Expect) | None => Err(format!(
+            "unknown lint level `{}`, expected one of `allow`, `warn`, `deny` or `forbid`",
+            level
+        )),
+        Some(level) => Ok((lint_name.replace("-", "_"), level)),
+    }
+}
+
 /// Parses the `--color` flag.
 pub fn parse_color(matches: &getopts::Matches) -> ColorConfig {
     match matches.opt_str("color").as_ref().map(|s| &s[..]) {
@@ -1475,30 +1640,36 @@ fn select_debuginfo(
 fn parse_libs(
     matches: &getopts::Matches,
     error_format: ErrorOutputType,
-) -> Vec<(String, Option<String>, NativeLibKind)> {
+) -> Vec<(String, Option<String>, NativeLibKind, NativeLibModifiers)> {
     matches
         .opt_strs("l")
         .into_iter()
         .map(|s| {
-            // Parse string of the form "[KIND=]lib[:new_name]",
+            // Parse string of the form "[KIND[:MODIFIERS]=]lib[:new_name]",
             // where KIND is one of "dylib", "framework", "static".
             let mut parts = s.splitn(2, '=');
             let kind = parts.next().unwrap();
-            let (name, kind) = match (parts.next(), kind) {
-                (None, name) => (name, NativeLibKind::Unspecified),
-                (Some(name), "dylib") => (name, NativeLibKind::Dylib),
-                (Some(name), "framework") => (name, NativeLibKind::Framework),
-                (Some(name), "static") => (name, NativeLibKind::StaticBundle),
-                (Some(name), "static-nobundle") => (name, NativeLibKind::StaticNoBundle),
-                (_, s) => {
-                    early_error(
-                        error_format,
-                        &format!(
-                            "unknown library kind `{}`, expected \
-                             one of dylib, framework, or static",
-                            s
-                        ),
-                    );
+            let (name, mut kind, modifiers) = match parts.next() {
+                None => (kind, NativeLibKind::Unspecified, None),
+                Some(name) => {
+                    let mut kind_parts = kind.splitn(2, ':');
+                    let kind = match kind_parts.next().unwrap() {
+                        "dylib" => NativeLibKind::Dylib,
+                        "framework" => NativeLibKind::Framework,
+                        "static" => NativeLibKind::StaticBundle,
+                        "static-nobundle" => NativeLibKind::StaticNoBundle,
+                        s => {
+                            early_error(
+                                error_format,
+                                &format!(
+                                    "unknown library kind `{}`, expected \
+                                     one of dylib, framework, or static",
+                                    s
+                                ),
+                            );
+                        }
+                    };
+                    (name, kind, kind_parts.next())
                 }
             };
             if kind == NativeLibKind::StaticNoBundle && !nightly_options::is_nightly_build() {
@@ -1508,10 +1679,23 @@ fn parse_libs(
                      accepted on the nightly compiler",
                 );
             }
+            let modifiers = match modifiers {
+                Some(modifiers) => {
+                    if !nightly_options::is_nightly_build() {
+                        early_error(
+                            error_format,
+                            "linking modifiers are only accepted on the nightly compiler",
+                        );
+                    }
+                    NativeLibModifiers::parse(&mut kind, modifiers)
+                        .unwrap_or_else(|msg| early_error(error_format, &msg))
+                }
+                None => NativeLibModifiers::default(),
+            };
             let mut name_parts = name.splitn(2, ':');
             let name = name_parts.next().unwrap();
             let new_name = name_parts.next();
-            (name.to_owned(), new_name.map(|n| n.to_owned()), kind)
+            (name.to_owned(), new_name.map(|n| n.to_owned()), kind, modifiers)
         })
         .collect()
 }
@@ -1667,6 +1851,13 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
     let mut debugging_opts = build_debugging_options(matches, error_format);
     check_debug_option_stability(&debugging_opts, error_format, json_rendered);
 
+    let lint_config = match &debugging_opts.lint_config {
+        Some(path) => parse_lint_config(path, error_format),
+        None => LintConfig::default(),
+    };
+
+    let translation_lang = parse_translation_lang(&debugging_opts);
+
     let output_types = parse_output_types(&debugging_opts, matches, error_format);
 
     let mut cg = build_codegen_options(matches, error_format);
@@ -1705,6 +1896,21 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         );
     }
 
+    if cg.profile_sample_use.is_some() {
+        if cg.profile_generate.enabled() {
+            early_error(
+                error_format,
+                "options `-C profile-generate` and `-C profile-sample-use` are exclusive",
+            );
+        }
+        if cg.profile_use.is_some() {
+            early_error(
+                error_format,
+                "options `-C profile-use` and `-C profile-sample-use` are exclusive",
+            );
+        }
+    }
+
     if !cg.embed_bitcode {
         match cg.lto {
             LtoCli::No | LtoCli::Unspecified => {}
@@ -1720,6 +1926,12 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
     let cg = cg;
 
     let sysroot_opt = matches.opt_str("sysroot").map(|m| PathBuf::from(&m));
+    let translation_catalogue = load_translation_catalogue(
+        &debugging_opts,
+        translation_lang.as_deref(),
+        sysroot_opt.as_deref(),
+        error_format,
+    );
     let target_triple = parse_target_triple(matches, error_format);
     let opt_level = parse_opt_level(matches, &cg, error_format);
     // The `-g` and `-C debuginfo` flags specify the same setting, so we want to be able
@@ -1756,7 +1968,10 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         optimize: opt_level,
         debuginfo,
         lint_opts,
+        lint_config,
         lint_cap,
+        translation_lang,
+        translation_catalogue,
         describe_lints,
         output_types,
         search_paths,
@@ -2020,16 +2235,18 @@ impl PpMode {
 /// how the hash should be calculated when adding a new command-line argument.
 crate mod dep_tracking {
     use super::{
-        CFGuard, CrateType, DebugInfo, ErrorOutputType, LinkerPluginLto, LtoCli, OptLevel,
-        OutputTypes, Passes, SanitizerSet, SourceFileHashAlgorithm, SwitchWithOptPath,
-        SymbolManglingVersion,
+        CFGuard, CrateType, DebugInfo, DebugInfoCompression, ErrorOutputType, LinkerPluginLto,
+        LintConfig, LtoCli, OptLevel, OutputTypes, Passes, SanitizerSet, SourceFileHashAlgorithm,
+        SplitDebuginfo, SwitchWithOptPath, SymbolManglingVersion,
     };
     use crate::lint;
-    use crate::utils::NativeLibKind;
+    use crate::utils::{NativeLibKind, NativeLibModifiers};
+    use rustc_data_structures::sync::Lrc;
+    use rustc_errors::Catalogue;
     use rustc_feature::UnstableFeatures;
     use rustc_span::edition::Edition;
     use rustc_target::spec::{CodeModel, MergeFunctions, PanicStrategy, RelocModel};
-    use rustc_target::spec::{RelroLevel, TargetTriple, TlsModel};
+    use rustc_target::spec::{RelroLevel, StackProtector, TargetTriple, TlsModel};
     use std::collections::hash_map::DefaultHasher;
     use std::collections::BTreeMap;
     use std::hash::Hash;
@@ -2084,17 +2301,23 @@ crate mod dep_tracking {
     impl_dep_tracking_hash_via_hash!(Option<RelroLevel>);
     impl_dep_tracking_hash_via_hash!(Option<lint::Level>);
     impl_dep_tracking_hash_via_hash!(Option<PathBuf>);
+    impl_dep_tracking_hash_via_hash!(Option<Lrc<Catalogue>>);
     impl_dep_tracking_hash_via_hash!(CrateType);
     impl_dep_tracking_hash_via_hash!(MergeFunctions);
     impl_dep_tracking_hash_via_hash!(PanicStrategy);
     impl_dep_tracking_hash_via_hash!(RelroLevel);
+    impl_dep_tracking_hash_via_hash!(StackProtector);
     impl_dep_tracking_hash_via_hash!(Passes);
+    impl_dep_tracking_hash_via_hash!(LintConfig);
     impl_dep_tracking_hash_via_hash!(OptLevel);
     impl_dep_tracking_hash_via_hash!(LtoCli);
     impl_dep_tracking_hash_via_hash!(DebugInfo);
     impl_dep_tracking_hash_via_hash!(UnstableFeatures);
     impl_dep_tracking_hash_via_hash!(OutputTypes);
     impl_dep_tracking_hash_via_hash!(NativeLibKind);
+    impl_dep_tracking_hash_via_hash!(NativeLibModifiers);
+    impl_dep_tracking_hash_via_hash!(SplitDebuginfo);
+    impl_dep_tracking_hash_via_hash!(DebugInfoCompression);
     impl_dep_tracking_hash_via_hash!(SanitizerSet);
     impl_dep_tracking_hash_via_hash!(CFGuard);
     impl_dep_tracking_hash_via_hash!(TargetTriple);
@@ -2108,7 +2331,12 @@ crate mod dep_tracking {
     impl_dep_tracking_hash_for_sortable_vec_of!(PathBuf);
     impl_dep_tracking_hash_for_sortable_vec_of!(CrateType);
     impl_dep_tracking_hash_for_sortable_vec_of!((String, lint::Level));
-    impl_dep_tracking_hash_for_sortable_vec_of!((String, Option<String>, NativeLibKind));
+    impl_dep_tracking_hash_for_sortable_vec_of!((
+        String,
+        Option<String>,
+        NativeLibKind,
+        NativeLibModifiers
+    ));
     impl_dep_tracking_hash_for_sortable_vec_of!((String, u64));
 
     impl<T1, T2> DepTrackingHash for (T1, T2)
@@ -2140,6 +2368,25 @@ crate mod dep_tracking {
         }
     }
 
+    impl<T1, T2, T3, T4> DepTrackingHash for (T1, T2, T3, T4)
+    where
+        T1: DepTrackingHash,
+        T2: DepTrackingHash,
+        T3: DepTrackingHash,
+        T4: DepTrackingHash,
+    {
+        fn hash(&self, hasher: &mut DefaultHasher, error_format: ErrorOutputType) {
+            Hash::hash(&0, hasher);
+            DepTrackingHash::hash(&self.0, hasher, error_format);
+            Hash::hash(&1, hasher);
+            DepTrackingHash::hash(&self.1, hasher, error_format);
+            Hash::hash(&2, hasher);
+            DepTrackingHash::hash(&self.2, hasher, error_format);
+            Hash::hash(&3, hasher);
+            DepTrackingHash::hash(&self.3, hasher, error_format);
+        }
+    }
+
     // This is a stable hash because BTreeMap is a sorted container
     pub fn stable_hash(
         sub_hashes: BTreeMap<&'static str, &dyn DepTrackingHash>,
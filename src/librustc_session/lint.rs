@@ -11,6 +11,9 @@ pub mod builtin;
 #[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
 pub enum Level {
     Allow,
+    /// Like `Allow`, but reports `UNFULFILLED_LINT_EXPECTATIONS` if the lint
+    /// is never emitted in the attribute's scope. Only set by `#[expect]`.
+    Expect,
     Warn,
     Deny,
     Forbid,
@@ -23,6 +26,7 @@ impl Level {
     pub fn as_str(self) -> &'static str {
         match self {
             Level::Allow => "allow",
+            Level::Expect => "expect",
             Level::Warn => "warn",
             Level::Deny => "deny",
             Level::Forbid => "forbid",
@@ -33,6 +37,7 @@ impl Level {
     pub fn from_str(x: &str) -> Option<Level> {
         match x {
             "allow" => Some(Level::Allow),
+            "expect" => Some(Level::Expect),
             "warn" => Some(Level::Warn),
             "deny" => Some(Level::Deny),
             "forbid" => Some(Level::Forbid),
@@ -44,6 +49,7 @@ impl Level {
     pub fn from_symbol(x: Symbol) -> Option<Level> {
         match x {
             sym::allow => Some(Level::Allow),
+            sym::expect => Some(Level::Expect),
             sym::warn => Some(Level::Warn),
             sym::deny => Some(Level::Deny),
             sym::forbid => Some(Level::Forbid),
@@ -199,6 +205,7 @@ pub enum BuiltinLintDiagnostics {
     RedundantImport(Vec<(Span, bool)>, Ident),
     DeprecatedMacro(Option<Symbol>, Span),
     UnusedDocComment(Span),
+    UnicodeTextFlow(Span, String, Applicability),
 }
 
 /// Lints that are buffered up early on in the `Session` before the
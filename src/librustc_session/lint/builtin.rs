@@ -92,6 +92,12 @@ declare_lint! {
     "unrecognized lint attribute"
 }
 
+declare_lint! {
+    pub UNFULFILLED_LINT_EXPECTATIONS,
+    Warn,
+    "`#[expect]` attributes whose lint was never emitted"
+}
+
 declare_lint! {
     pub UNUSED_VARIABLES,
     Warn,
@@ -110,6 +116,12 @@ declare_lint! {
     "detect unused, unexported items"
 }
 
+declare_lint! {
+    pub UNUSED_PUB_ITEMS,
+    Warn,
+    "detect public items of the crates given to `-Z report-unused-pub-items` that no crate uses"
+}
+
 declare_lint! {
     pub UNUSED_ATTRIBUTES,
     Warn,
@@ -549,6 +561,31 @@ declare_lint! {
     };
 }
 
+declare_lint! {
+    pub MUST_NOT_SUSPEND,
+    Warn,
+    "use of a `#[must_not_suspend]` value across a yield/await point",
+    @feature_gate = sym::must_not_suspend;
+}
+
+declare_lint! {
+    pub LARGE_ASSIGNMENTS,
+    Warn,
+    "detects moves or copies of values larger than the move size limit"
+}
+
+declare_lint! {
+    pub TEXT_DIRECTION_CODEPOINT_IN_COMMENT,
+    Deny,
+    "invisible directionality-changing codepoints in comment"
+}
+
+declare_lint! {
+    pub TEXT_DIRECTION_CODEPOINT_IN_LITERAL,
+    Deny,
+    "invisible directionality-changing codepoints in literal"
+}
+
 declare_lint_pass! {
     /// Does nothing as a lint pass, but registers some `Lint`s
     /// that are used by other parts of the compiler.
@@ -561,6 +598,7 @@ declare_lint_pass! {
         UNUSED_CRATE_DEPENDENCIES,
         UNUSED_QUALIFICATIONS,
         UNKNOWN_LINTS,
+        UNFULFILLED_LINT_EXPECTATIONS,
         UNUSED_VARIABLES,
         UNUSED_ASSIGNMENTS,
         DEAD_CODE,
@@ -623,6 +661,11 @@ declare_lint_pass! {
         UNSAFE_OP_IN_UNSAFE_FN,
         INCOMPLETE_INCLUDE,
         CENUM_IMPL_DROP_CAST,
+        MUST_NOT_SUSPEND,
+        LARGE_ASSIGNMENTS,
+        TEXT_DIRECTION_CODEPOINT_IN_COMMENT,
+        TEXT_DIRECTION_CODEPOINT_IN_LITERAL,
+        UNUSED_PUB_ITEMS,
     ]
 }
 
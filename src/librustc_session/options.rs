@@ -3,11 +3,13 @@ use crate::config::*;
 use crate::early_error;
 use crate::lint;
 use crate::search_paths::SearchPath;
-use crate::utils::NativeLibKind;
+use crate::utils::{NativeLibKind, NativeLibModifiers};
 
 use rustc_target::spec::{CodeModel, LinkerFlavor, MergeFunctions, PanicStrategy};
-use rustc_target::spec::{RelocModel, RelroLevel, TargetTriple, TlsModel};
+use rustc_target::spec::{RelocModel, RelroLevel, StackProtector, TargetTriple, TlsModel};
 
+use rustc_data_structures::sync::Lrc;
+use rustc_errors::{Applicability, Catalogue};
 use rustc_feature::UnstableFeatures;
 use rustc_span::edition::Edition;
 use rustc_span::SourceFileHashAlgorithm;
@@ -89,11 +91,18 @@ top_level_options!(
         debug_assertions: bool [TRACKED],
         debuginfo: DebugInfo [TRACKED],
         lint_opts: Vec<(String, lint::Level)> [TRACKED],
+        lint_config: LintConfig [TRACKED],
         lint_cap: Option<lint::Level> [TRACKED],
+        // The language that diagnostics are translated into, resolved from
+        // `debugging_opts.translation_lang`. `None` stands for English.
+        translation_lang: Option<String> [TRACKED],
+        // The message catalogue of `translation_lang`, which is loaded with the options so that
+        // its contents are tracked.
+        translation_catalogue: Option<Lrc<Catalogue>> [TRACKED],
         describe_lints: bool [UNTRACKED],
         output_types: OutputTypes [TRACKED],
         search_paths: Vec<SearchPath> [UNTRACKED],
-        libs: Vec<(String, Option<String>, NativeLibKind)> [TRACKED],
+        libs: Vec<(String, Option<String>, NativeLibKind, NativeLibModifiers)> [TRACKED],
         maybe_sysroot: Option<PathBuf> [UNTRACKED],
 
         target_triple: TargetTriple [TRACKED],
@@ -248,21 +257,28 @@ macro_rules! options {
         pub const parse_passes: &str = "a space-separated list of passes, or `all`";
         pub const parse_panic_strategy: &str = "either `unwind` or `abort`";
         pub const parse_relro_level: &str = "one of: `full`, `partial`, or `off`";
-        pub const parse_sanitizers: &str = "comma separated list of sanitizers: `address`, `leak`, `memory` or `thread`";
+        pub const parse_sanitizers: &str =
+            "comma separated list of sanitizers: `address`, `cfi`, `leak`, `memory` or `thread`";
         pub const parse_sanitizer_memory_track_origins: &str = "0, 1, or 2";
         pub const parse_cfguard: &str =
             "either a boolean (`yes`, `no`, `on`, `off`, etc), `checks`, or `nochecks`";
         pub const parse_strip: &str = "either `none`, `debuginfo`, or `symbols`";
+        pub const parse_split_debuginfo: &str = "one of: `off`, `packed`, or `unpacked`";
+        pub const parse_debuginfo_compression: &str = "either `none` or `zlib`";
         pub const parse_linker_flavor: &str = ::rustc_target::spec::LinkerFlavor::one_of();
         pub const parse_optimization_fuel: &str = "crate=integer";
         pub const parse_unpretty: &str = "`string` or `string=string`";
         pub const parse_treat_err_as_bug: &str = "either no value or a number bigger than 0";
+        pub const parse_apply_suggestions: &str =
+            "either no value, `machine-applicable`, or `maybe-incorrect`";
         pub const parse_lto: &str =
             "either a boolean (`yes`, `no`, `on`, `off`, etc), `thin`, `fat`, or omitted";
         pub const parse_linker_plugin_lto: &str =
             "either a boolean (`yes`, `no`, `on`, `off`, etc), or the path to the linker plugin";
         pub const parse_switch_with_opt_path: &str =
             "an optional path to the profiling data output directory";
+        pub const parse_stack_protector: &str =
+            "one of: `none` (default), `basic`, `strong`, or `all`";
         pub const parse_merge_functions: &str = "one of: `disabled`, `trampolines`, or `aliases`";
         pub const parse_symbol_mangling_version: &str = "either `legacy` or `v0` (RFC 2603)";
         pub const parse_src_file_hash: &str = "either `md5` or `sha1`";
@@ -464,6 +480,7 @@ macro_rules! options {
                 for s in v.split(',') {
                     *slot |= match s {
                         "address" => SanitizerSet::ADDRESS,
+                        "cfi" => SanitizerSet::CFI,
                         "leak" => SanitizerSet::LEAK,
                         "memory" => SanitizerSet::MEMORY,
                         "thread" => SanitizerSet::THREAD,
@@ -495,6 +512,25 @@ macro_rules! options {
             true
         }
 
+        fn parse_split_debuginfo(slot: &mut SplitDebuginfo, v: Option<&str>) -> bool {
+            match v {
+                Some("off") => *slot = SplitDebuginfo::Off,
+                Some("packed") => *slot = SplitDebuginfo::Packed,
+                Some("unpacked") => *slot = SplitDebuginfo::Unpacked,
+                _ => return false,
+            }
+            true
+        }
+
+        fn parse_debuginfo_compression(slot: &mut DebugInfoCompression, v: Option<&str>) -> bool {
+            match v {
+                Some("none") => *slot = DebugInfoCompression::None,
+                Some("zlib") => *slot = DebugInfoCompression::Zlib,
+                _ => return false,
+            }
+            true
+        }
+
         fn parse_cfguard(slot: &mut CFGuard, v: Option<&str>) -> bool {
             if v.is_some() {
                 let mut bool_arg = None;
@@ -558,6 +594,15 @@ macro_rules! options {
             }
         }
 
+        fn parse_apply_suggestions(slot: &mut Option<Applicability>, v: Option<&str>) -> bool {
+            *slot = match v {
+                None | Some("machine-applicable") => Some(Applicability::MachineApplicable),
+                Some("maybe-incorrect") => Some(Applicability::MaybeIncorrect),
+                Some(_) => return false,
+            };
+            true
+        }
+
         fn parse_lto(slot: &mut LtoCli, v: Option<&str>) -> bool {
             if v.is_some() {
                 let mut bool_arg = None;
@@ -616,6 +661,14 @@ macro_rules! options {
             true
         }
 
+        fn parse_stack_protector(slot: &mut StackProtector, v: Option<&str>) -> bool {
+            match v.and_then(|s| StackProtector::from_str(s).ok()) {
+                Some(stack_protector) => *slot = stack_protector,
+                _ => return false,
+            }
+            true
+        }
+
         fn parse_relocation_model(slot: &mut Option<RelocModel>, v: Option<&str>) -> bool {
             match v.and_then(|s| RelocModel::from_str(s).ok()) {
                 Some(relocation_model) => *slot = Some(relocation_model),
@@ -717,6 +770,10 @@ options! {CodegenOptions, CodegenSetter, basic_codegen_options,
         "extra arguments to append to the linker invocation (space separated)"),
     link_dead_code: bool = (false, parse_bool, [UNTRACKED],
         "keep dead code at link time (useful for code coverage) (default: no)"),
+    link_self_contained: Option<bool> = (None, parse_opt_bool, [UNTRACKED],
+        "control whether to link with the C runtime objects and the `rust-lld` linker shipped \
+        with Rust instead of the C toolchain installed in the system \
+        (default: depends on the target)"),
     linker: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
         "system linker to link outputs with"),
     linker_flavor: Option<LinkerFlavor> = (None, parse_linker_flavor, [UNTRACKED],
@@ -753,6 +810,9 @@ options! {CodegenOptions, CodegenSetter, basic_codegen_options,
     profile_generate: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
         parse_switch_with_opt_path, [TRACKED],
         "compile the program with profiling instrumentation"),
+    profile_sample_use: Option<PathBuf> = (None, parse_opt_pathbuf, [TRACKED],
+        "use the given `.prof` file for sampled profile-guided optimization (also known \
+        as AutoFDO)"),
     profile_use: Option<PathBuf> = (None, parse_opt_pathbuf, [TRACKED],
         "use the given `.profdata` file for profile-guided optimization"),
     relocation_model: Option<RelocModel> = (None, parse_relocation_model, [TRACKED],
@@ -766,6 +826,9 @@ options! {CodegenOptions, CodegenSetter, basic_codegen_options,
         "save all temporary output files during compilation (default: no)"),
     soft_float: bool = (false, parse_bool, [TRACKED],
         "use soft float ABI (*eabihf targets only) (default: no)"),
+    split_debuginfo: SplitDebuginfo = (SplitDebuginfo::Off, parse_split_debuginfo, [TRACKED],
+        "how to handle split-debuginfo, a platform-specific option (ELF targets only) \
+        (`off` (default), `packed`, or `unpacked`)"),
     target_cpu: Option<String> = (None, parse_opt_string, [TRACKED],
         "select target processor (`rustc --print target-cpus` for details)"),
     target_feature: String = (String::new(), parse_target_feature, [TRACKED],
@@ -792,6 +855,15 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "only allow the listed language features to be enabled in code (space separated)"),
     always_encode_mir: bool = (false, parse_bool, [TRACKED],
         "encode MIR of all functions into the crate metadata (default: no)"),
+    apply_suggestions: Option<Applicability> = (None, parse_apply_suggestions, [UNTRACKED],
+        "apply the suggestions of emitted diagnostics to the source files, either only the \
+        machine-applicable ones or also those that may be incorrect \
+        (default: machine-applicable)"),
+    apply_suggestions_lints: Option<Vec<String>> = (None, parse_opt_comma_list, [UNTRACKED],
+        "only apply the suggestions of the listed lints (comma separated)"),
+    apply_suggestions_output: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "write the applied suggestions as a patch to this file instead of rewriting the \
+        source files"),
     asm_comments: bool = (false, parse_bool, [TRACKED],
         "generate comments into the assembly (may change behavior) (default: no)"),
     ast_json: bool = (false, parse_bool, [UNTRACKED],
@@ -815,6 +887,9 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "inject the given attribute in the crate"),
     debug_macros: bool = (false, parse_bool, [TRACKED],
         "emit line numbers debug info inside macros (default: no)"),
+    debuginfo_compression: DebugInfoCompression = (DebugInfoCompression::None,
+        parse_debuginfo_compression, [TRACKED],
+        "compress debug info sections (ELF targets only) (`none` (default) or `zlib`)"),
     deduplicate_diagnostics: bool = (true, parse_bool, [UNTRACKED],
         "deduplicate identical diagnostics (default: yes)"),
     dep_info_omit_d_target: bool = (false, parse_bool, [TRACKED],
@@ -849,6 +924,9 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "in addition to `.mir` files, create graphviz `.dot` files (default: no)"),
     emit_stack_sizes: bool = (false, parse_bool, [UNTRACKED],
         "emit a section containing stack size metadata (default: no)"),
+    export_symbols: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "assign versions to, or hide, the symbols exported by a shared library, \
+        as listed by a GNU ld version script fragment"),
     fewer_names: bool = (false, parse_bool, [TRACKED],
         "reduce memory use by retaining fewer names within compilation artifacts (LLVM-IR) \
         (default: no)"),
@@ -880,8 +958,8 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         (such as entering an empty infinite loop) by inserting llvm.sideeffect \
         (default: no)"),
     instrument_coverage: bool = (false, parse_bool, [TRACKED],
-        "instrument the generated code with LLVM code region counters to (in the \
-        future) generate coverage reports (default: no; note, the compiler build \
+        "instrument the generated code to support LLVM source-based code coverage \
+        reports, generated with `llvm-cov` (default: no; note, the compiler build \
         config must include `profiler = true`)"),
     instrument_mcount: bool = (false, parse_bool, [TRACKED],
         "insert function instrument code for mcount-based tracing (default: no)"),
@@ -891,6 +969,9 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "link native libraries in the linker invocation (default: yes)"),
     link_only: bool = (false, parse_bool, [TRACKED],
         "link the `.rlink` file generated by `-Z no-link` (default: no)"),
+    lint_config: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "set lint levels from a file, with lines like `unused-imports = \"deny\"`; \
+        command-line flags and attributes override these levels"),
     llvm_time_trace: bool = (false, parse_bool, [UNTRACKED],
         "generate JSON tracing data file from LLVM data (default: no)"),
     ls: bool = (false, parse_bool, [UNTRACKED],
@@ -907,6 +988,9 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         (default: no)"),
     mir_opt_level: usize = (1, parse_uint, [TRACKED],
         "MIR optimization level (0-3; default: 1)"),
+    move_size_limit: Option<usize> = (None, parse_opt_uint, [TRACKED],
+        "the size in bytes at which the `large_assignments` lint starts to be emitted, \
+        unless set by `#![move_size_limit]` (default: no limit)"),
     mutable_noalias: bool = (false, parse_bool, [TRACKED],
         "emit noalias metadata for mutable references (default: no)"),
     new_llvm_pass_manager: bool = (false, parse_bool, [TRACKED],
@@ -958,6 +1042,10 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
     print_region_graph: bool = (false, parse_bool, [UNTRACKED],
         "prints region inference graph. \
         Use with RUST_REGION_GRAPH=help for more info (default: no)"),
+    print_stack_usage: bool = (false, parse_bool, [TRACKED],
+        "print the worst-case stack usage of each entry point and check it against its \
+        `#[stack_limit]`; only supported for ELF targets, implies `-Z emit-stack-sizes` \
+        (default: no)"),
     print_type_sizes: bool = (false, parse_bool, [UNTRACKED],
         "print layout information for each type encountered (default: no)"),
     profile: bool = (false, parse_bool, [TRACKED],
@@ -969,10 +1057,16 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "enable queries of the dependency graph for regression testing (default: no)"),
     query_stats: bool = (false, parse_bool, [UNTRACKED],
         "print some statistics about the query system (default: no)"),
+    record_item_uses: bool = (false, parse_bool, [TRACKED],
+        "record the items this crate references in its metadata, for use with \
+        `-Z report-unused-pub-items` (default: no)"),
     relro_level: Option<RelroLevel> = (None, parse_relro_level, [TRACKED],
         "choose which RELRO level to use"),
     report_delayed_bugs: bool = (false, parse_bool, [TRACKED],
         "immediately print bugs registered with `delay_span_bug` (default: no)"),
+    report_unused_pub_items: Option<Vec<String>> = (None, parse_opt_comma_list, [TRACKED],
+        "report the public items of these crates that no crate in the dependency graph uses \
+        (the crates must be compiled with `-Z record-item-uses`)"),
     // The default historical behavior was to always run dsymutil, so we're
     // preserving that temporarily, but we're likely to switch the default
     // soon.
@@ -1010,6 +1104,9 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "exclude spans when debug-printing compiler state (default: no)"),
     src_hash_algorithm: Option<SourceFileHashAlgorithm> = (None, parse_src_file_hash, [TRACKED],
         "hash algorithm of source files in debug info (`md5`, or `sha1`)"),
+    stack_protector: StackProtector = (StackProtector::None, parse_stack_protector, [TRACKED],
+        "control stack smashing protection with stack canaries \
+        (`none` (default), `basic`, `strong`, or `all`)"),
     strip: Strip = (Strip::None, parse_strip, [UNTRACKED],
         "tell the linker which information to strip (`none` (default), `debuginfo` or `symbols`)"),
     symbol_mangling_version: SymbolManglingVersion = (SymbolManglingVersion::Legacy,
@@ -1037,6 +1134,11 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "choose the TLS model to use (`rustc --print tls-models` for details)"),
     trace_macros: bool = (false, parse_bool, [UNTRACKED],
         "for every macro invocation, print its name and arguments (default: no)"),
+    translation_dir: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "load message catalogues from this directory instead of `<sysroot>/share/locale`"),
+    translation_lang: Option<String> = (None, parse_opt_string, [UNTRACKED],
+        "translate diagnostics into this language, or into the language of the current \
+        locale with `system` (default: English)"),
     treat_err_as_bug: Option<usize> = (None, parse_treat_err_as_bug, [TRACKED],
         "treat error number `val` that occurs as bug"),
     ui_testing: bool = (false, parse_bool, [UNTRACKED],
@@ -1061,6 +1163,9 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "validate MIR after each transformation"),
     verbose: bool = (false, parse_bool, [UNTRACKED],
         "in general, enable more debug printouts (default: no)"),
+    verify_determinism: bool = (false, parse_bool, [UNTRACKED],
+        "compile the crate a second time, in a different output directory and with different \
+        parallelism, and report the parts of the outputs that differ (default: no)"),
     verify_llvm_ir: bool = (false, parse_bool, [TRACKED],
         "verify LLVM IR (default: no)"),
 
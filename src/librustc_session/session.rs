@@ -1,7 +1,8 @@
 use crate::cgu_reuse_tracker::CguReuseTracker;
 use crate::code_stats::CodeStats;
 pub use crate::code_stats::{DataTypeKind, FieldInfo, SizeKind, VariantInfo};
-use crate::config::{self, CrateType, OutputType, PrintRequest, SanitizerSet, SwitchWithOptPath};
+use crate::config::{self, CrateType, OutputType, PrintRequest, SanitizerSet};
+use crate::config::{DebugInfoCompression, SplitDebuginfo, SwitchWithOptPath};
 use crate::filesearch;
 use crate::lint;
 use crate::parse::ParseSess;
@@ -19,20 +20,22 @@ use rustc_errors::annotate_snippet_emitter_writer::AnnotateSnippetEmitterWriter;
 use rustc_errors::emitter::{Emitter, EmitterWriter, HumanReadableErrorType};
 use rustc_errors::json::JsonEmitter;
 use rustc_errors::registry::Registry;
-use rustc_errors::{Applicability, DiagnosticBuilder, DiagnosticId, ErrorReported};
+use rustc_errors::apply_suggestions;
+use rustc_errors::{pluralize, Applicability, DiagnosticBuilder, DiagnosticId, ErrorReported};
+use rustc_errors::DiagnosticMessage;
 use rustc_span::edition::Edition;
 use rustc_span::source_map::{FileLoader, MultiSpan, RealFileLoader, SourceMap, Span};
 use rustc_span::{SourceFileHashAlgorithm, Symbol};
 use rustc_target::asm::InlineAsmArch;
 use rustc_target::spec::{CodeModel, PanicStrategy, RelocModel, RelroLevel};
-use rustc_target::spec::{Target, TargetTriple, TlsModel};
+use rustc_target::spec::{StackProtector, Target, TargetTriple, TlsModel};
 
 use std::cell::{self, RefCell};
 use std::env;
 use std::fmt;
 use std::io::Write;
 use std::num::NonZeroU32;
-use std::ops::{Div, Mul};
+use std::ops::{Div, Mul, Range};
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -139,6 +142,10 @@ pub struct Session {
     /// The maximum blocks a const expression can evaluate.
     pub const_eval_limit: OnceCell<Limit>,
 
+    /// The size in bytes above which moves and copies are linted by `large_assignments`,
+    /// or zero if they aren't.
+    pub move_size_limit: OnceCell<Limit>,
+
     incr_comp_session: OneThread<RefCell<IncrCompSession>>,
     /// Used for incremental compilation tests. Will only be populated if
     /// `-Zquery-dep-graph` is specified.
@@ -153,6 +160,10 @@ pub struct Session {
     /// Data about code being compiled, gathered during compilation.
     pub code_stats: CodeStats,
 
+    /// The byte ranges of the parts of the encoded crate metadata, by name. Only recorded
+    /// for `-Z verify-determinism`, to tell which part of the metadata differs.
+    pub metadata_sections: Lock<Vec<(&'static str, Range<usize>)>>,
+
     /// If `-zfuel=crate=n` is specified, `Some(crate)`.
     optimization_fuel_crate: Option<String>,
 
@@ -282,9 +293,64 @@ impl Session {
     /// Invoked all the way at the end to finish off diagnostics printing.
     pub fn finish_diagnostics(&self, registry: &Registry) {
         self.check_miri_unleashed_features();
+        self.apply_suggestions();
         self.diagnostic().print_error_count(registry);
     }
 
+    /// Applies the suggestions collected for `-Z apply-suggestions` to the source files,
+    /// or writes them as a patch to the path given with `-Z apply-suggestions-output`.
+    fn apply_suggestions(&self) {
+        if self.opts.debugging_opts.apply_suggestions.is_none() {
+            return;
+        }
+        // Stashed diagnostics may still carry suggestions.
+        self.diagnostic().emit_stashed_diagnostics();
+
+        let suggestions = self.diagnostic().take_collected_suggestions();
+        let lints = self.opts.debugging_opts.apply_suggestions_lints.as_deref();
+        let files = apply_suggestions::fix_files(self.source_map(), &suggestions, lints);
+
+        let (mut applied, mut skipped) = (0, 0);
+        match &self.opts.debugging_opts.apply_suggestions_output {
+            Some(output) => {
+                let patch: String = files.iter().map(|file| file.to_patch(3)).collect();
+                if let Err(err) = std::fs::write(output, patch) {
+                    self.err(&format!("failed to write patch `{}`: {}", output.display(), err));
+                    return;
+                }
+                applied = files.iter().map(|file| file.applied).sum();
+                skipped = files.iter().map(|file| file.skipped).sum();
+            }
+            None => {
+                for file in files.iter().filter(|file| file.applied > 0) {
+                    match file.write_in_place() {
+                        Ok(()) => {
+                            applied += file.applied;
+                            skipped += file.skipped;
+                        }
+                        Err(err) => self.err(&format!(
+                            "failed to apply suggestions to `{}`: {}",
+                            file.path.display(),
+                            err
+                        )),
+                    }
+                }
+            }
+        }
+
+        if applied > 0 || skipped > 0 {
+            let mut msg = format!("applied {} suggestion{}", applied, pluralize!(applied));
+            if skipped > 0 {
+                msg.push_str(&format!(
+                    ", skipped {} overlapping suggestion{}",
+                    skipped,
+                    pluralize!(skipped)
+                ));
+            }
+            self.diagnostic().note_without_error(&msg);
+        }
+    }
+
     pub fn local_crate_disambiguator(&self) -> CrateDisambiguator {
         self.crate_disambiguator.get().copied().unwrap()
     }
@@ -309,6 +375,10 @@ impl Session {
         self.const_eval_limit.get().copied().unwrap()
     }
 
+    pub fn move_size_limit(&self) -> Limit {
+        self.move_size_limit.get().copied().unwrap()
+    }
+
     pub fn struct_span_warn<S: Into<MultiSpan>>(&self, sp: S, msg: &str) -> DiagnosticBuilder<'_> {
         self.diagnostic().struct_span_warn(sp, msg)
     }
@@ -334,6 +404,11 @@ impl Session {
     ) -> DiagnosticBuilder<'_> {
         self.diagnostic().struct_span_err_with_code(sp, msg, code)
     }
+    /// Formats `msg` in the language selected with `-Z translation-lang`, falling back
+    /// to English.
+    pub fn translate(&self, msg: &DiagnosticMessage) -> String {
+        self.diagnostic().translate(msg)
+    }
     // FIXME: This method should be removed (every error should have an associated error code).
     pub fn struct_err(&self, msg: &str) -> DiagnosticBuilder<'_> {
         self.diagnostic().struct_err(msg)
@@ -701,6 +776,37 @@ impl Session {
         self.opts.cg.code_model.or(self.target.target.options.code_model)
     }
 
+    /// Returns how debuginfo is split out of the object files, which is always
+    /// `SplitDebuginfo::Off` when no debuginfo is generated.
+    pub fn split_debuginfo(&self) -> SplitDebuginfo {
+        if self.opts.debuginfo == config::DebugInfo::None {
+            SplitDebuginfo::Off
+        } else {
+            self.opts.cg.split_debuginfo
+        }
+    }
+
+    /// Returns the `llvm-dwp` that packages split debuginfo: the one shipped in the sysroot if
+    /// there is one, or else the first one in `PATH`.
+    pub fn llvm_dwp(&self) -> Option<PathBuf> {
+        let exe = format!("llvm-dwp{}", env::consts::EXE_SUFFIX);
+        let mut search_paths = self.host_filesearch(PathKind::All).get_tools_search_paths();
+        if let Some(path) = env::var_os("PATH") {
+            search_paths.extend(env::split_paths(&path));
+        }
+        search_paths.into_iter().map(|dir| dir.join(&exe)).find(|path| path.is_file())
+    }
+
+    /// Returns the stack smashing protection strategy, which is always
+    /// `StackProtector::None` if the target does not support stack canaries.
+    pub fn stack_protector(&self) -> StackProtector {
+        if self.target.target.options.supports_stack_protector {
+            self.opts.debugging_opts.stack_protector
+        } else {
+            StackProtector::None
+        }
+    }
+
     pub fn tls_model(&self) -> TlsModel {
         self.opts.debugging_opts.tls_model.unwrap_or(self.target.target.options.tls_model)
     }
@@ -1130,10 +1236,13 @@ pub fn build_session(
     ));
     let emitter = default_emitter(&sopts, registry, source_map.clone(), write_dest);
 
-    let span_diagnostic = rustc_errors::Handler::with_emitter_and_flags(
+    let mut span_diagnostic = rustc_errors::Handler::with_emitter_and_flags(
         emitter,
         sopts.debugging_opts.diagnostic_handler_flags(can_emit_warnings),
     );
+    if let Some(catalogue) = sopts.translation_catalogue.clone() {
+        span_diagnostic = span_diagnostic.with_catalogue(catalogue);
+    }
 
     let self_profiler = if let SwitchWithOptPath::Enabled(ref d) = sopts.debugging_opts.self_profile
     {
@@ -1250,6 +1359,7 @@ pub fn build_session(
         recursion_limit: OnceCell::new(),
         type_length_limit: OnceCell::new(),
         const_eval_limit: OnceCell::new(),
+        move_size_limit: OnceCell::new(),
         incr_comp_session: OneThread::new(RefCell::new(IncrCompSession::NotInitialized)),
         cgu_reuse_tracker,
         prof,
@@ -1261,6 +1371,7 @@ pub fn build_session(
             normalize_projection_ty: AtomicUsize::new(0),
         },
         code_stats: Default::default(),
+        metadata_sections: Lock::new(Vec::new()),
         optimization_fuel_crate,
         optimization_fuel,
         print_fuel_crate,
@@ -1313,6 +1424,23 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
         }
     }
 
+    if let Some(ref path) = sess.opts.cg.profile_sample_use {
+        if !path.exists() {
+            sess.err(&format!(
+                "File `{}` passed to `-C profile-sample-use` does not exist.",
+                path.display()
+            ));
+        }
+        // Samples are attributed to the code through the line tables of the debuginfo, so
+        // without debuginfo, LLVM silently ignores the whole profile.
+        if sess.opts.debuginfo == config::DebugInfo::None {
+            sess.warn(
+                "`-C profile-sample-use` has no effect without debuginfo, \
+                consider adding `-C debuginfo=1`",
+            );
+        }
+    }
+
     // Unwind tables cannot be disabled if the target requires them.
     if let Some(include_uwtables) = sess.opts.cg.force_unwind_tables {
         if sess.panic_strategy() == PanicStrategy::Unwind && !include_uwtables {
@@ -1330,6 +1458,39 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
         }
     }
 
+    // Split and compressed DWARF are only supported for ELF object files.
+    let is_elf = sess.target.target.options.is_elf;
+    if sess.opts.cg.split_debuginfo != SplitDebuginfo::Off && !is_elf {
+        sess.err(&format!(
+            "`-C split-debuginfo` is only supported on ELF targets, not `{}`",
+            sess.opts.target_triple
+        ));
+    }
+    if sess.split_debuginfo() == SplitDebuginfo::Packed && is_elf && sess.llvm_dwp().is_none() {
+        sess.struct_err(
+            "`-C split-debuginfo=packed` requires `llvm-dwp`, which was found neither in the \
+             sysroot nor in `PATH`",
+        )
+        .help("install `llvm-dwp`, or use `-C split-debuginfo=unpacked` to keep the `.dwo` files")
+        .emit();
+    }
+    if sess.opts.debugging_opts.debuginfo_compression != DebugInfoCompression::None && !is_elf {
+        sess.err(&format!(
+            "`-Z debuginfo-compression` is only supported on ELF targets, not `{}`",
+            sess.opts.target_triple
+        ));
+    }
+
+    if sess.opts.debugging_opts.stack_protector != StackProtector::None
+        && !sess.target.target.options.supports_stack_protector
+    {
+        sess.warn(&format!(
+            "`-Z stack-protector={}` is not supported for target `{}` and will be ignored",
+            sess.opts.debugging_opts.stack_protector.desc(),
+            sess.opts.target_triple
+        ));
+    }
+
     // PGO does not work reliably with panic=unwind on Windows. Let's make it
     // an error to combine the two for now. It always runs into an assertions
     // if LLVM is built with assertions, but without assertions it sometimes
@@ -1362,6 +1523,8 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
         &["aarch64-unknown-linux-gnu", "x86_64-unknown-linux-gnu"];
     const TSAN_SUPPORTED_TARGETS: &[&str] =
         &["aarch64-unknown-linux-gnu", "x86_64-apple-darwin", "x86_64-unknown-linux-gnu"];
+    const CFI_SUPPORTED_TARGETS: &[&str] =
+        &["aarch64-unknown-linux-gnu", "x86_64-unknown-linux-gnu"];
 
     // Sanitizers can only be used on some tested platforms.
     for s in sess.opts.debugging_opts.sanitizer {
@@ -1370,6 +1533,7 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
             SanitizerSet::LEAK => LSAN_SUPPORTED_TARGETS,
             SanitizerSet::MEMORY => MSAN_SUPPORTED_TARGETS,
             SanitizerSet::THREAD => TSAN_SUPPORTED_TARGETS,
+            SanitizerSet::CFI => CFI_SUPPORTED_TARGETS,
             _ => panic!("unrecognized sanitizer {}", s),
         };
         if !supported_targets.contains(&&*sess.opts.target_triple.triple()) {
@@ -1379,7 +1543,11 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
                 supported_targets.join(", ")
             ));
         }
-        let conflicting = sess.opts.debugging_opts.sanitizer - s;
+        // CFI is implemented purely at compile time and composes with the runtime sanitizers.
+        if s == SanitizerSet::CFI {
+            continue;
+        }
+        let conflicting = sess.opts.debugging_opts.sanitizer - s - SanitizerSet::CFI;
         if !conflicting.is_empty() {
             sess.err(&format!(
                 "`-Zsanitizer={}` is incompatible with `-Zsanitizer={}`",
@@ -1389,6 +1557,13 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
             break;
         }
     }
+
+    // The type tests inserted by CFI are only lowered when the whole program is visible.
+    if sess.opts.debugging_opts.sanitizer.contains(SanitizerSet::CFI)
+        && sess.lto() != config::Lto::Fat
+    {
+        sess.err("`-Zsanitizer=cfi` requires `-Clto`");
+    }
 }
 
 /// Holds data on the current incremental compilation session, if there is one.
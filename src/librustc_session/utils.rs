@@ -30,3 +30,75 @@ pub enum NativeLibKind {
 }
 
 rustc_data_structures::impl_stable_hash_via_hash!(NativeLibKind);
+
+/// The linking modifiers given to a native library, with `-l KIND:MODIFIERS=NAME` or
+/// `#[link(name = "...", kind = "...", modifiers = "...")]`. The `bundle` modifier
+/// is represented by the `NativeLibKind` instead.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(RustcEncodable, RustcDecodable)]
+pub struct NativeLibModifiers {
+    /// `whole-archive`: whether all object files of a static library are linked, instead of
+    /// only those needed to resolve undefined symbols. `None` if not given, in which case
+    /// bundled libraries of the crate being compiled are linked as whole archives.
+    pub whole_archive: Option<bool>,
+    /// `+verbatim`: the library name is passed to the linker as is, without
+    /// adding a target-specific prefix or suffix.
+    pub verbatim: bool,
+    /// `as-needed`: whether a dynamic library is only linked if it resolves undefined symbols.
+    /// `None` if not given, in which case the linker's default applies.
+    pub as_needed: Option<bool>,
+}
+
+impl NativeLibModifiers {
+    /// Parses a comma-separated list of modifiers, each prefixed by `+` or `-`, given to
+    /// a library of the given `kind`, which the `bundle` modifier updates.
+    pub fn parse(kind: &mut NativeLibKind, modifiers: &str) -> Result<Self, String> {
+        let mut result = NativeLibModifiers::default();
+        for modifier in modifiers.split(',') {
+            let value = match modifier.chars().next() {
+                Some('+') => true,
+                Some('-') => false,
+                _ => {
+                    return Err(format!(
+                        "invalid linking modifier syntax `{}`, expected `+` or `-` \
+                         followed by one of: as-needed, bundle, verbatim, whole-archive",
+                        modifier
+                    ));
+                }
+            };
+            let is_static =
+                matches!(*kind, NativeLibKind::StaticBundle | NativeLibKind::StaticNoBundle);
+            let is_dylib = matches!(*kind, NativeLibKind::Dylib | NativeLibKind::Unspecified);
+            match &modifier[1..] {
+                "bundle" | "whole-archive" if !is_static => {
+                    return Err(format!(
+                        "linking modifier `{}` is only compatible with the `static` linking kind",
+                        &modifier[1..]
+                    ));
+                }
+                "as-needed" if !is_dylib => {
+                    return Err(
+                        "linking modifier `as-needed` is only compatible with the `dylib` \
+                         linking kind"
+                            .to_string(),
+                    );
+                }
+                "bundle" if value => *kind = NativeLibKind::StaticBundle,
+                "bundle" => *kind = NativeLibKind::StaticNoBundle,
+                "whole-archive" => result.whole_archive = Some(value),
+                "verbatim" => result.verbatim = value,
+                "as-needed" => result.as_needed = Some(value),
+                name => {
+                    return Err(format!(
+                        "unknown linking modifier `{}`, expected one of: \
+                         as-needed, bundle, verbatim, whole-archive",
+                        name
+                    ));
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+rustc_data_structures::impl_stable_hash_via_hash!(NativeLibModifiers);
@@ -241,7 +241,6 @@ symbols! {
         copy_closures,
         core,
         core_intrinsics,
-        count_code_region,
         crate_id,
         crate_in_paths,
         crate_local,
@@ -313,6 +312,7 @@ symbols! {
         exhaustive_integer_patterns,
         exhaustive_patterns,
         existential_type,
+        expect,
         expected,
         export_name,
         expr,
@@ -422,6 +422,7 @@ symbols! {
         label_break_value,
         lang,
         lang_items,
+        large_assignments,
         lateout,
         let_chains,
         lhs,
@@ -474,17 +475,21 @@ symbols! {
         min_specialization,
         mips_target_feature,
         mmx_target_feature,
+        modifiers,
         module,
         module_path,
         more_struct_aliases,
         move_ref_pattern,
+        move_size_limit,
         move_val_init,
         movbe_target_feature,
         mul_with_overflow,
+        must_not_suspend,
         must_use,
         naked,
         naked_functions,
         name,
+        native_link_modifiers,
         needs_allocator,
         needs_drop,
         needs_panic_runtime,
@@ -514,6 +519,7 @@ symbols! {
         non_modrs_mods,
         noreturn,
         no_niche,
+        no_panic,
         no_sanitize,
         nostack,
         no_stack_check,
@@ -727,6 +733,7 @@ symbols! {
         speed,
         sse4a_target_feature,
         stable,
+        stack_limit,
         staged_api,
         start,
         static_in_const,
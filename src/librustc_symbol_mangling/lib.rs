@@ -97,6 +97,7 @@
 #[macro_use]
 extern crate rustc_middle;
 
+use rustc_hir as hir;
 use rustc_hir::def_id::{CrateNum, LOCAL_CRATE};
 use rustc_hir::Node;
 use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
@@ -126,6 +127,30 @@ pub fn symbol_name_for_instance_in_crate(
     compute_symbol_name(tcx, instance, || instantiating_crate)
 }
 
+/// This function computes the type identifier that `-Z sanitizer=cfi` uses to
+/// check indirect calls through a `fn` pointer with the signature `fn_sig`.
+/// It is the v0 mangling of the `fn` pointer type, with all regions erased.
+/// Unsafety is not part of the identifier, since a safe `fn` can be coerced
+/// to an `unsafe fn` pointer.
+pub fn typeid_for_fn_sig(tcx: TyCtxt<'tcx>, fn_sig: ty::PolyFnSig<'tcx>) -> String {
+    let mut fn_sig = tcx.normalize_erasing_late_bound_regions(ty::ParamEnv::reveal_all(), &fn_sig);
+    fn_sig.unsafety = hir::Unsafety::Normal;
+    v0::mangle_typeid_for_fn_sig(tcx, fn_sig)
+}
+
+/// Like `typeid_for_fn_sig`, but for trait methods called through a vtable.
+/// The receiver is replaced by a type parameter placeholder, which can never
+/// appear in a monomorphic signature, so that the methods of all implementors
+/// get the same type identifier as the `dyn Trait` method they are called as.
+pub fn typeid_for_virtual_call(tcx: TyCtxt<'tcx>, fn_sig: ty::PolyFnSig<'tcx>) -> String {
+    let mut fn_sig = tcx.normalize_erasing_late_bound_regions(ty::ParamEnv::reveal_all(), &fn_sig);
+    let mut inputs_and_output = fn_sig.inputs_and_output.to_vec();
+    inputs_and_output[0] = tcx.types.self_param;
+    fn_sig.inputs_and_output = tcx.intern_type_list(&inputs_and_output);
+    fn_sig.unsafety = hir::Unsafety::Normal;
+    v0::mangle_typeid_for_fn_sig(tcx, fn_sig)
+}
+
 pub fn provide(providers: &mut Providers<'_>) {
     *providers = Providers { symbol_name: symbol_name_provider, ..*providers };
 }
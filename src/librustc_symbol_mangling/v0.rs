@@ -54,6 +54,13 @@ pub(super) fn mangle(
     cx.out
 }
 
+pub(super) fn mangle_typeid_for_fn_sig(tcx: TyCtxt<'tcx>, fn_sig: ty::FnSig<'tcx>) -> String {
+    // Type identifiers are compared as whole strings and never demangled, so
+    // backreferences would only make them harder to read in the LLVM IR.
+    let cx = SymbolMangler { tcx, compress: None, binders: vec![], out: String::new() };
+    cx.print_type(tcx.mk_fn_ptr(ty::Binder::dummy(fn_sig))).unwrap().out
+}
+
 struct CompressionCaches<'tcx> {
     // The length of the prefix in `out` (e.g. 2 for `_R`).
     start_offset: usize,
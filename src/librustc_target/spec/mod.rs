@@ -269,6 +269,50 @@ impl ToJson for MergeFunctions {
     }
 }
 
+/// The stack smashing protection strategy, i.e. which functions get stack canaries.
+#[derive(Clone, Copy, Debug, PartialEq, Hash, RustcEncodable, RustcDecodable)]
+pub enum StackProtector {
+    /// Disable stack canary generation.
+    None,
+
+    /// Generate stack canaries in functions with character arrays, like
+    /// `-fstack-protector` in C compilers.
+    Basic,
+
+    /// Generate stack canaries in functions with arrays or local variables whose
+    /// address is taken, like `-fstack-protector-strong` in C compilers.
+    Strong,
+
+    /// Generate stack canaries in all functions, like `-fstack-protector-all`
+    /// in C compilers.
+    All,
+}
+
+impl StackProtector {
+    pub fn desc(&self) -> &str {
+        match *self {
+            StackProtector::None => "none",
+            StackProtector::Basic => "basic",
+            StackProtector::Strong => "strong",
+            StackProtector::All => "all",
+        }
+    }
+}
+
+impl FromStr for StackProtector {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<StackProtector, ()> {
+        match s {
+            "none" => Ok(StackProtector::None),
+            "basic" => Ok(StackProtector::Basic),
+            "strong" => Ok(StackProtector::Strong),
+            "all" => Ok(StackProtector::All),
+            _ => Err(()),
+        }
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Hash, Debug)]
 pub enum RelocModel {
     Static,
@@ -747,6 +791,10 @@ pub struct TargetOptions {
     pub post_link_objects_fallback: CrtObjects,
     /// Which logic to use to determine whether to fall back to the "self-contained" mode or not.
     pub crt_objects_fallback: Option<CrtObjectsFallback>,
+    /// Whether the "self-contained" mode of `-C link-self-contained` links with the `rust-lld`
+    /// shipped with rustc, either through the `cc` driver or directly, instead of the system
+    /// linker.
+    pub self_contained_lld: bool,
 
     /// Linker arguments that are unconditionally passed after any
     /// user-defined but before post-link objects. Standard platform
@@ -838,6 +886,9 @@ pub struct TargetOptions {
     pub is_like_emscripten: bool,
     /// Whether the target toolchain is like Fuchsia's.
     pub is_like_fuchsia: bool,
+    /// Whether the object files and binaries of the target are ELF files, which split and
+    /// compressed DWARF require. Defaults to true.
+    pub is_elf: bool,
     /// Whether the linker support GNU-like arguments such as -O. Defaults to false.
     pub linker_is_gnu: bool,
     /// The MinGW toolchain has a known issue that prevents it from correctly
@@ -988,6 +1039,10 @@ pub struct TargetOptions {
     /// Whether to use legacy .ctors initialization hooks rather than .init_array. Defaults
     /// to false (uses .init_array).
     pub use_ctors_section: bool,
+
+    /// Whether the target supports stack canaries, see `-Z stack-protector`.
+    /// Defaults to true.
+    pub supports_stack_protector: bool,
 }
 
 impl Default for TargetOptions {
@@ -1027,6 +1082,7 @@ impl Default for TargetOptions {
             is_like_emscripten: false,
             is_like_msvc: false,
             is_like_fuchsia: false,
+            is_elf: true,
             linker_is_gnu: false,
             allows_weak_linkage: true,
             has_rpath: false,
@@ -1040,6 +1096,7 @@ impl Default for TargetOptions {
             pre_link_objects_fallback: Default::default(),
             post_link_objects_fallback: Default::default(),
             crt_objects_fallback: None,
+            self_contained_lld: false,
             late_link_args: LinkArgs::new(),
             late_link_args_dynamic: LinkArgs::new(),
             late_link_args_static: LinkArgs::new(),
@@ -1080,6 +1137,7 @@ impl Default for TargetOptions {
             relax_elf_relocations: false,
             llvm_args: vec![],
             use_ctors_section: false,
+            supports_stack_protector: true,
         }
     }
 }
@@ -1397,6 +1455,7 @@ impl Target {
         key!(pre_link_objects_fallback, link_objects);
         key!(post_link_objects_fallback, link_objects);
         key!(crt_objects_fallback, crt_objects_fallback)?;
+        key!(self_contained_lld, bool);
         key!(pre_link_args, link_args);
         key!(late_link_args, link_args);
         key!(late_link_args_dynamic, link_args);
@@ -1431,6 +1490,7 @@ impl Target {
         key!(is_like_emscripten, bool);
         key!(is_like_android, bool);
         key!(is_like_fuchsia, bool);
+        key!(is_elf, bool);
         key!(linker_is_gnu, bool);
         key!(allows_weak_linkage, bool);
         key!(has_rpath, bool);
@@ -1473,6 +1533,7 @@ impl Target {
         key!(relax_elf_relocations, bool);
         key!(llvm_args, list);
         key!(use_ctors_section, bool);
+        key!(supports_stack_protector, bool);
 
         if let Some(array) = obj.find("abi-blacklist").and_then(Json::as_array) {
             for name in array.iter().filter_map(|abi| abi.as_string()) {
@@ -1628,6 +1689,7 @@ impl ToJson for Target {
         target_option_val!(pre_link_objects_fallback);
         target_option_val!(post_link_objects_fallback);
         target_option_val!(crt_objects_fallback);
+        target_option_val!(self_contained_lld);
         target_option_val!(link_args - pre_link_args);
         target_option_val!(link_args - late_link_args);
         target_option_val!(link_args - late_link_args_dynamic);
@@ -1662,6 +1724,7 @@ impl ToJson for Target {
         target_option_val!(is_like_emscripten);
         target_option_val!(is_like_android);
         target_option_val!(is_like_fuchsia);
+        target_option_val!(is_elf);
         target_option_val!(linker_is_gnu);
         target_option_val!(allows_weak_linkage);
         target_option_val!(has_rpath);
@@ -1704,6 +1767,7 @@ impl ToJson for Target {
         target_option_val!(relax_elf_relocations);
         target_option_val!(llvm_args);
         target_option_val!(use_ctors_section);
+        target_option_val!(supports_stack_protector);
 
         if default.abi_blacklist != self.options.abi_blacklist {
             d.insert(
@@ -21,6 +21,7 @@ pub fn opts() -> TargetOptions {
         executables: true,
         is_like_windows: true,
         is_like_msvc: true,
+        is_elf: false,
         lld_flavor: LldFlavor::Link,
         pre_link_args,
         abi_return_struct_as_int: true,
@@ -35,6 +35,7 @@ pub fn target() -> TargetResult {
             // Needed to use `dylib` and `bin` crate types and the linker.
             dynamic_linking: true,
             executables: true,
+            is_elf: false,
 
             // Avoid using dylib because it contain metadata not supported
             // by LLVM NVPTX backend.
@@ -53,6 +54,9 @@ pub fn target() -> TargetResult {
             // This behavior is not supported by PTX ISA.
             merge_functions: MergeFunctions::Disabled,
 
+            // The PTX backend has no way to abort on a clobbered stack canary.
+            supports_stack_protector: false,
+
             // FIXME: enable compilation tests for the target and
             // create the tests for this.
             abi_blacklist: vec![
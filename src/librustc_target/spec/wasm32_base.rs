@@ -90,6 +90,7 @@ pub fn options() -> TargetOptions {
         dll_prefix: String::new(),
         dll_suffix: ".wasm".to_string(),
         linker_is_gnu: false,
+        is_elf: false,
 
         max_atomic_width: Some(64),
 
@@ -78,6 +78,7 @@ pub fn opts() -> TargetOptions {
         staticlib_suffix: ".a".to_string(),
         target_family: Some("windows".to_string()),
         is_like_windows: true,
+        is_elf: false,
         allows_weak_linkage: false,
         pre_link_args,
         pre_link_objects: crt_objects::pre_mingw(),
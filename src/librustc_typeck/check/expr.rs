@@ -14,13 +14,14 @@ use crate::check::Expectation::{self, ExpectCastableToType, ExpectHasType, NoExp
 use crate::check::FnCtxt;
 use crate::check::Needs;
 use crate::check::TupleArgumentsFlag::DontTupleArguments;
-use crate::type_error_struct;
+use crate::type_error_struct_msg;
 
 use rustc_ast::ast;
 use rustc_ast::util::lev_distance::find_best_match_for_name;
 use rustc_data_structures::fx::FxHashMap;
 use rustc_errors::ErrorReported;
-use rustc_errors::{pluralize, struct_span_err, Applicability, DiagnosticBuilder, DiagnosticId};
+use rustc_errors::{pluralize, struct_span_err, struct_span_err_msg};
+use rustc_errors::{Applicability, DiagnosticBuilder, DiagnosticId, DiagnosticMessage};
 use rustc_hir as hir;
 use rustc_hir::def::{CtorKind, DefKind, Res};
 use rustc_hir::def_id::DefId;
@@ -321,13 +322,16 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                     if let Some(ty) = self.lookup_derefing(expr, oprnd, oprnd_t) {
                         oprnd_t = ty;
                     } else {
-                        let mut err = type_error_struct!(
+                        let mut err = type_error_struct_msg!(
                             tcx.sess,
                             expr.span,
                             oprnd_t,
                             E0614,
-                            "type `{}` cannot be dereferenced",
-                            oprnd_t,
+                            DiagnosticMessage::new(
+                                "typeck-cannot-deref",
+                                "type `{$ty}` cannot be dereferenced",
+                            )
+                            .arg("ty", oprnd_t.to_string())
                         );
                         let sp = tcx.sess.source_map().start_point(expr.span);
                         if let Some(sp) =
@@ -435,13 +439,19 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
             })
         });
         if !is_named {
-            struct_span_err!(
+            struct_span_err_msg!(
                 self.tcx.sess,
                 oprnd.span,
                 E0745,
-                "cannot take address of a temporary"
+                DiagnosticMessage::new(
+                    "typeck-address-of-temporary",
+                    "cannot take address of a temporary",
+                )
+            )
+            .span_label_msg(
+                oprnd.span,
+                &DiagnosticMessage::new("typeck-address-of-temporary-label", "temporary value"),
             )
-            .span_label(oprnd.span, "temporary value")
             .emit();
         }
     }
@@ -653,11 +663,14 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         expr: &'tcx hir::Expr<'tcx>,
     ) -> Ty<'tcx> {
         if self.ret_coercion.is_none() {
-            struct_span_err!(
+            struct_span_err_msg!(
                 self.tcx.sess,
                 expr.span,
                 E0572,
-                "return statement outside of function body",
+                DiagnosticMessage::new(
+                    "typeck-return-outside-fn",
+                    "return statement outside of function body",
+                )
             )
             .emit();
         } else if let Some(ref e) = expr_opt {
@@ -1129,11 +1142,14 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                             .insert(expr.hir_id, fru_field_types);
                     }
                     _ => {
-                        struct_span_err!(
+                        struct_span_err_msg!(
                             self.tcx.sess,
                             base_expr.span,
                             E0436,
-                            "functional record update syntax requires a struct"
+                            DiagnosticMessage::new(
+                                "typeck-functional-record-update-on-non-struct",
+                                "functional record update syntax requires a struct",
+                            )
                         )
                         .emit();
                     }
@@ -1198,16 +1214,32 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
             } else {
                 error_happened = true;
                 if let Some(prev_span) = seen_fields.get(&ident) {
-                    let mut err = struct_span_err!(
+                    let mut err = struct_span_err_msg!(
                         self.tcx.sess,
                         field.ident.span,
                         E0062,
-                        "field `{}` specified more than once",
-                        ident
+                        DiagnosticMessage::new(
+                            "typeck-field-multiply-specified",
+                            "field `{$ident}` specified more than once",
+                        )
+                        .arg("ident", ident)
                     );
 
-                    err.span_label(field.ident.span, "used more than once");
-                    err.span_label(*prev_span, format!("first use of `{}`", ident));
+                    err.span_label_msg(
+                        field.ident.span,
+                        &DiagnosticMessage::new(
+                            "typeck-field-multiply-specified-label",
+                            "used more than once",
+                        ),
+                    );
+                    err.span_label_msg(
+                        *prev_span,
+                        &DiagnosticMessage::new(
+                            "typeck-field-multiply-specified-previous-label",
+                            "first use of `{$ident}`",
+                        )
+                        .arg("ident", ident),
+                    );
 
                     err.emit();
                 } else {
@@ -1488,13 +1520,16 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         } else if !expr_t.is_primitive_ty() {
             self.ban_nonexisting_field(field, base, expr, expr_t);
         } else {
-            type_error_struct!(
+            type_error_struct_msg!(
                 self.tcx().sess,
                 field.span,
                 expr_t,
                 E0610,
-                "`{}` is a primitive type and therefore doesn't have fields",
-                expr_t
+                DiagnosticMessage::new(
+                    "typeck-field-of-primitive",
+                    "`{$ty}` is a primitive type and therefore doesn't have fields",
+                )
+                .arg("ty", expr_t.to_string())
             )
             .emit();
         }
@@ -1556,7 +1591,10 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
             kind_name,
             struct_path
         );
-        err.span_label(field.span, "private field");
+        err.span_label_msg(
+            field.span,
+            &DiagnosticMessage::new("typeck-private-field-label", "private field"),
+        );
         // Also check if an accessible method exists, which is often what is meant.
         if self.method_exists(field, expr_t, expr.hir_id, false) && !self.expr_in_place(expr.hir_id)
         {
@@ -1572,16 +1610,22 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
     }
 
     fn ban_take_value_of_method(&self, expr: &hir::Expr<'_>, expr_t: Ty<'tcx>, field: Ident) {
-        let mut err = type_error_struct!(
+        let mut err = type_error_struct_msg!(
             self.tcx().sess,
             field.span,
             expr_t,
             E0615,
-            "attempted to take value of method `{}` on type `{}`",
-            field,
-            expr_t
+            DiagnosticMessage::new(
+                "typeck-method-value",
+                "attempted to take value of method `{$field}` on type `{$ty}`",
+            )
+            .arg("field", field)
+            .arg("ty", expr_t.to_string())
+        );
+        err.span_label_msg(
+            field.span,
+            &DiagnosticMessage::new("typeck-method-value-label", "method, not a field"),
         );
-        err.span_label(field.span, "method, not a field");
         if !self.expr_in_place(expr.hir_id) {
             self.suggest_method_call(
                 &mut err,
@@ -1591,7 +1635,10 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                 expr,
             );
         } else {
-            err.help("methods are immutable and cannot be assigned to");
+            err.help_msg(&DiagnosticMessage::new(
+                "typeck-method-value-assigned-help",
+                "methods are immutable and cannot be assigned to",
+            ));
         }
 
         err.emit();
@@ -1686,14 +1733,14 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         field: T,
         expr_t: &ty::TyS<'_>,
     ) -> DiagnosticBuilder<'_> {
-        type_error_struct!(
+        type_error_struct_msg!(
             self.tcx().sess,
             span,
             expr_t,
             E0609,
-            "no field `{}` on type `{}`",
-            field,
-            expr_t
+            DiagnosticMessage::new("typeck-no-such-field", "no field `{$field}` on type `{$ty}`")
+                .arg("field", field.to_string())
+                .arg("ty", expr_t.to_string())
         )
     }
 
@@ -1719,13 +1766,16 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                     element_ty
                 }
                 None => {
-                    let mut err = type_error_struct!(
+                    let mut err = type_error_struct_msg!(
                         self.tcx.sess,
                         expr.span,
                         base_t,
                         E0608,
-                        "cannot index into a value of type `{}`",
-                        base_t
+                        DiagnosticMessage::new(
+                            "typeck-cannot-index",
+                            "cannot index into a value of type `{$ty}`",
+                        )
+                        .arg("ty", base_t.to_string())
                     );
                     // Try to give some advice about indexing tuples.
                     if let ty::Tuple(..) = base_t.kind {
@@ -1781,11 +1831,14 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                 self.tcx.mk_unit()
             }
             _ => {
-                struct_span_err!(
+                struct_span_err_msg!(
                     self.tcx.sess,
                     expr.span,
                     E0627,
-                    "yield expression outside of generator literal"
+                    DiagnosticMessage::new(
+                        "typeck-yield-outside-generator",
+                        "yield expression outside of generator literal",
+                    )
                 )
                 .emit();
                 self.tcx.mk_unit()
@@ -9,9 +9,11 @@ use rustc_hir as hir;
 use rustc_hir::def::{CtorKind, DefKind, Res};
 use rustc_hir::def_id::DefId;
 use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
-use rustc_hir::{Expr, ExprKind, Pat, PatKind};
+use rustc_hir::{Expr, ExprKind, HirId, Pat, PatKind};
 use rustc_middle::middle::region::{self, YieldData};
 use rustc_middle::ty::{self, Ty};
+use rustc_session::lint::builtin::MUST_NOT_SUSPEND;
+use rustc_span::symbol::sym;
 use rustc_span::Span;
 
 struct InteriorVisitor<'a, 'tcx> {
@@ -27,6 +29,7 @@ impl<'a, 'tcx> InteriorVisitor<'a, 'tcx> {
     fn record(
         &mut self,
         ty: Ty<'tcx>,
+        hir_id: HirId,
         scope: Option<region::Scope>,
         expr: Option<&'tcx Expr<'tcx>>,
         source_span: Span,
@@ -88,6 +91,19 @@ impl<'a, 'tcx> InteriorVisitor<'a, 'tcx> {
                     .span_note(yield_data.span, &*note)
                     .emit();
             } else {
+                check_must_not_suspend_ty(
+                    self.fcx,
+                    ty,
+                    hir_id,
+                    SuspendCheckData {
+                        source_span,
+                        yield_span: yield_data.span,
+                        descr_pre: "",
+                        descr_post: "",
+                    },
+                    &mut FxHashSet::default(),
+                );
+
                 // Map the type to the number of types added before it
                 let entries = self.types.len();
                 let scope_span = scope.map(|s| s.span(self.fcx.tcx, self.region_scope_tree));
@@ -223,7 +239,7 @@ impl<'a, 'tcx> Visitor<'tcx> for InteriorVisitor<'a, 'tcx> {
         if let PatKind::Binding(..) = pat.kind {
             let scope = self.region_scope_tree.var_scope(pat.hir_id.local_id);
             let ty = self.fcx.tables.borrow().pat_ty(pat);
-            self.record(ty, Some(scope), None, pat.span);
+            self.record(ty, pat.hir_id, Some(scope), None, pat.span);
         }
     }
 
@@ -264,7 +280,7 @@ impl<'a, 'tcx> Visitor<'tcx> for InteriorVisitor<'a, 'tcx> {
         // If there are adjustments, then record the final type --
         // this is the actual value that is being produced.
         if let Some(adjusted_ty) = self.fcx.tables.borrow().expr_ty_adjusted_opt(expr) {
-            self.record(adjusted_ty, scope, Some(expr), expr.span);
+            self.record(adjusted_ty, expr.hir_id, scope, Some(expr), expr.span);
         }
 
         // Also record the unadjusted type (which is the only type if
@@ -292,9 +308,181 @@ impl<'a, 'tcx> Visitor<'tcx> for InteriorVisitor<'a, 'tcx> {
         // The type table might not have information for this expression
         // if it is in a malformed scope. (#66387)
         if let Some(ty) = self.fcx.tables.borrow().expr_ty_opt(expr) {
-            self.record(ty, scope, Some(expr), expr.span);
+            self.record(ty, expr.hir_id, scope, Some(expr), expr.span);
         } else {
             self.fcx.tcx.sess.delay_span_bug(expr.span, "no type for node");
         }
     }
 }
+
+struct SuspendCheckData<'a> {
+    source_span: Span,
+    yield_span: Span,
+    descr_pre: &'a str,
+    descr_post: &'a str,
+}
+
+// Returns whether a lint was emitted for `ty`, looking through boxes, references, tuples,
+// arrays, trait objects, `impl Trait` and the fields of ADTs for `#[must_not_suspend]` types.
+// `visited` guards against recursing forever into recursive types.
+fn check_must_not_suspend_ty<'tcx>(
+    fcx: &FnCtxt<'_, 'tcx>,
+    ty: Ty<'tcx>,
+    hir_id: HirId,
+    data: SuspendCheckData<'_>,
+    visited: &mut FxHashSet<Ty<'tcx>>,
+) -> bool {
+    if ty.is_unit() || !visited.insert(ty) {
+        return false;
+    }
+
+    match ty.kind {
+        ty::Adt(..) if ty.is_box() => {
+            let boxed_ty = ty.boxed_ty();
+            let descr_pre = &format!("{}boxed ", data.descr_pre);
+            check_must_not_suspend_ty(
+                fcx,
+                boxed_ty,
+                hir_id,
+                SuspendCheckData { descr_pre, ..data },
+                visited,
+            )
+        }
+        ty::Adt(def, substs) => {
+            if check_must_not_suspend_def(fcx.tcx, def.did, hir_id, &data) {
+                return true;
+            }
+            // A value containing a `#[must_not_suspend]` field is just as bad as the field.
+            let path = fcx.tcx.def_path_str(def.did);
+            for field in def.all_fields() {
+                let descr_post =
+                    &format!(" in field `{}` of `{}`{}", field.ident, path, data.descr_post);
+                if check_must_not_suspend_ty(
+                    fcx,
+                    field.ty(fcx.tcx, substs),
+                    hir_id,
+                    SuspendCheckData { descr_post, ..data },
+                    visited,
+                ) {
+                    return true;
+                }
+            }
+            false
+        }
+        ty::Opaque(def, _) => {
+            for (predicate, _) in fcx.tcx.predicates_of(def).predicates {
+                if let ty::PredicateKind::Trait(ref poly_trait_predicate, _) = predicate.kind() {
+                    let def_id = poly_trait_predicate.skip_binder().trait_ref.def_id;
+                    let descr_pre = &format!("{}implementer of ", data.descr_pre);
+                    if check_must_not_suspend_def(
+                        fcx.tcx,
+                        def_id,
+                        hir_id,
+                        &SuspendCheckData { descr_pre, ..data },
+                    ) {
+                        return true;
+                    }
+                }
+            }
+            false
+        }
+        ty::Dynamic(binder, _) => {
+            for predicate in binder.skip_binder().iter() {
+                if let ty::ExistentialPredicate::Trait(ref trait_ref) = predicate {
+                    let descr_post = &format!(" trait object{}", data.descr_post);
+                    if check_must_not_suspend_def(
+                        fcx.tcx,
+                        trait_ref.def_id,
+                        hir_id,
+                        &SuspendCheckData { descr_post, ..data },
+                    ) {
+                        return true;
+                    }
+                }
+            }
+            false
+        }
+        ty::Tuple(ref tys) => {
+            let mut has_emitted = false;
+            for (i, ty) in tys.iter().map(|k| k.expect_ty()).enumerate() {
+                let descr_post = &format!(" in tuple element {}{}", i, data.descr_post);
+                if check_must_not_suspend_ty(
+                    fcx,
+                    ty,
+                    hir_id,
+                    SuspendCheckData { descr_post, ..data },
+                    visited,
+                ) {
+                    has_emitted = true;
+                }
+            }
+            has_emitted
+        }
+        ty::Array(ty, len) => match len.try_eval_usize(fcx.tcx, fcx.param_env) {
+            // Empty arrays never hold a value, so there is nothing to lint.
+            Some(n) if n != 0 => {
+                let descr_pre = &format!("{}array of ", data.descr_pre);
+                check_must_not_suspend_ty(
+                    fcx,
+                    ty,
+                    hir_id,
+                    SuspendCheckData { descr_pre, ..data },
+                    visited,
+                )
+            }
+            _ => false,
+        },
+        ty::Ref(_, ty, _) => {
+            let descr_pre = &format!("{}reference to ", data.descr_pre);
+            check_must_not_suspend_ty(
+                fcx,
+                ty,
+                hir_id,
+                SuspendCheckData { descr_pre, ..data },
+                visited,
+            )
+        }
+        _ => false,
+    }
+}
+
+fn check_must_not_suspend_def(
+    tcx: ty::TyCtxt<'_>,
+    def_id: DefId,
+    hir_id: HirId,
+    data: &SuspendCheckData<'_>,
+) -> bool {
+    for attr in tcx.get_attrs(def_id).iter() {
+        if attr.check_name(sym::must_not_suspend) {
+            tcx.struct_span_lint_hir(MUST_NOT_SUSPEND, hir_id, data.source_span, |lint| {
+                let msg = format!(
+                    "{}`{}`{} held across a yield point, but should not be",
+                    data.descr_pre,
+                    tcx.def_path_str(def_id),
+                    data.descr_post,
+                );
+                let mut err = lint.build(&msg);
+
+                // `scope: None` records are live across the whole body and have no yield span.
+                if !data.yield_span.is_dummy() {
+                    err.span_label(data.yield_span, "the value is held across this yield point");
+                }
+
+                // check for #[must_not_suspend = "..."]
+                if let Some(note) = attr.value_str() {
+                    err.span_note(data.source_span, &note.as_str());
+                }
+
+                err.span_help(
+                    data.source_span,
+                    "consider using a block (`{ ... }`) to shrink the value's scope, ending before \
+                     the yield point",
+                );
+
+                err.emit();
+            });
+            return true;
+        }
+    }
+    false
+}
@@ -175,6 +175,19 @@ macro_rules! type_error_struct {
     })
 }
 
+/// Like `type_error_struct!`, but takes a `DiagnosticMessage` that is translated through
+/// the loaded message catalogue.
+#[macro_export]
+macro_rules! type_error_struct_msg {
+    ($session:expr, $span:expr, $typ:expr, $code:ident, $message:expr) => ({
+        if $typ.references_error() {
+            $session.diagnostic().struct_dummy()
+        } else {
+            rustc_errors::struct_span_err_msg!($session, $span, $code, $message)
+        }
+    })
+}
+
 /// The type of a local binding, including the revealed type for anon types.
 #[derive(Copy, Clone, Debug)]
 pub struct LocalTy<'tcx> {
@@ -2363,6 +2363,10 @@ fn codegen_fn_attrs(tcx: TyCtxt<'_>, id: DefId) -> CodegenFnAttrs {
                     .emit();
             }
             codegen_fn_attrs.flags |= CodegenFnAttrFlags::TRACK_CALLER;
+        } else if attr.check_name(sym::no_panic) {
+            codegen_fn_attrs.flags |= CodegenFnAttrFlags::NO_PANIC;
+        } else if attr.check_name(sym::stack_limit) {
+            codegen_fn_attrs.stack_limit = check_stack_limit(tcx, attr);
         } else if attr.check_name(sym::export_name) {
             if let Some(s) = attr.value_str() {
                 if s.as_str().contains('\0') {
@@ -2616,6 +2620,35 @@ fn check_link_ordinal(tcx: TyCtxt<'_>, attr: &ast::Attribute) -> Option<usize> {
     }
 }
 
+fn check_stack_limit(tcx: TyCtxt<'_>, attr: &ast::Attribute) -> Option<u64> {
+    use rustc_ast::ast::{LitIntType, LitKind, MetaItemKind};
+    let limit = match attr.meta().map(|meta| meta.kind) {
+        Some(MetaItemKind::NameValue(lit)) => match lit.kind {
+            LitKind::Int(limit, LitIntType::Unsuffixed) => Some(limit),
+            _ => None,
+        },
+        _ => None,
+    };
+    match limit {
+        Some(limit) if limit <= u64::MAX as u128 => Some(limit as u64),
+        Some(limit) => {
+            let msg = format!("stack limit in `stack_limit` is too large: `{}`", limit);
+            tcx.sess
+                .struct_span_err(attr.span, &msg)
+                .note("the value may not exceed `u64::MAX`")
+                .emit();
+            None
+        }
+        None => {
+            tcx.sess
+                .struct_span_err(attr.span, "illegal stack limit format in `stack_limit`")
+                .note("an unsuffixed integer number of bytes, e.g., `4096`, is expected")
+                .emit();
+            None
+        }
+    }
+}
+
 fn check_link_name_xor_ordinal(
     tcx: TyCtxt<'_>,
     codegen_fn_attrs: &CodegenFnAttrs,
@@ -309,6 +309,7 @@ pub fn run_core(options: RustdocOptions) -> (clean::Crate, RenderInfo, RenderOpt
     let private_doc_tests = rustc_lint::builtin::PRIVATE_DOC_TESTS.name;
     let no_crate_level_docs = rustc_lint::builtin::MISSING_CRATE_LEVEL_DOCS.name;
     let invalid_codeblock_attribute_name = rustc_lint::builtin::INVALID_CODEBLOCK_ATTRIBUTE.name;
+    let unfulfilled_lint_expectations = lint::builtin::UNFULFILLED_LINT_EXPECTATIONS.name;
 
     // In addition to those specific lints, we also need to whitelist those given through
     // command line, otherwise they'll get ignored and we don't want that.
@@ -319,11 +320,13 @@ pub fn run_core(options: RustdocOptions) -> (clean::Crate, RenderInfo, RenderOpt
         private_doc_tests.to_owned(),
         no_crate_level_docs.to_owned(),
         invalid_codeblock_attribute_name.to_owned(),
+        unfulfilled_lint_expectations.to_owned(),
     ];
 
     let (lint_opts, lint_caps) = init_lints(whitelisted_lints, lint_opts, |lint| {
         if lint.name == intra_link_resolution_failure_name
             || lint.name == invalid_codeblock_attribute_name
+            || lint.name == unfulfilled_lint_expectations
         {
             None
         } else {
@@ -554,6 +557,8 @@ pub fn run_core(options: RustdocOptions) -> (clean::Crate, RenderInfo, RenderOpt
                     }
                 }
 
+                rustc_lint::check_expectations(tcx);
+
                 ctxt.sess().abort_if_errors();
 
                 (krate, ctxt.renderinfo.into_inner(), render_options)
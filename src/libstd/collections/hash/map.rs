@@ -819,6 +819,40 @@ where
         self.base.insert(k, v)
     }
 
+    /// Inserts a key-value pair into the map, returning an error instead of
+    /// aborting if the map needs to grow and the allocation fails.
+    ///
+    /// On success, this behaves exactly like [`insert`]. On error, the map is
+    /// left unchanged and both `k` and `v` are dropped.
+    ///
+    /// Space for one more element is reserved before the key is looked up, so
+    /// this may allocate even if the key is already present.
+    ///
+    /// [`insert`]: #method.insert
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    /// use std::collections::HashMap;
+    ///
+    /// let mut map = HashMap::new();
+    /// assert_eq!(map.try_insert(37, "a").expect("out of memory"), None);
+    /// assert_eq!(map.try_insert(37, "b").expect("out of memory"), Some("a"));
+    /// assert_eq!(map[&37], "b");
+    /// ```
+    #[unstable(feature = "try_reserve", reason = "new API", issue = "48043")]
+    #[inline]
+    pub fn try_insert(&mut self, k: K, v: V) -> Result<Option<V>, TryReserveError> {
+        self.try_reserve(1)?;
+        Ok(self.base.insert(k, v))
+    }
+
     /// Removes a key from the map, returning the value at the key if the key
     /// was previously in the map.
     ///
@@ -3488,6 +3522,16 @@ mod test_map {
         }
     }
 
+    #[test]
+    fn test_try_insert() {
+        let mut map: HashMap<u8, u8> = HashMap::new();
+        assert_eq!(map.try_insert(1, 10), Ok(None));
+        assert_eq!(map.try_insert(1, 20), Ok(Some(10)));
+        assert_eq!(map.try_insert(2, 30), Ok(None));
+        assert_eq!(map.len(), 2);
+        assert_eq!(map[&1], 20);
+    }
+
     #[test]
     fn test_raw_entry() {
         use super::RawEntryMut::{Occupied, Vacant};
@@ -286,6 +286,9 @@ mod sync;
 
 mod cache_aligned;
 
+#[unstable(feature = "mpmc_channel", issue = "none")]
+pub mod mpmc;
+
 /// The receiving half of Rust's [`channel`] (or [`sync_channel`]) type.
 /// This half can only be owned by one thread.
 ///
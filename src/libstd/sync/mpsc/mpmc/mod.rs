@@ -0,0 +1,662 @@
+//! Multi-producer, multi-consumer FIFO queue communication primitives.
+//!
+//! This module provides channels whose [`Sender`] and [`Receiver`] halves
+//! can both be cloned, so that many threads may send into and receive from
+//! the same channel. Every message is received by exactly one receiver.
+//!
+//! Channels are created with one of two functions:
+//!
+//! 1. [`channel`] creates an unbounded channel. Sends never block, and the
+//!    channel conceptually has an infinite buffer.
+//!
+//! 2. [`sync_channel`] creates a bounded channel. Sends block while the
+//!    buffer holds `bound` messages. A bound of 0 turns the channel into a
+//!    "rendezvous" channel where each send waits until a receiver takes the
+//!    message.
+//!
+//! The channel is disconnected once all senders or all receivers have been
+//! dropped. Sending into a disconnected channel fails and hands the message
+//! back. Receiving from a disconnected channel fails once every message
+//! already in the buffer has been received.
+//!
+//! Unlike the channels in [`mpsc`], the implementation is lock-free for the
+//! unbounded and non-zero bounded flavors, and threads only park after a
+//! short period of spinning.
+//!
+//! [`Sender`]: struct.Sender.html
+//! [`Receiver`]: struct.Receiver.html
+//! [`channel`]: fn.channel.html
+//! [`sync_channel`]: fn.sync_channel.html
+//! [`mpsc`]: ../index.html
+//!
+//! # Examples
+//!
+//! ```
+//! #![feature(mpmc_channel)]
+//!
+//! use std::sync::mpsc::mpmc::channel;
+//! use std::thread;
+//!
+//! let (tx, rx) = channel();
+//!
+//! let workers: Vec<_> = (0..4)
+//!     .map(|_| {
+//!         let rx = rx.clone();
+//!         thread::spawn(move || rx.iter().map(|x: u32| x * 2).sum::<u32>())
+//!     })
+//!     .collect();
+//! drop(rx);
+//!
+//! for i in 0..100 {
+//!     tx.send(i).unwrap();
+//! }
+//! drop(tx);
+//!
+//! let total: u32 = workers.into_iter().map(|w| w.join().unwrap()).sum();
+//! assert_eq!(total, 9900);
+//! ```
+
+// The flavors are based on the implementation in the crossbeam-channel crate.
+
+mod array;
+mod context;
+mod counter;
+mod list;
+mod select;
+mod utils;
+mod waker;
+mod zero;
+
+use crate::fmt;
+use crate::time::{Duration, Instant};
+
+pub use super::{RecvError, RecvTimeoutError, SendError, TryRecvError, TrySendError};
+
+/// Creates a new unbounded channel, returning the sender/receiver halves.
+///
+/// Sends never block. Both halves can be cloned and shared between threads.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(mpmc_channel)]
+///
+/// use std::sync::mpsc::mpmc::channel;
+///
+/// let (tx, rx) = channel();
+/// let rx2 = rx.clone();
+///
+/// tx.send(1).unwrap();
+/// tx.send(2).unwrap();
+///
+/// assert_eq!(rx.recv(), Ok(1));
+/// assert_eq!(rx2.recv(), Ok(2));
+/// ```
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let (s, r) = counter::new(list::Channel::new());
+    (Sender { flavor: SenderFlavor::List(s) }, Receiver { flavor: ReceiverFlavor::List(r) })
+}
+
+/// Creates a new bounded channel, returning the sender/receiver halves.
+///
+/// The channel can hold at most `bound` messages at a time, and [`send`]
+/// blocks while it is full. If `bound` is 0, every [`send`] blocks until a
+/// receiver takes the message.
+///
+/// [`send`]: struct.Sender.html#method.send
+///
+/// # Examples
+///
+/// ```
+/// #![feature(mpmc_channel)]
+///
+/// use std::sync::mpsc::mpmc::{sync_channel, TrySendError};
+///
+/// let (tx, rx) = sync_channel(1);
+///
+/// tx.send(1).unwrap();
+/// assert_eq!(tx.try_send(2), Err(TrySendError::Full(2)));
+///
+/// assert_eq!(rx.recv(), Ok(1));
+/// tx.try_send(2).unwrap();
+/// ```
+pub fn sync_channel<T>(bound: usize) -> (Sender<T>, Receiver<T>) {
+    if bound == 0 {
+        let (s, r) = counter::new(zero::Channel::new());
+        (Sender { flavor: SenderFlavor::Zero(s) }, Receiver { flavor: ReceiverFlavor::Zero(r) })
+    } else {
+        let (s, r) = counter::new(array::Channel::with_capacity(bound));
+        (Sender { flavor: SenderFlavor::Array(s) }, Receiver { flavor: ReceiverFlavor::Array(r) })
+    }
+}
+
+/// The sending half of a [`channel`] or [`sync_channel`].
+///
+/// Senders can be cloned and sent to other threads. The channel is
+/// disconnected for receivers once every sender has been dropped.
+///
+/// [`channel`]: fn.channel.html
+/// [`sync_channel`]: fn.sync_channel.html
+pub struct Sender<T> {
+    flavor: SenderFlavor<T>,
+}
+
+/// Sender flavors.
+enum SenderFlavor<T> {
+    /// Bounded channel based on a preallocated array.
+    Array(counter::Sender<array::Channel<T>>),
+
+    /// Unbounded channel implemented as a linked list.
+    List(counter::Sender<list::Channel<T>>),
+
+    /// Zero-capacity channel.
+    Zero(counter::Sender<zero::Channel<T>>),
+}
+
+unsafe impl<T: Send> Send for Sender<T> {}
+unsafe impl<T: Send> Sync for Sender<T> {}
+
+impl<T> Sender<T> {
+    /// Attempts to send a message into the channel without blocking.
+    ///
+    /// This fails with [`TrySendError::Full`] if a bounded channel is full
+    /// (or, for a zero-capacity channel, if no receiver is waiting), and with
+    /// [`TrySendError::Disconnected`] if all receivers have been dropped. In
+    /// both cases the message is handed back inside the error.
+    ///
+    /// [`TrySendError::Full`]: enum.TrySendError.html#variant.Full
+    /// [`TrySendError::Disconnected`]: enum.TrySendError.html#variant.Disconnected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpmc_channel)]
+    ///
+    /// use std::sync::mpsc::mpmc::{sync_channel, TrySendError};
+    ///
+    /// let (tx, rx) = sync_channel(1);
+    ///
+    /// assert_eq!(tx.try_send(1), Ok(()));
+    /// assert_eq!(tx.try_send(2), Err(TrySendError::Full(2)));
+    ///
+    /// drop(rx);
+    /// assert_eq!(tx.try_send(3), Err(TrySendError::Disconnected(3)));
+    /// ```
+    pub fn try_send(&self, msg: T) -> Result<(), TrySendError<T>> {
+        match &self.flavor {
+            SenderFlavor::Array(chan) => chan.try_send(msg),
+            SenderFlavor::List(chan) => chan.try_send(msg),
+            SenderFlavor::Zero(chan) => chan.try_send(msg),
+        }
+    }
+
+    /// Sends a message into the channel, blocking while a bounded channel is
+    /// full.
+    ///
+    /// An error is returned if all receivers have been dropped. The message
+    /// is then handed back inside the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpmc_channel)]
+    ///
+    /// use std::sync::mpsc::mpmc::{channel, SendError};
+    ///
+    /// let (tx, rx) = channel();
+    ///
+    /// assert_eq!(tx.send(1), Ok(()));
+    ///
+    /// drop(rx);
+    /// assert_eq!(tx.send(2), Err(SendError(2)));
+    /// ```
+    pub fn send(&self, msg: T) -> Result<(), SendError<T>> {
+        match &self.flavor {
+            SenderFlavor::Array(chan) => chan.send(msg),
+            SenderFlavor::List(chan) => chan.send(msg),
+            SenderFlavor::Zero(chan) => chan.send(msg),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        unsafe {
+            match &self.flavor {
+                SenderFlavor::Array(chan) => chan.release(|c| c.disconnect()),
+                SenderFlavor::List(chan) => chan.release(|c| c.disconnect()),
+                SenderFlavor::Zero(chan) => chan.release(|c| c.disconnect()),
+            }
+        }
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        let flavor = match &self.flavor {
+            SenderFlavor::Array(chan) => SenderFlavor::Array(chan.acquire()),
+            SenderFlavor::List(chan) => SenderFlavor::List(chan.acquire()),
+            SenderFlavor::Zero(chan) => SenderFlavor::Zero(chan.acquire()),
+        };
+
+        Sender { flavor }
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").finish()
+    }
+}
+
+/// The receiving half of a [`channel`] or [`sync_channel`].
+///
+/// Receivers can be cloned and sent to other threads. Each message is
+/// delivered to only one of the receivers. The channel is disconnected for
+/// senders once every receiver has been dropped.
+///
+/// [`channel`]: fn.channel.html
+/// [`sync_channel`]: fn.sync_channel.html
+pub struct Receiver<T> {
+    flavor: ReceiverFlavor<T>,
+}
+
+/// Receiver flavors.
+enum ReceiverFlavor<T> {
+    /// Bounded channel based on a preallocated array.
+    Array(counter::Receiver<array::Channel<T>>),
+
+    /// Unbounded channel implemented as a linked list.
+    List(counter::Receiver<list::Channel<T>>),
+
+    /// Zero-capacity channel.
+    Zero(counter::Receiver<zero::Channel<T>>),
+}
+
+unsafe impl<T: Send> Send for Receiver<T> {}
+unsafe impl<T: Send> Sync for Receiver<T> {}
+
+impl<T> Receiver<T> {
+    /// Attempts to receive a message from the channel without blocking.
+    ///
+    /// This fails with [`TryRecvError::Empty`] if no message is available
+    /// right now, and with [`TryRecvError::Disconnected`] if the channel is
+    /// empty and all senders have been dropped.
+    ///
+    /// [`TryRecvError::Empty`]: enum.TryRecvError.html#variant.Empty
+    /// [`TryRecvError::Disconnected`]: enum.TryRecvError.html#variant.Disconnected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpmc_channel)]
+    ///
+    /// use std::sync::mpsc::mpmc::{channel, TryRecvError};
+    ///
+    /// let (tx, rx) = channel();
+    /// assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    ///
+    /// tx.send(5).unwrap();
+    /// drop(tx);
+    ///
+    /// assert_eq!(rx.try_recv(), Ok(5));
+    /// assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+    /// ```
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        match &self.flavor {
+            ReceiverFlavor::Array(chan) => chan.try_recv(),
+            ReceiverFlavor::List(chan) => chan.try_recv(),
+            ReceiverFlavor::Zero(chan) => chan.try_recv(),
+        }
+    }
+
+    /// Blocks the current thread until a message is received or the channel
+    /// is empty and disconnected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpmc_channel)]
+    ///
+    /// use std::sync::mpsc::mpmc::{channel, RecvError};
+    /// use std::thread;
+    ///
+    /// let (tx, rx) = channel();
+    ///
+    /// thread::spawn(move || {
+    ///     tx.send(5).unwrap();
+    /// });
+    ///
+    /// assert_eq!(rx.recv(), Ok(5));
+    /// assert_eq!(rx.recv(), Err(RecvError));
+    /// ```
+    pub fn recv(&self) -> Result<T, RecvError> {
+        self.recv_inner(None).map_err(|_| RecvError)
+    }
+
+    /// Waits for a message for at most `timeout`.
+    ///
+    /// This fails with [`RecvTimeoutError::Timeout`] if no message arrived in
+    /// time, and with [`RecvTimeoutError::Disconnected`] if the channel is
+    /// empty and all senders have been dropped.
+    ///
+    /// [`RecvTimeoutError::Timeout`]: enum.RecvTimeoutError.html#variant.Timeout
+    /// [`RecvTimeoutError::Disconnected`]: enum.RecvTimeoutError.html#variant.Disconnected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpmc_channel)]
+    ///
+    /// use std::sync::mpsc::mpmc::{channel, RecvTimeoutError};
+    /// use std::time::Duration;
+    ///
+    /// let (tx, rx) = channel::<i32>();
+    ///
+    /// let timeout = Duration::from_millis(10);
+    /// assert_eq!(rx.recv_timeout(timeout), Err(RecvTimeoutError::Timeout));
+    ///
+    /// drop(tx);
+    /// assert_eq!(rx.recv_timeout(timeout), Err(RecvTimeoutError::Disconnected));
+    /// ```
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.recv_inner(Some(deadline)),
+            // So far in the future that it's practically the same as waiting indefinitely.
+            None => self.recv().map_err(RecvTimeoutError::from),
+        }
+    }
+
+    fn recv_inner(&self, deadline: Option<Instant>) -> Result<T, RecvTimeoutError> {
+        match &self.flavor {
+            ReceiverFlavor::Array(chan) => chan.recv(deadline),
+            ReceiverFlavor::List(chan) => chan.recv(deadline),
+            ReceiverFlavor::Zero(chan) => chan.recv(deadline),
+        }
+    }
+
+    /// Returns an iterator that blocks waiting for messages and ends when
+    /// the channel is empty and disconnected.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { rx: self }
+    }
+
+    /// Returns an iterator over the messages that are available right now.
+    /// It never blocks.
+    pub fn try_iter(&self) -> TryIter<'_, T> {
+        TryIter { rx: self }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        unsafe {
+            match &self.flavor {
+                ReceiverFlavor::Array(chan) => chan.release(|c| c.disconnect()),
+                ReceiverFlavor::List(chan) => chan.release(|c| c.disconnect()),
+                ReceiverFlavor::Zero(chan) => chan.release(|c| c.disconnect()),
+            }
+        }
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Self {
+        let flavor = match &self.flavor {
+            ReceiverFlavor::Array(chan) => ReceiverFlavor::Array(chan.acquire()),
+            ReceiverFlavor::List(chan) => ReceiverFlavor::List(chan.acquire()),
+            ReceiverFlavor::Zero(chan) => ReceiverFlavor::Zero(chan.acquire()),
+        };
+
+        Receiver { flavor }
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").finish()
+    }
+}
+
+/// An iterator over messages on a [`Receiver`], created by [`iter`].
+///
+/// [`Receiver`]: struct.Receiver.html
+/// [`iter`]: struct.Receiver.html#method.iter
+#[derive(Debug)]
+pub struct Iter<'a, T: 'a> {
+    rx: &'a Receiver<T>,
+}
+
+/// An iterator over the currently available messages on a [`Receiver`],
+/// created by [`try_iter`].
+///
+/// [`Receiver`]: struct.Receiver.html
+/// [`try_iter`]: struct.Receiver.html#method.try_iter
+#[derive(Debug)]
+pub struct TryIter<'a, T: 'a> {
+    rx: &'a Receiver<T>,
+}
+
+/// An owning iterator over messages on a [`Receiver`], created by
+/// **Receiver::into_iter**.
+///
+/// [`Receiver`]: struct.Receiver.html
+#[derive(Debug)]
+pub struct IntoIter<T> {
+    rx: Receiver<T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.rx.recv().ok()
+    }
+}
+
+impl<'a, T> Iterator for TryIter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.rx.try_recv().ok()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Receiver<T> {
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.rx.recv().ok()
+    }
+}
+
+impl<T> IntoIterator for Receiver<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { rx: self }
+    }
+}
+
+#[cfg(all(test, not(target_os = "emscripten")))]
+mod tests {
+    use super::*;
+    use crate::sync::atomic::{AtomicUsize, Ordering};
+    use crate::sync::Arc;
+    use crate::thread;
+    use crate::time::Duration;
+
+    #[test]
+    fn smoke() {
+        let (tx, rx) = channel::<i32>();
+        tx.send(1).unwrap();
+        assert_eq!(rx.recv().unwrap(), 1);
+    }
+
+    #[test]
+    fn smoke_bounded() {
+        let (tx, rx) = sync_channel::<i32>(1);
+        tx.send(1).unwrap();
+        assert_eq!(tx.try_send(2), Err(TrySendError::Full(2)));
+        assert_eq!(rx.recv().unwrap(), 1);
+    }
+
+    #[test]
+    fn smoke_rendezvous() {
+        let (tx, rx) = sync_channel::<i32>(0);
+        assert_eq!(tx.try_send(1), Err(TrySendError::Full(1)));
+        let t = thread::spawn(move || tx.send(2).unwrap());
+        assert_eq!(rx.recv().unwrap(), 2);
+        t.join().unwrap();
+    }
+
+    #[test]
+    fn try_recv_states() {
+        for &cap in &[None, Some(0), Some(1), Some(4)] {
+            let (tx, rx) = match cap {
+                None => channel::<i32>(),
+                Some(cap) => sync_channel::<i32>(cap),
+            };
+            assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+            drop(tx);
+            assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+        }
+    }
+
+    #[test]
+    fn recv_timeout() {
+        let (tx, rx) = sync_channel::<i32>(0);
+        let timeout = Duration::from_millis(1);
+        assert_eq!(rx.recv_timeout(timeout), Err(RecvTimeoutError::Timeout));
+        drop(tx);
+        assert_eq!(rx.recv_timeout(timeout), Err(RecvTimeoutError::Disconnected));
+
+        let (tx, rx) = channel::<i32>();
+        let t = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(1));
+            tx.send(1).unwrap();
+        });
+        assert_eq!(rx.recv_timeout(Duration::from_secs(60)), Ok(1));
+        t.join().unwrap();
+    }
+
+    #[test]
+    fn recv_drains_after_disconnect() {
+        let (tx, rx) = sync_channel::<i32>(3);
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+        drop(tx);
+        assert_eq!(rx.recv(), Ok(1));
+        assert_eq!(rx.recv(), Ok(2));
+        assert_eq!(rx.recv(), Err(RecvError));
+    }
+
+    #[test]
+    fn send_after_receivers_dropped() {
+        let (tx, rx) = channel::<i32>();
+        let rx2 = rx.clone();
+        drop(rx);
+        tx.send(1).unwrap();
+        drop(rx2);
+        assert_eq!(tx.send(2), Err(SendError(2)));
+    }
+
+    #[test]
+    fn blocked_sender_disconnected() {
+        let (tx, rx) = sync_channel::<i32>(0);
+        let t = thread::spawn(move || tx.send(1));
+        thread::sleep(Duration::from_millis(10));
+        drop(rx);
+        assert_eq!(t.join().unwrap(), Err(SendError(1)));
+    }
+
+    #[test]
+    fn blocked_receiver_disconnected() {
+        let (tx, rx) = sync_channel::<i32>(1);
+        let t = thread::spawn(move || rx.recv());
+        thread::sleep(Duration::from_millis(10));
+        drop(tx);
+        assert_eq!(t.join().unwrap(), Err(RecvError));
+    }
+
+    #[test]
+    fn messages_dropped_with_channel() {
+        struct DropCounter(Arc<AtomicUsize>);
+
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        for &cap in &[None, Some(5)] {
+            let drops = Arc::new(AtomicUsize::new(0));
+            let (tx, rx) = match cap {
+                None => channel(),
+                Some(cap) => sync_channel(cap),
+            };
+            for _ in 0..5 {
+                tx.send(DropCounter(drops.clone())).unwrap();
+            }
+            drop(rx.recv().unwrap());
+            assert_eq!(drops.load(Ordering::SeqCst), 1);
+            drop(tx);
+            drop(rx);
+            assert_eq!(drops.load(Ordering::SeqCst), 5);
+        }
+    }
+
+    #[test]
+    fn mpmc_stress() {
+        const AMT: usize = 10000;
+        const NTHREADS: usize = 4;
+
+        for &cap in &[None, Some(0), Some(1), Some(16)] {
+            let (tx, rx) = match cap {
+                None => channel::<usize>(),
+                Some(cap) => sync_channel::<usize>(cap),
+            };
+            let sum = Arc::new(AtomicUsize::new(0));
+
+            let receivers: Vec<_> = (0..NTHREADS)
+                .map(|_| {
+                    let rx = rx.clone();
+                    let sum = sum.clone();
+                    thread::spawn(move || {
+                        for x in rx {
+                            sum.fetch_add(x, Ordering::SeqCst);
+                        }
+                    })
+                })
+                .collect();
+            drop(rx);
+
+            let senders: Vec<_> = (0..NTHREADS)
+                .map(|_| {
+                    let tx = tx.clone();
+                    thread::spawn(move || {
+                        for i in 0..AMT {
+                            tx.send(i).unwrap();
+                        }
+                    })
+                })
+                .collect();
+            drop(tx);
+
+            for t in senders.into_iter().chain(receivers) {
+                t.join().unwrap();
+            }
+            assert_eq!(sum.load(Ordering::SeqCst), NTHREADS * AMT * (AMT - 1) / 2);
+        }
+    }
+}
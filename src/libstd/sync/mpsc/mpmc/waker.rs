@@ -0,0 +1,178 @@
+use super::context::Context;
+use super::select::{Operation, Selected};
+
+use crate::ptr;
+use crate::sync::atomic::{AtomicBool, Ordering};
+use crate::sync::Mutex;
+
+/// Represents a thread blocked on a specific channel operation.
+pub(super) struct Entry {
+    /// The operation.
+    pub(super) oper: Operation,
+
+    /// Optional packet.
+    pub(super) packet: *mut (),
+
+    /// Context associated with the thread owning this operation.
+    pub(super) cx: Context,
+}
+
+/// A queue of threads blocked on channel operations.
+///
+/// This data structure is used by threads to register blocking operations and get woken up once
+/// an operation becomes ready.
+pub(super) struct Waker {
+    /// A list of blocked operations.
+    selectors: Vec<Entry>,
+}
+
+impl Waker {
+    /// Creates a new `Waker`.
+    #[inline]
+    pub(super) fn new() -> Self {
+        Waker { selectors: Vec::new() }
+    }
+
+    /// Registers an operation.
+    #[inline]
+    pub(super) fn register(&mut self, oper: Operation, cx: &Context) {
+        self.register_with_packet(oper, ptr::null_mut(), cx);
+    }
+
+    /// Registers an operation and a packet.
+    #[inline]
+    pub(super) fn register_with_packet(&mut self, oper: Operation, packet: *mut (), cx: &Context) {
+        self.selectors.push(Entry { oper, packet, cx: cx.clone() });
+    }
+
+    /// Unregisters an operation.
+    #[inline]
+    pub(super) fn unregister(&mut self, oper: Operation) -> Option<Entry> {
+        let pos = self.selectors.iter().position(|entry| entry.oper == oper)?;
+        Some(self.selectors.remove(pos))
+    }
+
+    /// Attempts to find another thread's entry, select the operation, and wake it up.
+    #[inline]
+    pub(super) fn try_select(&mut self) -> Option<Entry> {
+        if self.selectors.is_empty() {
+            return None;
+        }
+
+        let thread_id = current_thread_id();
+        self.selectors
+            .iter()
+            .position(|selector| {
+                // Does the entry belong to a different thread, and can its operation be selected?
+                selector.cx.thread_id() != thread_id
+                    && selector.cx.try_select(Selected::Operation(selector.oper)).is_ok()
+                    && {
+                        // Wake the thread up.
+                        selector.cx.unpark();
+                        true
+                    }
+            })
+            // Remove the entry from the queue to keep it clean and improve performance.
+            .map(|pos| self.selectors.remove(pos))
+    }
+
+    /// Returns `true` if no operations are registered.
+    #[inline]
+    pub(super) fn is_empty(&self) -> bool {
+        self.selectors.is_empty()
+    }
+
+    /// Notifies all registered operations that the channel is disconnected.
+    #[inline]
+    pub(super) fn disconnect(&mut self) {
+        for entry in self.selectors.iter() {
+            if entry.cx.try_select(Selected::Disconnected).is_ok() {
+                // Wake the thread up.
+                //
+                // Here we don't remove the entry from the queue. Registered threads must
+                // unregister from the waker by themselves. They might also want to recover the
+                // packet value and destroy it, if necessary.
+                entry.cx.unpark();
+            }
+        }
+    }
+}
+
+impl Drop for Waker {
+    #[inline]
+    fn drop(&mut self) {
+        debug_assert_eq!(self.selectors.len(), 0);
+    }
+}
+
+/// A waker that can be shared among threads without locking.
+///
+/// This is a simple wrapper around `Waker` that internally uses a mutex for synchronization.
+pub(super) struct SyncWaker {
+    /// The inner `Waker`.
+    inner: Mutex<Waker>,
+
+    /// `true` if the waker is empty.
+    is_empty: AtomicBool,
+}
+
+impl SyncWaker {
+    /// Creates a new `SyncWaker`.
+    #[inline]
+    pub(super) fn new() -> Self {
+        SyncWaker { inner: Mutex::new(Waker::new()), is_empty: AtomicBool::new(true) }
+    }
+
+    /// Registers the current thread with an operation.
+    #[inline]
+    pub(super) fn register(&self, oper: Operation, cx: &Context) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.register(oper, cx);
+        self.is_empty.store(inner.is_empty(), Ordering::SeqCst);
+    }
+
+    /// Unregisters an operation previously registered by the current thread.
+    #[inline]
+    pub(super) fn unregister(&self, oper: Operation) -> Option<Entry> {
+        let mut inner = self.inner.lock().unwrap();
+        let entry = inner.unregister(oper);
+        self.is_empty.store(inner.is_empty(), Ordering::SeqCst);
+        entry
+    }
+
+    /// Attempts to find one thread (not the current one), select its operation, and wake it up.
+    #[inline]
+    pub(super) fn notify(&self) {
+        if !self.is_empty.load(Ordering::SeqCst) {
+            let mut inner = self.inner.lock().unwrap();
+            if !self.is_empty.load(Ordering::SeqCst) {
+                inner.try_select();
+                self.is_empty.store(inner.is_empty(), Ordering::SeqCst);
+            }
+        }
+    }
+
+    /// Notifies all threads that the channel is disconnected.
+    #[inline]
+    pub(super) fn disconnect(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.disconnect();
+        self.is_empty.store(inner.is_empty(), Ordering::SeqCst);
+    }
+}
+
+impl Drop for SyncWaker {
+    #[inline]
+    fn drop(&mut self) {
+        debug_assert!(self.is_empty.load(Ordering::SeqCst));
+    }
+}
+
+/// Returns a unique id for the current thread.
+#[inline]
+pub(super) fn current_thread_id() -> usize {
+    // `u8` has no destructor, so this variable stays available during thread destruction,
+    // whereas `thread::current()` would not be.
+    thread_local! { static DUMMY: u8 = 0 }
+    DUMMY.with(|x| x as *const u8 as usize)
+}
@@ -3,6 +3,7 @@
 use crate::ffi::CStr;
 use crate::io;
 use crate::mem;
+use crate::num::NonZeroUsize;
 use crate::sys::hermit::abi;
 use crate::sys::hermit::fast_thread_local::run_dtors;
 use crate::time::Duration;
@@ -95,6 +96,10 @@ impl Thread {
     }
 }
 
+pub fn available_parallelism() -> io::Result<NonZeroUsize> {
+    super::unsupported()
+}
+
 pub mod guard {
     pub type Guard = !;
     pub unsafe fn current() -> Option<Guard> {
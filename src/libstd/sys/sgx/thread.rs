@@ -1,6 +1,7 @@
 #![cfg_attr(test, allow(dead_code))] // why is this necessary?
 use crate::ffi::CStr;
 use crate::io;
+use crate::num::NonZeroUsize;
 use crate::time::Duration;
 
 use super::abi::usercalls;
@@ -82,6 +83,10 @@ impl Thread {
     }
 }
 
+pub fn available_parallelism() -> io::Result<NonZeroUsize> {
+    super::unsupported()
+}
+
 pub mod guard {
     pub type Guard = !;
     pub unsafe fn current() -> Option<Guard> {
@@ -2,6 +2,7 @@ use crate::cmp;
 use crate::ffi::CStr;
 use crate::io;
 use crate::mem;
+use crate::num::NonZeroUsize;
 use crate::ptr;
 use crate::sys::{os, stack_overflow};
 use crate::time::Duration;
@@ -212,6 +213,317 @@ impl Drop for Thread {
     }
 }
 
+pub fn available_parallelism() -> io::Result<NonZeroUsize> {
+    cfg_if::cfg_if! {
+        if #[cfg(any(
+            target_os = "android",
+            target_os = "emscripten",
+            target_os = "fuchsia",
+            target_os = "ios",
+            target_os = "linux",
+            target_os = "macos",
+            target_os = "solaris",
+            target_os = "illumos",
+        ))] {
+            // Android's `cpu_set_t` comes without the `CPU_COUNT` helper, so only the cgroup
+            // quota applies there.
+            #[cfg(target_os = "linux")]
+            {
+                // The affinity mask reflects `taskset` and container cpusets, both of which
+                // can be narrower than the number of online CPUs.
+                let quota = cgroups::quota().max(1);
+                let mut set: libc::cpu_set_t = unsafe { mem::zeroed() };
+                unsafe {
+                    let size = mem::size_of::<libc::cpu_set_t>();
+                    if libc::sched_getaffinity(0, size, &mut set) == 0 {
+                        let count = libc::CPU_COUNT(&set) as usize;
+                        let count = cmp::min(count, quota);
+                        // `count` is at least 1 here: the calling thread runs on some CPU.
+                        if let Some(count) = NonZeroUsize::new(count) {
+                            return Ok(count);
+                        }
+                    }
+                }
+            }
+            match unsafe { libc::sysconf(libc::_SC_NPROCESSORS_ONLN) } {
+                -1 => Err(io::Error::last_os_error()),
+                0 => Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "The number of hardware threads is not known for the target platform",
+                )),
+                cpus => {
+                    let count = cpus as usize;
+                    // Cover Android, and the unusual situation where we were able to get the
+                    // quota but not the affinity mask.
+                    #[cfg(any(target_os = "android", target_os = "linux"))]
+                    let count = cmp::min(count, cgroups::quota().max(1));
+                    Ok(unsafe { NonZeroUsize::new_unchecked(count) })
+                }
+            }
+        } else if #[cfg(any(
+            target_os = "freebsd",
+            target_os = "dragonfly",
+            target_os = "netbsd",
+        ))] {
+            let online = unsafe { libc::sysconf(libc::_SC_NPROCESSORS_ONLN) };
+            let mut cpus: libc::c_uint = match online {
+                n if n > 0 => n as libc::c_uint,
+                _ => 0,
+            };
+            let mut cpus_size = mem::size_of_val(&cpus);
+
+            // Fallback approach in case of errors or no hardware threads.
+            if cpus < 1 {
+                let mut mib = [libc::CTL_HW, libc::HW_NCPU, 0, 0];
+                let res = unsafe {
+                    libc::sysctl(
+                        mib.as_mut_ptr(),
+                        2,
+                        &mut cpus as *mut _ as *mut _,
+                        &mut cpus_size as *mut _ as *mut _,
+                        ptr::null_mut(),
+                        0,
+                    )
+                };
+
+                // Handle errors if any.
+                if res == -1 {
+                    return Err(io::Error::last_os_error());
+                } else if cpus == 0 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::NotFound,
+                        "The number of hardware threads is not known for the target platform",
+                    ));
+                }
+            }
+            Ok(unsafe { NonZeroUsize::new_unchecked(cpus as usize) })
+        } else if #[cfg(target_os = "openbsd")] {
+            let mut cpus: libc::c_uint = 0;
+            let mut cpus_size = mem::size_of_val(&cpus);
+            let mut mib = [libc::CTL_HW, libc::HW_NCPU, 0, 0];
+
+            let res = unsafe {
+                libc::sysctl(
+                    mib.as_mut_ptr(),
+                    2,
+                    &mut cpus as *mut _ as *mut _,
+                    &mut cpus_size as *mut _ as *mut _,
+                    ptr::null_mut(),
+                    0,
+                )
+            };
+
+            // Handle errors if any.
+            if res == -1 {
+                return Err(io::Error::last_os_error());
+            } else if cpus == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "The number of hardware threads is not known for the target platform",
+                ));
+            }
+
+            Ok(unsafe { NonZeroUsize::new_unchecked(cpus as usize) })
+        } else {
+            // FIXME: implement on vxWorks, Redox, Haiku, l4re
+            Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Getting the number of hardware threads is not supported on the target platform",
+            ))
+        }
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod cgroups {
+    //! Reads the CPU bandwidth limit of the cgroup this process belongs to.
+    //!
+    //! Not covered:
+    //! * cgroup v2 mounted anywhere but `/sys/fs/cgroup`
+    //! * paths containing spaces or control characters, which procfs escapes
+
+    use crate::ffi::OsString;
+    use crate::fs::File;
+    use crate::io::{BufRead, BufReader, Read};
+    use crate::os::unix::ffi::OsStringExt;
+    use crate::path::{Path, PathBuf};
+    use crate::str::from_utf8;
+
+    #[derive(PartialEq)]
+    enum Cgroup {
+        V1,
+        V2,
+    }
+
+    /// Returns the cgroup CPU quota in core-equivalents, rounded down, or
+    /// `usize::MAX` if the quota cannot be determined or is not set.
+    pub(super) fn quota() -> usize {
+        match current_cgroup() {
+            Some((path, Cgroup::V1)) => quota_v1(&path),
+            Some((path, Cgroup::V2)) => quota_v2(&path),
+            None => usize::MAX,
+        }
+    }
+
+    /// Finds our place in the cgroup hierarchy from `/proc/self/cgroup`.
+    ///
+    /// Each line has the form `hierarchy-ID:controller-list:cgroup-path`. The
+    /// controller list is empty for the v2 unified hierarchy. A v1 hierarchy
+    /// with a `cpu` controller wins over v2 since it names its controllers
+    /// explicitly.
+    fn current_cgroup() -> Option<(PathBuf, Cgroup)> {
+        let mut buf = Vec::with_capacity(128);
+        File::open("/proc/self/cgroup").ok()?.read_to_end(&mut buf).ok()?;
+
+        let mut found = None;
+        for line in buf.split(|&c| c == b'\n') {
+            let mut fields = line.splitn(3, |&c| c == b':');
+            let version = match fields.nth(1) {
+                Some(b"") => Cgroup::V2,
+                Some(controllers)
+                    if from_utf8(controllers)
+                        .map_or(false, |c| c.split(',').any(|c| c == "cpu")) =>
+                {
+                    Cgroup::V1
+                }
+                _ => continue,
+            };
+            if found.is_some() && version == Cgroup::V2 {
+                continue;
+            }
+            let path = match fields.next() {
+                // Skip the leading slash so the path can be joined onto a mount point.
+                Some(path) if path.starts_with(b"/") => path[1..].to_vec(),
+                _ => continue,
+            };
+            found = Some((PathBuf::from(OsString::from_vec(path)), version));
+        }
+        found
+    }
+
+    fn quota_v2(group_path: &Path) -> usize {
+        let mut quota = usize::MAX;
+
+        // Standard mount location defined in the file-hierarchy(7) manpage.
+        let cgroup_mount = Path::new("/sys/fs/cgroup");
+        let mut path = cgroup_mount.join(group_path);
+
+        // Skip if we're not looking at a cgroup2 filesystem.
+        if !path.join("cgroup.controllers").exists() {
+            return quota;
+        }
+
+        // Limits of every ancestor apply, so walk up to the root and keep the
+        // smallest one.
+        let mut read_buf = String::with_capacity(20);
+        while path.starts_with(cgroup_mount) {
+            read_buf.clear();
+            path.push("cpu.max");
+            if File::open(&path).and_then(|mut f| f.read_to_string(&mut read_buf)).is_ok() {
+                // The file contains `$MAX $PERIOD`, where `$MAX` may be `max`.
+                let mut fields = read_buf.trim().split(' ');
+                if let (Some(limit), Some(period)) = (fields.next(), fields.next()) {
+                    match (limit.parse::<usize>(), period.parse::<usize>()) {
+                        (Ok(limit), Ok(period)) if period > 0 => {
+                            quota = quota.min(limit / period);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            path.pop(); // pop the file name
+            if !path.pop() {
+                break;
+            }
+        }
+
+        quota
+    }
+
+    fn quota_v1(group_path: &Path) -> usize {
+        let mut quota = usize::MAX;
+
+        // Try the commonly used locations from the cgroups(7) manpage first and
+        // only scan the mount table if neither of them holds our cgroup.
+        let mut candidates = vec![
+            (PathBuf::from("/sys/fs/cgroup/cpu"), group_path.to_path_buf()),
+            (PathBuf::from("/sys/fs/cgroup/cpu,cpuacct"), group_path.to_path_buf()),
+        ];
+        candidates.extend(find_mountpoint(group_path));
+
+        let mut read_buf = String::with_capacity(20);
+        for (mount, group_path) in candidates {
+            let mut path = mount.join(&group_path);
+            if !path.exists() {
+                continue;
+            }
+
+            while path.starts_with(&mount) {
+                let mut parse_file = |path: &mut PathBuf, name| {
+                    path.push(name);
+                    read_buf.clear();
+                    let res =
+                        File::open(&*path).and_then(|mut f| f.read_to_string(&mut read_buf));
+                    path.pop();
+                    res.ok()?;
+                    // An unlimited quota is reported as `-1`, which fails to parse.
+                    read_buf.trim().parse::<usize>().ok()
+                };
+
+                let limit = parse_file(&mut path, "cpu.cfs_quota_us");
+                let period = parse_file(&mut path, "cpu.cfs_period_us");
+                match (limit, period) {
+                    (Some(limit), Some(period)) if period > 0 => quota = quota.min(limit / period),
+                    _ => {}
+                }
+
+                if !path.pop() {
+                    break;
+                }
+            }
+
+            // The path existed, so this was the right hierarchy.
+            break;
+        }
+
+        quota
+    }
+
+    /// Scans `/proc/self/mountinfo` for a cgroup v1 mount with a `cpu`
+    /// controller.
+    ///
+    /// If the cgroupfs is a bind mount, the returned group path has the bound
+    /// prefix stripped from it.
+    fn find_mountpoint(group_path: &Path) -> Option<(PathBuf, PathBuf)> {
+        let reader = BufReader::new(File::open("/proc/self/mountinfo").ok()?);
+        for line in reader.lines() {
+            let line = line.ok()?;
+            let mut items = line.trim().split(' ');
+
+            // Fields: mount ID, parent ID, major:minor, root, mount point, ...,
+            // `-`, filesystem type, mount source, super options.
+            let sub_path = items.nth(3)?;
+            let mount_point = items.next()?;
+            let mount_opts = items.next_back()?;
+            let filesystem_type = items.nth_back(1)?;
+
+            if filesystem_type != "cgroup" || !mount_opts.split(',').any(|opt| opt == "cpu") {
+                continue;
+            }
+
+            let sub_path = match Path::new(sub_path).strip_prefix("/") {
+                Ok(sub_path) => sub_path,
+                Err(_) => continue,
+            };
+            if let Ok(trimmed) = group_path.strip_prefix(sub_path) {
+                return Some((PathBuf::from(mount_point), trimmed.to_path_buf()));
+            }
+        }
+
+        None
+    }
+}
+
 #[cfg(all(
     not(all(target_os = "linux", not(target_env = "musl"))),
     not(target_os = "freebsd"),
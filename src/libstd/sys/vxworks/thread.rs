@@ -2,6 +2,7 @@ use crate::cmp;
 use crate::ffi::CStr;
 use crate::io;
 use crate::mem;
+use crate::num::NonZeroUsize;
 use crate::ptr;
 use crate::sys::{os, stack_overflow};
 use crate::time::Duration;
@@ -137,6 +138,14 @@ impl Drop for Thread {
     }
 }
 
+pub fn available_parallelism() -> io::Result<NonZeroUsize> {
+    // FIXME: implement on vxWorks
+    Err(io::Error::new(
+        io::ErrorKind::Other,
+        "Getting the number of hardware threads is not supported on the target platform",
+    ))
+}
+
 #[cfg_attr(test, allow(dead_code))]
 pub mod guard {
     use crate::ops::Range;
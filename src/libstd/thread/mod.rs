@@ -162,7 +162,7 @@ use crate::ffi::{CStr, CString};
 use crate::fmt;
 use crate::io;
 use crate::mem;
-use crate::num::NonZeroU64;
+use crate::num::{NonZeroU64, NonZeroUsize};
 use crate::panic;
 use crate::panicking;
 use crate::str;
@@ -1511,6 +1511,65 @@ impl<T> fmt::Debug for JoinHandle<T> {
     }
 }
 
+/// Returns an estimate of the default amount of parallelism a program should use.
+///
+/// Parallelism is a resource. A given machine provides a certain capacity for
+/// parallelism, i.e., a bound on the number of computations it can perform
+/// simultaneously. This number often corresponds to the number of CPUs a
+/// computer has, but it may diverge in various cases.
+///
+/// Host environments such as VMs or container orchestrators may want to
+/// restrict the amount of parallelism made available to programs in them. This
+/// is often done to limit the potential impact of (unintentionally)
+/// resource-intensive programs on other programs running on the same machine.
+///
+/// # Limitations
+///
+/// The purpose of this API is to provide an easy and portable way to query
+/// the default amount of parallelism the program should use. Among other things
+/// it does not expose information on NUMA regions, does not account for
+/// differences in (co)processor capabilities, and will not modify the program's
+/// global state in order to more accurately query the amount of available
+/// parallelism.
+///
+/// The value returned by this function should be considered a simplified
+/// approximation of the actual amount of parallelism available at any given
+/// time. To get a more detailed or precise overview of the amount of
+/// parallelism available to the program, you may wish to use
+/// platform-specific APIs as well. The following platform limitations
+/// currently apply to `available_parallelism`:
+///
+/// On Linux:
+/// - It respects the affinity mask of the calling thread (as set by
+///   `sched_setaffinity` or `taskset`), so calling it from a thread whose
+///   mask differs from the rest of the process may over- or undercount.
+/// - It takes CPU quotas set through cgroup v1 (`cpu.cfs_quota_us`) or cgroup
+///   v2 (`cpu.max`) into account, rounded down to whole CPUs but never below
+///   one.
+///
+/// # Errors
+///
+/// This function will return an error in the following situations, but is not
+/// limited to just these cases:
+///
+/// - If the number of hardware threads is not known for the target platform.
+/// - The process lacks permissions to view the number of hardware threads
+///   available.
+///
+/// # Examples
+///
+/// ```
+/// # #![allow(dead_code)]
+/// #![feature(available_parallelism)]
+/// use std::thread;
+///
+/// let count = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+/// ```
+#[unstable(feature = "available_parallelism", issue = "74479")]
+pub fn available_parallelism() -> io::Result<NonZeroUsize> {
+    imp::available_parallelism()
+}
+
 fn _assert_sync_and_send() {
     fn _assert_both<T: Send + Sync>() {}
     _assert_both::<JoinHandle<()>>();
@@ -1786,6 +1845,25 @@ mod tests {
         assert!(thread::current().id() != spawned_id);
     }
 
+    #[test]
+    #[cfg(any(windows, target_os = "linux", target_os = "macos"))]
+    fn test_available_parallelism() {
+        assert!(super::available_parallelism().unwrap().get() >= 1);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_available_parallelism_affinity() {
+        let mut set: libc::cpu_set_t = unsafe { mem::zeroed() };
+        let size = mem::size_of::<libc::cpu_set_t>();
+        assert_eq!(unsafe { libc::sched_getaffinity(0, size, &mut set) }, 0);
+        let allowed = (0..libc::CPU_SETSIZE as usize)
+            .filter(|&cpu| unsafe { libc::CPU_ISSET(cpu, &set) })
+            .count();
+        let count = super::available_parallelism().unwrap().get();
+        assert!(count <= allowed, "{} threads for {} allowed CPUs", count, allowed);
+    }
+
     // NOTE: the corresponding test for stderr is in ui/thread-stderr, due
     // to the test harness apparently interfering with stderr configuration.
 }
@@ -20,12 +20,14 @@
 #include "llvm/Passes/StandardInstrumentations.h"
 #endif
 #include "llvm/Support/CBindingWrapping.h"
+#include "llvm/Support/Compression.h"
 #include "llvm/Support/FileSystem.h"
 #include "llvm/Support/Host.h"
 #include "llvm/Target/TargetMachine.h"
 #include "llvm/Transforms/IPO/PassManagerBuilder.h"
 #include "llvm/Transforms/IPO/AlwaysInliner.h"
 #include "llvm/Transforms/IPO/FunctionImport.h"
+#include "llvm/Transforms/Utils.h"
 #include "llvm/Transforms/Utils/FunctionImportUtils.h"
 #include "llvm/LTO/LTO.h"
 #include "llvm-c/Transforms/PassManagerBuilder.h"
@@ -308,6 +310,26 @@ static Optional<CodeModel::Model> fromRust(LLVMRustCodeModel Model) {
   }
 }
 
+enum class LLVMRustDebugCompression {
+  None,
+  Zlib,
+};
+
+static DebugCompressionType fromRust(LLVMRustDebugCompression Compression) {
+  switch (Compression) {
+  case LLVMRustDebugCompression::None:
+    return DebugCompressionType::None;
+  case LLVMRustDebugCompression::Zlib:
+    return DebugCompressionType::Z;
+  default:
+    report_fatal_error("Bad DebugCompression.");
+  }
+}
+
+extern "C" bool LLVMRustLLVMHasZlibCompression() {
+  return zlib::isAvailable();
+}
+
 enum class LLVMRustCodeGenOptLevel {
   Other,
   None,
@@ -465,7 +487,8 @@ extern "C" LLVMTargetMachineRef LLVMRustCreateTargetMachine(
     bool AsmComments,
     bool EmitStackSizeSection,
     bool RelaxELFRelocations,
-    bool UseInitArray) {
+    bool UseInitArray,
+    LLVMRustDebugCompression RustDebugCompression) {
 
   auto OptLevel = fromRust(RustOptLevel);
   auto RM = fromRust(RustReloc);
@@ -507,6 +530,7 @@ extern "C" LLVMTargetMachineRef LLVMRustCreateTargetMachine(
   }
 
   Options.EmitStackSizeSection = EmitStackSizeSection;
+  Options.CompressDebugSections = fromRust(RustDebugCompression);
 
   TargetMachine *TM = TheTarget->createTargetMachine(
       Trip.getTriple(), CPU, Feature, Options, RM, CM, OptLevel);
@@ -520,7 +544,7 @@ extern "C" void LLVMRustDisposeTargetMachine(LLVMTargetMachineRef TM) {
 extern "C" void LLVMRustConfigurePassManagerBuilder(
     LLVMPassManagerBuilderRef PMBR, LLVMRustCodeGenOptLevel OptLevel,
     bool MergeFunctions, bool SLPVectorize, bool LoopVectorize, bool PrepareForThinLTO,
-    const char* PGOGenPath, const char* PGOUsePath) {
+    const char* PGOGenPath, const char* PGOUsePath, const char* PGOSampleUsePath) {
   unwrap(PMBR)->MergeFunctions = MergeFunctions;
   unwrap(PMBR)->SLPVectorize = SLPVectorize;
   unwrap(PMBR)->OptLevel = fromRust(OptLevel);
@@ -536,6 +560,18 @@ extern "C" void LLVMRustConfigurePassManagerBuilder(
     assert(!PGOGenPath);
     unwrap(PMBR)->PGOInstrUse = PGOUsePath;
   }
+  if (PGOSampleUsePath) {
+    assert(!PGOGenPath && !PGOUsePath);
+    unwrap(PMBR)->PGOSampleUse = PGOSampleUsePath;
+    // Samples of the code sharing a source line are told apart by the line's
+    // discriminators, which have to be assigned before the profile is loaded.
+    // This mirrors what Clang does for `-fprofile-sample-use`.
+    unwrap(PMBR)->addExtension(
+        PassManagerBuilder::EP_EarlyAsPossible,
+        [](const PassManagerBuilder &, legacy::PassManagerBase &PM) {
+          PM.add(createAddDiscriminatorsPass());
+        });
+  }
 }
 
 // Unfortunately, the LLVM C API doesn't provide a way to set the `LibraryInfo`
@@ -626,7 +662,7 @@ static TargetMachine::CodeGenFileType fromRust(LLVMRustFileType Type) {
 
 extern "C" LLVMRustResult
 LLVMRustWriteOutputFile(LLVMTargetMachineRef Target, LLVMPassManagerRef PMR,
-                        LLVMModuleRef M, const char *Path,
+                        LLVMModuleRef M, const char *Path, const char *DwoPath,
                         LLVMRustFileType RustFileType) {
   llvm::legacy::PassManager *PM = unwrap<llvm::legacy::PassManager>(PMR);
   auto FileType = fromRust(RustFileType);
@@ -642,6 +678,28 @@ LLVMRustWriteOutputFile(LLVMTargetMachineRef Target, LLVMPassManagerRef PMR,
   }
 
   buffer_ostream BOS(OS);
+  if (DwoPath) {
+    // Split DWARF is written to a separate stream, and the skeleton units in
+    // the object file refer to it by the name set in the target options.
+    raw_fd_ostream DOS(DwoPath, EC, sys::fs::F_None);
+    if (EC)
+      ErrorInfo = EC.message();
+    if (ErrorInfo != "") {
+      LLVMRustSetLastError(ErrorInfo.c_str());
+      return LLVMRustResult::Failure;
+    }
+
+    buffer_ostream DBOS(DOS);
+    unwrap(Target)->Options.MCOptions.SplitDwarfFile = DwoPath;
+    unwrap(Target)->addPassesToEmitFile(*PM, BOS, &DBOS, FileType, false);
+    PM->run(*unwrap(M));
+
+    // The pass manager also refers to the on-the-stack DWARF output stream
+    // (DOS), so it has to be deleted before leaving this scope.
+    LLVMDisposePassManager(PMR);
+    return LLVMRustResult::Success;
+  }
+
   unwrap(Target)->addPassesToEmitFile(*PM, BOS, nullptr, FileType, false);
   PM->run(*unwrap(M));
 
@@ -735,7 +793,7 @@ LLVMRustOptimizeWithNewPassManager(
     bool MergeFunctions, bool UnrollLoops, bool SLPVectorize, bool LoopVectorize,
     bool DisableSimplifyLibCalls, bool EmitLifetimeMarkers,
     LLVMRustSanitizerOptions *SanitizerOptions,
-    const char *PGOGenPath, const char *PGOUsePath,
+    const char *PGOGenPath, const char *PGOUsePath, const char *PGOSampleUsePath,
     void* LlvmSelfProfiler,
     LLVMRustSelfProfileBeforePassCallback BeforePassCallback,
     LLVMRustSelfProfileAfterPassCallback AfterPassCallback) {
@@ -768,6 +826,10 @@ LLVMRustOptimizeWithNewPassManager(
   } else if (PGOUsePath) {
     assert(!PGOGenPath);
     PGOOpt = PGOOptions(PGOUsePath, "", "", PGOOptions::IRUse);
+  } else if (PGOSampleUsePath) {
+    // `SampleUse` also makes the pipeline assign discriminators before the
+    // profile is loaded.
+    PGOOpt = PGOOptions(PGOSampleUsePath, "", "", PGOOptions::SampleUse);
   }
 
   PassBuilder PB(TM, PTO, PGOOpt, &PIC);
@@ -208,6 +208,12 @@ static Attribute::AttrKind fromRust(LLVMRustAttribute Kind) {
     return Attribute::ReadNone;
   case InaccessibleMemOnly:
     return Attribute::InaccessibleMemOnly;
+  case StackProtect:
+    return Attribute::StackProtect;
+  case StackProtectStrong:
+    return Attribute::StackProtectStrong;
+  case StackProtectReq:
+    return Attribute::StackProtectReq;
   }
   report_fatal_error("bad AttributeKind");
 }
@@ -679,6 +685,12 @@ extern "C" LLVMValueRef LLVMRustMetadataAsValue(LLVMContextRef C, LLVMMetadataRe
   return wrap(MetadataAsValue::get(*unwrap(C), unwrap(MD)));
 }
 
+extern "C" void LLVMRustGlobalAddMetadata(LLVMValueRef Global, unsigned Kind,
+                                          LLVMValueRef MD) {
+  MDNode *N = cast<MDNode>(unwrap<MetadataAsValue>(MD)->getMetadata());
+  unwrap<GlobalObject>(Global)->addMetadata(Kind, *N);
+}
+
 extern "C" LLVMRustDIBuilderRef LLVMRustDIBuilderCreate(LLVMModuleRef M) {
   return new DIBuilder(*unwrap(M));
 }
@@ -696,13 +708,15 @@ extern "C" LLVMMetadataRef LLVMRustDIBuilderCreateCompileUnit(
     const char *Producer, size_t ProducerLen, bool isOptimized,
     const char *Flags, unsigned RuntimeVer,
     const char *SplitName, size_t SplitNameLen,
-    LLVMRustDebugEmissionKind Kind) {
+    LLVMRustDebugEmissionKind Kind, bool DebugInfoForProfiling) {
   auto *File = unwrapDI<DIFile>(FileRef);
 
   return wrap(Builder->createCompileUnit(Lang, File, StringRef(Producer, ProducerLen),
                                          isOptimized, Flags, RuntimeVer,
                                          StringRef(SplitName, SplitNameLen),
-                                         fromRust(Kind)));
+                                         fromRust(Kind), /* DWOId */ 0,
+                                         /* SplitDebugInlining */ true,
+                                         DebugInfoForProfiling));
 }
 
 extern "C" LLVMMetadataRef LLVMRustDIBuilderCreateFile(
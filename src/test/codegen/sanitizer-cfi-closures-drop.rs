@@ -0,0 +1,48 @@
+// Verifies that `-Zsanitizer=cfi` gives a closure coerced to a `fn` pointer the
+// type identifier of that `fn` pointer, and checks the destructors called
+// through vtables.
+//
+// needs-sanitizer-cfi
+// compile-flags: -Clto -Zsanitizer=cfi -Copt-level=0
+
+#![crate_type = "lib"]
+
+pub trait Trait {
+    fn method(&self) -> u32;
+}
+
+pub struct Foo(u32);
+
+impl Trait for Foo {
+    fn method(&self) -> u32 {
+        self.0
+    }
+}
+
+impl Drop for Foo {
+    fn drop(&mut self) {}
+}
+
+pub fn closure_as_fn_ptr() -> fn(i32) -> i32 {
+    |x| x + 1
+}
+
+pub fn call_fn_ptr(f: fn(i32) -> i32, x: i32) -> i32 {
+    f(x)
+}
+
+pub fn drop_dyn(t: Box<dyn Trait>) -> u32 {
+    t.method()
+}
+
+pub fn make_dyn() -> Box<dyn Trait> {
+    Box::new(Foo(0))
+}
+
+// The `FnOnce::call_once` shim the closure is reified as is the target of the
+// `fn` pointer, and the drop glue of `Foo` is the destructor in its vtable.
+// CHECK-DAG: define{{.*}}FnOnce9call_once{{.*}}!type ![[TYPE_CLOSURE:[0-9]+]]
+// CHECK-DAG: call i1 @llvm.type.test(i8* {{%.+}}, metadata !"FlEl")
+// CHECK-DAG: call i1 @llvm.type.test(i8* {{%.+}}, metadata !"FpEu")
+// CHECK-DAG: ![[TYPE_CLOSURE]] = !{i64 0, !"FlEl"}
+// CHECK-DAG: !{i64 0, !"FpEu"}
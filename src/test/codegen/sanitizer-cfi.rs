@@ -0,0 +1,45 @@
+// Verifies that `-Zsanitizer=cfi` attaches type metadata to function definitions,
+// and checks the targets of `fn` pointer and `dyn Trait` method calls against it.
+//
+// needs-sanitizer-cfi
+// compile-flags: -Clto -Zsanitizer=cfi -Copt-level=0
+
+#![crate_type = "lib"]
+
+pub trait Trait {
+    fn method(&self, x: i32) -> i32;
+}
+
+pub struct Foo;
+
+impl Trait for Foo {
+    // CHECK-LABEL: define{{.*}}6method{{.*}}!type !{{[0-9]+}} !type ![[TYPE_VIRTUAL:[0-9]+]]
+    fn method(&self, x: i32) -> i32 {
+        x
+    }
+}
+
+// CHECK-LABEL: define{{.*}}3foo{{.*}}!type ![[TYPE_FN:[0-9]+]]
+pub fn foo(x: i32) -> i32 {
+    x
+}
+
+// CHECK-LABEL: define{{.*}}call_fn_ptr
+pub fn call_fn_ptr(f: fn(i32) -> i32, x: i32) -> i32 {
+    // CHECK: [[TT:%.+]] = call i1 @llvm.type.test(i8* {{%.+}}, metadata !"FlEl")
+    // CHECK: br i1 [[TT]], label %type_test.pass, label %type_test.fail
+    // CHECK: type_test.fail:
+    // CHECK-NEXT: call void @llvm.trap()
+    // CHECK-NEXT: unreachable
+    f(x)
+}
+
+// CHECK-LABEL: define{{.*}}call_dyn
+pub fn call_dyn(t: &dyn Trait, x: i32) -> i32 {
+    // CHECK: [[TT:%.+]] = call i1 @llvm.type.test(i8* {{%.+}}, metadata !"FplEl")
+    // CHECK: br i1 [[TT]], label %type_test.pass, label %type_test.fail
+    t.method(x)
+}
+
+// CHECK-DAG: ![[TYPE_VIRTUAL]] = !{i64 0, !"FplEl"}
+// CHECK-DAG: ![[TYPE_FN]] = !{i64 0, !"FlEl"}
@@ -1,6 +1,6 @@
-// Test that the initial version of Rust coverage injects count_code_region() placeholder calls,
-// at the top of each function. The placeholders are later converted into LLVM instrprof.increment
-// intrinsics, during codegen.
+// Test that `-Zinstrument-coverage` injects `Coverage` statements, declaring the counters and
+// counter expressions of each function and the code regions they count. Codegen converts the
+// counters into LLVM instrprof.increment intrinsics, and the code regions into the coverage map.
 
 // needs-profiler-support
 // compile-flags: -Zinstrument-coverage
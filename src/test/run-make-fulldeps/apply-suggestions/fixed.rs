@@ -0,0 +1,9 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+fn main() {
+    let x = 1;
+    let y = x + 1;
+    println!("{}", y);
+}
@@ -0,0 +1,17 @@
+fn used_function() {}
+
+fn unused_function() {
+    used_function();
+}
+
+fn unused_generic_function<T: Default>() -> T {
+    T::default()
+}
+
+fn main() {
+    let unused_closure = |x: u32| {
+        x + 1
+    };
+    let _ = &unused_closure;
+    used_function();
+}
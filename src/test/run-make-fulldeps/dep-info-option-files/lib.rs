@@ -0,0 +1,6 @@
+pub fn unused_variable() {
+    let x = 0;
+}
+
+#[no_mangle]
+pub extern "C" fn exported() {}
@@ -0,0 +1,13 @@
+#![crate_type = "cdylib"]
+
+#[no_mangle]
+pub extern "C" fn plugin_init() {}
+
+#[no_mangle]
+pub extern "C" fn plugin_extra() {}
+
+#[no_mangle]
+pub extern "C" fn plugin_run_v2() {}
+
+#[no_mangle]
+pub extern "C" fn plugin_debug_hook() {}
@@ -0,0 +1,7 @@
+extern "C" {
+    fn native_func() -> i32;
+}
+
+fn main() {
+    assert_eq!(unsafe { native_func() }, 42);
+}
@@ -0,0 +1 @@
+int native_func() { return 42; }
@@ -0,0 +1 @@
+int unused_func() { return 0; }
@@ -0,0 +1,10 @@
+#[no_mangle]
+#[inline(never)]
+pub fn sampled_function(x: u32) -> u32 {
+    if x > 100 { x * 2 } else { x + 1 }
+}
+
+fn main() {
+    let x = std::env::args().count() as u32;
+    println!("{}", sampled_function(x));
+}
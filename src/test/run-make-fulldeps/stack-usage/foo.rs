@@ -0,0 +1,33 @@
+#![crate_type = "rlib"]
+
+#[inline(never)]
+fn leaf(x: &[u8; 64]) -> u8 {
+    x[0]
+}
+
+#[no_mangle]
+pub extern "C" fn bounded() -> u8 {
+    leaf(&[0; 64])
+}
+
+fn countdown(n: u32) {
+    if n > 0 {
+        countdown(n - 1);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn recursive() {
+    countdown(10);
+}
+
+#[no_mangle]
+pub extern "C" fn indirect() {
+    let f: extern "C" fn() -> u8 = bounded;
+    f();
+}
+
+#[no_mangle]
+pub extern "C" fn panics() {
+    panic!("explicit panic");
+}
@@ -0,0 +1,28 @@
+#![crate_type = "rlib"]
+#![feature(stack_limit)]
+
+#[inline(never)]
+fn leaf(x: &[u8; 64]) -> u8 {
+    x[0]
+}
+
+#[stack_limit = 16]
+pub fn bounded() -> u8 {
+    leaf(&[0; 64])
+}
+
+fn countdown(n: u32) {
+    if n > 0 {
+        countdown(n - 1);
+    }
+}
+
+#[stack_limit = 4096]
+pub fn recursive() {
+    countdown(10);
+}
+
+#[stack_limit = 65536]
+pub fn within_limit() -> u8 {
+    leaf(&[0; 64])
+}
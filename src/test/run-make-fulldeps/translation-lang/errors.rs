@@ -0,0 +1,9 @@
+struct Foo {
+    x: i32,
+}
+
+fn main() {
+    let _ = Foo { x: 0, x: 1 };
+    let _ = Foo { x: 0 }.y;
+    let _ = 0u8[0];
+}
@@ -0,0 +1,5 @@
+# Only some of the messages of E0062 are translated, the others fall back to English.
+typeck-field-multiply-specified = le champ `{$ident}` est spécifié plus d'une fois
+typeck-field-multiply-specified-previous-label = première utilisation de `{$ident}`
+typeck-no-such-field = aucun champ `{$field}` sur le type `{$ty}`
+typeck-cannot-index = impossible d'indexer une valeur de type `{$ty}`
@@ -0,0 +1,40 @@
+#![crate_type = "rlib"]
+
+pub fn used_by_b() {}
+pub fn used_by_main() {}
+pub fn used_by_a() {}
+pub fn unused_fn() {}
+pub fn used_by_dead_code() {}
+
+#[allow(unused_pub_items)]
+pub fn allowed_fn() {}
+
+#[allow(unused_pub_items)]
+pub mod allowed_mod {
+    pub fn allowed_by_mod() {}
+}
+
+pub struct Used;
+pub struct Tuple(pub u32);
+pub struct Unused;
+
+impl Used {
+    pub fn used_method(&self) {}
+    pub fn unused_method(&self) {}
+}
+
+pub trait Implemented {}
+pub trait UnusedTrait {}
+
+pub mod inner {
+    pub const UNUSED_CONST: u32 = 0;
+
+    pub fn reexported() {}
+}
+
+pub use inner::reexported;
+
+pub fn uses_a() -> Tuple {
+    used_by_a();
+    Tuple(0)
+}
@@ -0,0 +1,19 @@
+#![crate_type = "rlib"]
+
+extern crate a;
+
+pub fn used_by_main() {
+    a::used_by_b();
+    a::Used.used_method();
+}
+
+pub fn unused_fn() {}
+
+#[allow(dead_code)]
+fn dead() {
+    a::used_by_dead_code();
+}
+
+pub struct Local;
+
+impl a::Implemented for Local {}
@@ -0,0 +1,10 @@
+extern crate a;
+extern crate b;
+
+fn main() {
+    a::used_by_main();
+    a::uses_a();
+    a::reexported();
+    b::used_by_main();
+    let _ = b::Local;
+}
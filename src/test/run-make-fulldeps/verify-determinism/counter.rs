@@ -0,0 +1,13 @@
+#![crate_type = "proc-macro"]
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+#[proc_macro]
+pub fn counter(_: TokenStream) -> TokenStream {
+    format!("pub const COUNT: usize = {};", COUNT.fetch_add(1, Ordering::SeqCst)).parse().unwrap()
+}
@@ -0,0 +1,13 @@
+pub static STATIC: i32 = 1234;
+
+pub struct Struct<T> {
+    pub field: T,
+}
+
+pub fn regular_fn(x: i32) -> i32 {
+    x * 2
+}
+
+pub fn generic_fn<T: Clone>(s: &Struct<T>) -> T {
+    s.field.clone()
+}
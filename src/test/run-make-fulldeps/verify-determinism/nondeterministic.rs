@@ -0,0 +1,9 @@
+#![crate_type = "rlib"]
+
+extern crate counter;
+
+counter::counter!();
+
+pub fn count() -> usize {
+    COUNT
+}
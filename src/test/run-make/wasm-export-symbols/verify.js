@@ -0,0 +1,20 @@
+const fs = require('fs');
+const process = require('process');
+const buffer = fs.readFileSync(process.argv[2]);
+
+let m = new WebAssembly.Module(buffer);
+let list = WebAssembly.Module.exports(m);
+console.log('exports', list);
+
+const my_exports = {};
+for (const entry of list) {
+  my_exports[entry.name] = entry.kind;
+}
+
+for (const name of ['plugin_init', 'plugin_extra', 'plugin_run_v2']) {
+  if (my_exports[name] != "function")
+    throw new Error("`" + name + "` wasn't exported");
+}
+
+if (my_exports.plugin_debug_hook !== undefined)
+  throw new Error("`plugin_debug_hook` shouldn't be exported");
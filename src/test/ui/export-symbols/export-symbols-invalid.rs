@@ -0,0 +1,7 @@
+// compile-flags: -Z export-symbols={{src-base}}/export-symbols/export-symbols-invalid.txt
+// error-pattern: symbol patterns like `plugin_*` are not supported
+
+#![crate_type = "cdylib"]
+
+#[no_mangle]
+pub extern "C" fn plugin_init() {}
@@ -0,0 +1,5 @@
+#[expect(unused_variables)]
+//~^ ERROR the `#[expect]` attribute is an experimental feature
+fn main() {
+    let x = 1;
+}
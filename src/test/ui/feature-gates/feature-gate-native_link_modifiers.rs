@@ -0,0 +1,5 @@
+#[link(name = "foo", kind = "static", modifiers = "+whole-archive")]
+//~^ ERROR: linking modifiers are experimental
+extern "C" {}
+
+fn main() {}
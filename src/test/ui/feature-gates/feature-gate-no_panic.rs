@@ -0,0 +1,6 @@
+#[no_panic] //~ ERROR the `#[no_panic]` attribute is an experimental feature
+fn foo() {}
+
+fn main() {
+    foo();
+}
@@ -0,0 +1,6 @@
+#[stack_limit = 1024] //~ ERROR the `#[stack_limit]` attribute is an experimental feature
+fn foo() {}
+
+fn main() {
+    foo();
+}
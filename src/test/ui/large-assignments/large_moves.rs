@@ -0,0 +1,34 @@
+// build-fail
+// only-x86_64
+
+#![feature(large_assignments)]
+#![deny(large_assignments)]
+#![move_size_limit = "1000"]
+
+struct Big([u8; 2000]);
+struct Other([u8; 2000]);
+
+fn consume(_: Big) {}
+
+fn make() -> Big {
+    Big([0; 2000]) //~ ERROR moving 2000 bytes
+}
+
+fn make_other() -> Other {
+    Other([0; 2000]) //~ ERROR moving 2000 bytes
+}
+
+// Only linted for the first instantiation.
+fn forward<T>(t: T) -> T {
+    t //~ ERROR moving 2000 bytes
+}
+
+fn main() {
+    let x = make();
+    let y = x; //~ ERROR moving 2000 bytes
+    consume(y); //~ ERROR moving 2000 bytes
+    forward(make()); //~ ERROR moving 2000 bytes
+    forward(make_other()); //~ ERROR moving 2000 bytes
+    let small = [0u8; 10];
+    let _copy = small;
+}
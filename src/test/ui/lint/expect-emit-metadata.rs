@@ -0,0 +1,22 @@
+// Tests that the expectations of lints emitted while generating code aren't reported as
+// unfulfilled when no code is generated, as the lints may not have run.
+
+// build-pass
+// compile-flags: --emit=metadata
+
+#![feature(lint_reasons)]
+
+#[expect(arithmetic_overflow, reason = "only reported when generating code")]
+fn mir_lint() {
+    let _b = 200u8 + 200u8;
+}
+
+#[expect(unused_variables)]
+fn late_lint() {
+    let x = 1;
+}
+
+fn main() {
+    mir_lint();
+    late_lint();
+}
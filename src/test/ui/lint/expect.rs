@@ -0,0 +1,34 @@
+// Tests that `#[expect]` suppresses lints like `#[allow]`, and that it warns when the
+// expected lint is never emitted in its scope.
+
+// build-pass
+
+#![feature(lint_reasons)]
+
+#[expect(unused_variables)]
+fn fulfilled() {
+    let x = 1;
+}
+
+#[expect(arithmetic_overflow, reason = "MIR lints count too")]
+fn fulfilled_by_mir_lint() {
+    let _b = 200u8 + 200u8;
+}
+
+#[expect(unused_variables, reason = "`x` is used after all")]
+//~^ WARNING this lint expectation is unfulfilled
+fn unfulfilled() {
+    let x = 1;
+    println!("{}", x);
+}
+
+#[expect(unused)]
+//~^ WARNING this lint expectation is unfulfilled
+fn unfulfilled_group() {}
+
+fn main() {
+    fulfilled();
+    fulfilled_by_mir_lint();
+    unfulfilled();
+    unfulfilled_group();
+}
@@ -0,0 +1,4 @@
+// compile-flags: -Z lint-config={{src-base}}/lint/lint-config/lint-config-invalid.txt
+// error-pattern: unknown lint level `expect`
+
+fn main() {}
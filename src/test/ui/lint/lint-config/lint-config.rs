@@ -0,0 +1,18 @@
+// Checks that lint levels can be set with `-Z lint-config`, that command-line flags override
+// them, and that attributes override both.
+
+// compile-flags: -Z lint-config={{src-base}}/lint/lint-config/lints.txt -A dead-code
+
+fn dead() {}
+
+#[allow(unused_variables)]
+fn allowed() {
+    let x = 1;
+}
+
+fn main() {
+    let x = 1; //~ WARN unused variable: `x`
+    let mut y = 2; //~ ERROR variable does not need to be mutable
+    allowed();
+    let _ = y;
+}
@@ -0,0 +1,15 @@
+// edition:2018
+#![feature(must_not_suspend)]
+#![deny(must_not_suspend)]
+
+use std::sync::Mutex;
+
+async fn other() {}
+
+pub async fn uhoh(m: &Mutex<u32>) {
+    let _guard = m.lock().unwrap(); //~ ERROR `std::sync::MutexGuard` held across
+    other().await;
+}
+
+fn main() {
+}
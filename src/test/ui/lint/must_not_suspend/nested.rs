@@ -0,0 +1,45 @@
+// edition:2018
+#![feature(must_not_suspend)]
+#![deny(must_not_suspend)]
+
+#[must_not_suspend]
+struct Umm {
+    _i: i64
+}
+
+struct Wrapper {
+    _u: Umm,
+}
+
+fn bar() -> Umm {
+    Umm {
+        _i: 1
+    }
+}
+
+async fn other() {}
+
+pub async fn tuple() {
+    let _guard = (bar(), 1); //~ ERROR `Umm` in tuple element 0 held across
+    other().await;
+}
+
+pub async fn boxed() {
+    let _guard = Box::new(bar()); //~ ERROR boxed `Umm` held across
+    other().await;
+}
+
+pub async fn field() {
+    let _guard = Wrapper { _u: bar() }; //~ ERROR `Umm` in field `_u` of `Wrapper` held across
+    other().await;
+}
+
+pub async fn scoped() {
+    {
+        let _guard = bar();
+    }
+    other().await;
+}
+
+fn main() {
+}
@@ -0,0 +1,19 @@
+#![feature(native_link_modifiers)]
+
+#[link(name = "foo", kind = "static", modifiers = "whole-archive")]
+//~^ ERROR invalid linking modifier syntax `whole-archive`
+extern "C" {}
+
+#[link(name = "bar", kind = "static", modifiers = "+unknown")]
+//~^ ERROR unknown linking modifier `unknown`
+extern "C" {}
+
+#[link(name = "baz", kind = "dylib", modifiers = "+whole-archive")]
+//~^ ERROR linking modifier `whole-archive` is only compatible with the `static` linking kind
+extern "C" {}
+
+#[link(name = "qux", kind = "static", modifiers = "-bundle,+as-needed")]
+//~^ ERROR linking modifier `as-needed` is only compatible with the `dylib` linking kind
+extern "C" {}
+
+fn main() {}
@@ -0,0 +1,34 @@
+// Checks that `#[no_panic]` functions that may panic are rejected at monomorphization time,
+// with the call chain that leads to the panic.
+
+// build-fail
+
+#![feature(core_panic, no_panic)]
+#![crate_type = "lib"]
+
+#[no_panic]
+pub fn wrapping(a: u32, b: u32) -> u32 {
+    a.wrapping_add(b)
+}
+
+#[no_panic]
+pub fn index(a: &[u32], i: usize) -> u32 { //~ ERROR `index` is marked `#[no_panic]`, but can panic
+    a[i]
+}
+
+fn helper(x: u32) -> u32 {
+    if x == 0 {
+        core::panicking::panic("zero");
+    }
+    x
+}
+
+#[no_panic]
+pub fn chain(x: u32) -> u32 { //~ ERROR `chain` is marked `#[no_panic]`, but can panic
+    helper(x)
+}
+
+#[no_panic]
+pub fn call(f: fn()) { //~ ERROR `call` is marked `#[no_panic]`, but can panic
+    f()
+}
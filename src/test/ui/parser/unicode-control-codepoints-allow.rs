@@ -0,0 +1,40 @@
+// check-pass
+// The lints about text direction codepoints honour the lint attributes of the innermost item,
+// statement or expression containing the codepoints.
+
+#[allow(text_direction_codepoint_in_comment)]
+fn comment() {
+    // a‮b
+}
+
+#[allow(text_direction_codepoint_in_literal)]
+fn literal() -> &'static str {
+    "a‮b"
+}
+
+#[allow(text_direction_codepoint_in_comment, text_direction_codepoint_in_literal)]
+mod module {
+    /// a‮b
+    pub fn doc_comment() {}
+
+    pub const LITERAL: &str = r"a‮b";
+}
+
+struct S;
+
+impl S {
+    #[allow(text_direction_codepoint_in_literal)]
+    fn method(&self) -> &'static str {
+        "a‮b"
+    }
+}
+
+fn main() {
+    #[allow(text_direction_codepoint_in_literal)]
+    let _s = "a‮b";
+    comment();
+    literal();
+    module::doc_comment();
+    let _ = module::LITERAL;
+    S.method();
+}
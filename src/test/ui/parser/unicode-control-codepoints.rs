@@ -0,0 +1,9 @@
+fn main() {
+    // a‮b
+    //~^ ERROR unicode codepoint changing visible direction of text present in comment
+    let _s = "a‮b";
+    //~^ ERROR unicode codepoint changing visible direction of text present in literal
+    let _r = r"a‮b";
+    //~^ ERROR unicode codepoint changing visible direction of text present in literal
+    let _e = "a\u{202e}b";
+}
@@ -0,0 +1,49 @@
+// Verifies that the indirect calls `-Zsanitizer=cfi` checks accept their valid
+// targets: closures coerced to `fn` pointers, closures called through `dyn Fn*`,
+// and destructors called through vtables.
+//
+// needs-sanitizer-cfi
+// compile-flags: -Clto -Zsanitizer=cfi
+// run-pass
+
+use std::cell::Cell;
+
+trait Trait {
+    fn get(&self) -> u32;
+}
+
+struct Foo<'a>(&'a Cell<u32>);
+
+impl Trait for Foo<'_> {
+    fn get(&self) -> u32 {
+        self.0.get()
+    }
+}
+
+impl Drop for Foo<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+fn call(f: fn(u32) -> u32, x: u32) -> u32 {
+    f(x)
+}
+
+fn main() {
+    assert_eq!(call(|x| x + 1, 1), 2);
+    let f: fn(u32) -> u32 = |x| x * 2;
+    assert_eq!(call(f, 2), 4);
+
+    let y = 3;
+    let boxed: Box<dyn Fn(u32) -> u32> = Box::new(move |x| x + y);
+    assert_eq!(boxed(1), 4);
+    let once: Box<dyn FnOnce(u32) -> u32> = Box::new(|x| x + 5);
+    assert_eq!(once(1), 6);
+
+    let drops = Cell::new(0);
+    let t: Box<dyn Trait + '_> = Box::new(Foo(&drops));
+    assert_eq!(t.get(), 0);
+    drop(t);
+    assert_eq!(drops.get(), 1);
+}
@@ -0,0 +1,6 @@
+// compile-flags: -Z sanitizer=cfi --target x86_64-unknown-linux-gnu
+// error-pattern: error: `-Zsanitizer=cfi` requires `-Clto`
+
+#![feature(no_core)]
+#![no_core]
+#![no_main]
@@ -0,0 +1,20 @@
+#![feature(stack_limit)]
+
+#[stack_limit = "1024"] //~ ERROR illegal stack limit format in `stack_limit`
+fn string() {}
+
+#[stack_limit = 1024u32] //~ ERROR illegal stack limit format in `stack_limit`
+fn suffixed() {}
+
+#[stack_limit = 1024] //~ ERROR attribute should be applied to a function body
+struct NotAFunction;
+
+trait Trait {
+    #[stack_limit = 1024] //~ ERROR attribute should be applied to a function body
+    fn required();
+}
+
+#[stack_limit = 1024]
+fn valid() {}
+
+fn main() {}
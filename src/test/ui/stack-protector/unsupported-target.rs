@@ -0,0 +1,6 @@
+// check-pass
+// compile-flags: -Z stack-protector=all --target nvptx64-nvidia-cuda --crate-type lib
+// needs-llvm-components: nvptx
+
+#![feature(no_core)]
+#![no_core]
@@ -47,6 +47,7 @@ impl EarlyProps {
         let has_lsan = util::LSAN_SUPPORTED_TARGETS.contains(&&*config.target);
         let has_msan = util::MSAN_SUPPORTED_TARGETS.contains(&&*config.target);
         let has_tsan = util::TSAN_SUPPORTED_TARGETS.contains(&&*config.target);
+        let has_cfi = util::CFI_SUPPORTED_TARGETS.contains(&&*config.target);
 
         iter_header(testfile, None, rdr, &mut |ln| {
             // we should check if any only-<platform> exists and if it exists
@@ -68,6 +69,14 @@ impl EarlyProps {
                     props.ignore = true;
                 }
 
+                if let Some(needed) = config.parse_name_value_directive(ln, "needs-llvm-components")
+                {
+                    let components: Vec<_> = config.llvm_components.split_whitespace().collect();
+                    if !needed.split_whitespace().all(|needed| components.contains(&needed)) {
+                        props.ignore = true;
+                    }
+                }
+
                 if config.run_clang_based_tests_with.is_none()
                     && config.parse_needs_matching_clang(ln)
                 {
@@ -100,6 +109,10 @@ impl EarlyProps {
                     props.ignore = true;
                 }
 
+                if !has_cfi && config.parse_name_directive(ln, "needs-sanitizer-cfi") {
+                    props.ignore = true;
+                }
+
                 if config.target == "wasm32-unknown-unknown" && config.parse_check_run_results(ln) {
                     props.ignore = true;
                 }
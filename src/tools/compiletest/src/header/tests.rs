@@ -212,6 +212,7 @@ fn sanitizers() {
     assert!(!parse_rs(&config, "// needs-sanitizer-leak").ignore);
     assert!(!parse_rs(&config, "// needs-sanitizer-memory").ignore);
     assert!(!parse_rs(&config, "// needs-sanitizer-thread").ignore);
+    assert!(!parse_rs(&config, "// needs-sanitizer-cfi").ignore);
 
     // Target that doesn't support sanitizers:
     config.target = "wasm32-unknown-emscripten".to_owned();
@@ -219,4 +220,16 @@ fn sanitizers() {
     assert!(parse_rs(&config, "// needs-sanitizer-leak").ignore);
     assert!(parse_rs(&config, "// needs-sanitizer-memory").ignore);
     assert!(parse_rs(&config, "// needs-sanitizer-thread").ignore);
+    assert!(parse_rs(&config, "// needs-sanitizer-cfi").ignore);
+}
+
+#[test]
+fn llvm_components() {
+    let mut config = config();
+
+    config.llvm_components = "x86 arm".to_owned();
+    assert!(!parse_rs(&config, "// needs-llvm-components: x86").ignore);
+    assert!(!parse_rs(&config, "// needs-llvm-components: arm x86").ignore);
+    assert!(parse_rs(&config, "// needs-llvm-components: nvptx").ignore);
+    assert!(parse_rs(&config, "// needs-llvm-components: x86 nvptx").ignore);
 }